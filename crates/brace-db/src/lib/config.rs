@@ -1,38 +1,248 @@
 use std::error::Error;
-use std::net::Ipv4Addr;
+use std::fs::read;
+use std::path::PathBuf;
 
+use failure::format_err;
+use native_tls::{Certificate, TlsConnector};
 use postgres::params::{ConnectParams, Host, IntoConnectParams};
+use r2d2_postgres::TlsMode;
 use serde::{Deserialize, Serialize};
 
+use crate::tls::NativeTls;
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(default)]
 pub struct DatabaseConfig {
-    pub host: Ipv4Addr,
+    pub url: Option<String>,
+    pub host: String,
     pub port: u16,
     pub username: String,
     pub password: String,
     pub database: String,
+    pub migrate: bool,
+    pub pool: DatabasePoolConfig,
+    pub tls: DatabaseTlsConfig,
 }
 
 impl Default for DatabaseConfig {
     fn default() -> Self {
         Self {
-            host: Ipv4Addr::new(127, 0, 0, 1),
+            url: None,
+            host: "127.0.0.1".into(),
             port: 5432,
             username: "postgres".into(),
             password: "postgres".into(),
             database: "postgres".into(),
+            migrate: false,
+            pool: DatabasePoolConfig::default(),
+            tls: DatabaseTlsConfig::default(),
+        }
+    }
+}
+
+impl DatabaseConfig {
+    pub fn validate(&self) -> Result<(), failure::Error> {
+        if self.pool.threads == 0 {
+            return Err(format_err!("database.pool.threads must be at least 1"));
+        }
+
+        if self.pool.max_size == 0 {
+            return Err(format_err!("database.pool.max_size must be at least 1"));
+        }
+
+        if let Some(min_idle) = self.pool.min_idle {
+            if min_idle > self.pool.max_size {
+                return Err(format_err!(
+                    "database.pool.min_idle ({}) must not be greater than database.pool.max_size ({})",
+                    min_idle,
+                    self.pool.max_size
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn host(&self) -> Host {
+        if self.host.starts_with('/') {
+            Host::Unix(PathBuf::from(&self.host))
+        } else {
+            Host::Tcp(
+                self.host
+                    .trim_start_matches('[')
+                    .trim_end_matches(']')
+                    .to_string(),
+            )
         }
     }
 }
 
 impl IntoConnectParams for DatabaseConfig {
     fn into_connect_params(self) -> Result<ConnectParams, Box<dyn Error + Sync + Send>> {
+        if let Some(ref url) = self.url {
+            return url.as_str().into_connect_params();
+        }
+
         let mut builder = ConnectParams::builder();
 
+        builder.port(self.port);
         builder.user(&self.username, Some(&self.password));
         builder.database(&self.database);
 
-        Ok(builder.build(Host::Tcp(self.host.to_string())))
+        Ok(builder.build(self.host()))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct DatabasePoolConfig {
+    pub max_size: u32,
+    pub min_idle: Option<u32>,
+    pub timeout: u64,
+    pub threads: usize,
+}
+
+impl Default for DatabasePoolConfig {
+    fn default() -> Self {
+        Self {
+            max_size: 10,
+            min_idle: None,
+            timeout: 30,
+            threads: 3,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct DatabaseTlsConfig {
+    pub mode: DatabaseTlsMode,
+    pub ca_file: Option<PathBuf>,
+}
+
+impl DatabaseTlsConfig {
+    pub fn to_tls_mode(&self) -> Result<TlsMode, failure::Error> {
+        match self.mode {
+            DatabaseTlsMode::Disable => Ok(TlsMode::None),
+            DatabaseTlsMode::Prefer => Ok(TlsMode::Prefer(Box::new(self.handshake()?))),
+            DatabaseTlsMode::Require => Ok(TlsMode::Require(Box::new(self.handshake()?))),
+        }
+    }
+
+    fn handshake(&self) -> Result<NativeTls, failure::Error> {
+        let mut builder = TlsConnector::builder();
+
+        if let Some(ref path) = self.ca_file {
+            builder.add_root_certificate(Certificate::from_pem(&read(path)?)?);
+        }
+
+        Ok(NativeTls::new(builder.build()?))
+    }
+}
+
+impl Default for DatabaseTlsConfig {
+    fn default() -> Self {
+        Self {
+            mode: DatabaseTlsMode::Disable,
+            ca_file: None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DatabaseTlsMode {
+    Disable,
+    Prefer,
+    Require,
+}
+
+#[cfg(test)]
+mod tests {
+    use postgres::params::{Host, IntoConnectParams};
+
+    use super::DatabaseConfig;
+
+    #[test]
+    fn test_validate_pool() {
+        let mut conf = DatabaseConfig::default();
+
+        assert!(conf.validate().is_ok());
+
+        conf.pool.threads = 0;
+
+        assert!(conf.validate().is_err());
+
+        conf.pool.threads = 1;
+        conf.pool.min_idle = Some(conf.pool.max_size + 1);
+
+        assert!(conf.validate().is_err());
+
+        conf.pool.min_idle = Some(conf.pool.max_size);
+
+        assert!(conf.validate().is_ok());
+    }
+
+    #[test]
+    fn test_connect_params_fields() {
+        let mut conf = DatabaseConfig::default();
+
+        conf.host = "db.internal".to_string();
+        conf.port = 5433;
+
+        let params = conf.into_connect_params().unwrap();
+
+        assert_eq!(params.port(), 5433);
+        assert_eq!(params.database(), Some("postgres"));
+
+        match params.host() {
+            Host::Tcp(host) => assert_eq!(host, "db.internal"),
+            Host::Unix(_) => panic!("expected a tcp host"),
+        }
+    }
+
+    #[test]
+    fn test_connect_params_ipv6() {
+        let mut conf = DatabaseConfig::default();
+
+        conf.host = "[::1]".to_string();
+
+        match conf.into_connect_params().unwrap().host() {
+            Host::Tcp(host) => assert_eq!(host, "::1"),
+            Host::Unix(_) => panic!("expected a tcp host"),
+        }
+    }
+
+    #[test]
+    fn test_connect_params_socket() {
+        let mut conf = DatabaseConfig::default();
+
+        conf.host = "/var/run/postgresql".to_string();
+
+        match conf.into_connect_params().unwrap().host() {
+            Host::Unix(path) => assert_eq!(path.to_str(), Some("/var/run/postgresql")),
+            Host::Tcp(_) => panic!("expected a unix socket"),
+        }
+    }
+
+    #[test]
+    fn test_connect_params_url() {
+        let mut conf = DatabaseConfig::default();
+
+        conf.url = Some("postgres://brace@db:6543".to_string());
+        conf.host = "ignored".to_string();
+        conf.database = "fallback".to_string();
+
+        let params = conf.into_connect_params().unwrap();
+
+        assert_eq!(params.port(), 6543);
+        assert_eq!(params.database(), None);
+        assert_eq!(params.user().unwrap().name(), "brace");
+        assert_eq!(params.user().unwrap().password(), None);
+
+        match params.host() {
+            Host::Tcp(host) => assert_eq!(host, "db"),
+            Host::Unix(_) => panic!("expected a tcp host"),
+        }
     }
 }
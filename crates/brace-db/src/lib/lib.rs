@@ -1,23 +1,37 @@
 use std::ops::Deref;
+use std::time::Duration;
 
 use actix::{Actor, Addr, SyncArbiter, SyncContext};
 use failure::Error;
 use r2d2::Pool;
-use r2d2_postgres::{PostgresConnectionManager, TlsMode};
+use r2d2_postgres::PostgresConnectionManager;
 
 pub use self::config::DatabaseConfig;
+pub use self::schema::Migration;
+pub use self::transaction::transaction;
 
 pub mod config;
+pub mod schema;
+pub mod tls;
+pub mod transaction;
 
 #[derive(Clone)]
 pub struct Database(pub Addr<DatabaseInner>);
 
 impl Database {
     pub fn from_config(conf: DatabaseConfig) -> Result<Self, Error> {
-        let manager = PostgresConnectionManager::new(conf, TlsMode::None)?;
-        let pool = Pool::new(manager)?;
-
-        Ok(Self(SyncArbiter::start(3, move || {
+        conf.validate()?;
+
+        let tls = conf.tls.to_tls_mode()?;
+        let opts = conf.pool.clone();
+        let manager = PostgresConnectionManager::new(conf, tls)?;
+        let pool = Pool::builder()
+            .max_size(opts.max_size)
+            .min_idle(opts.min_idle)
+            .connection_timeout(Duration::from_secs(opts.timeout))
+            .build(manager)?;
+
+        Ok(Self(SyncArbiter::start(opts.threads, move || {
             DatabaseInner(pool.clone())
         })))
     }
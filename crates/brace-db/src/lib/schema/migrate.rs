@@ -0,0 +1,42 @@
+use actix::{Handler, Message};
+use failure::{format_err, Error};
+use futures::future::Future;
+
+use super::{apply, prepare, Migration};
+use crate::{Database, DatabaseInner};
+
+pub fn migrate(
+    database: &Database,
+    migrations: Vec<Migration>,
+) -> impl Future<Item = Vec<Migration>, Error = Error> {
+    database
+        .send(Migrate(migrations))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct Migrate(pub Vec<Migration>);
+
+impl Message for Migrate {
+    type Result = Result<Vec<Migration>, Error>;
+}
+
+impl Handler<Migrate> for DatabaseInner {
+    type Result = Result<Vec<Migration>, Error>;
+
+    fn handle(&mut self, msg: Migrate, _: &mut Self::Context) -> Self::Result {
+        self.transaction(|trans| {
+            let applied = prepare(trans)?;
+            let mut migrations = Vec::new();
+
+            for migration in msg.0 {
+                if !applied.contains_key(&migration.key()) {
+                    apply(trans, &migration)?;
+                    migrations.push(migration);
+                }
+            }
+
+            Ok(migrations)
+        })
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use failure::Error;
+use postgres::transaction::Transaction;
+use postgres::GenericConnection;
+
+pub mod migrate;
+pub mod reset;
+pub mod rollback;
+pub mod status;
+
+static LOCK_QUERY: &str = r#"
+    SELECT pg_advisory_xact_lock($1)
+"#;
+
+static CREATE_QUERY: &str = r#"
+    CREATE TABLE IF NOT EXISTS schema_migrations (
+        module text NOT NULL CHECK (module <> ''),
+        name text NOT NULL CHECK (name <> ''),
+        applied timestamp with time zone NOT NULL DEFAULT now(),
+        PRIMARY KEY (module, name)
+    )
+"#;
+
+static EXISTS_QUERY: &str = r#"
+    SELECT to_regclass('schema_migrations') IS NOT NULL
+"#;
+
+static SELECT_QUERY: &str = r#"
+    SELECT module, name, applied
+    FROM schema_migrations
+"#;
+
+static INSERT_QUERY: &str = r#"
+    INSERT INTO schema_migrations (module, name)
+    VALUES ($1, $2)
+"#;
+
+static DELETE_QUERY: &str = r#"
+    DELETE FROM schema_migrations *
+    WHERE module = $1 AND name = $2
+"#;
+
+const LOCK_KEY: i64 = 0x6272_6163_6500;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Migration {
+    pub module: &'static str,
+    pub name: &'static str,
+    pub up: &'static str,
+    pub down: &'static str,
+}
+
+impl Migration {
+    pub fn new(
+        module: &'static str,
+        name: &'static str,
+        up: &'static str,
+        down: &'static str,
+    ) -> Self {
+        Self {
+            module,
+            name,
+            up,
+            down,
+        }
+    }
+
+    fn key(&self) -> (String, String) {
+        (self.module.to_owned(), self.name.to_owned())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MigrationStatus {
+    pub migration: Migration,
+    pub applied: Option<DateTime<Utc>>,
+}
+
+type Applied = HashMap<(String, String), DateTime<Utc>>;
+
+fn prepare(trans: &Transaction) -> Result<Applied, Error> {
+    trans.execute(LOCK_QUERY, &[&LOCK_KEY])?;
+    trans.batch_execute(CREATE_QUERY)?;
+
+    load(trans)
+}
+
+fn load(conn: &dyn GenericConnection) -> Result<Applied, Error> {
+    if !conn.query(EXISTS_QUERY, &[])?.get(0).get::<_, bool>(0) {
+        return Ok(Applied::new());
+    }
+
+    let rows = conn.query(SELECT_QUERY, &[])?;
+
+    Ok(rows
+        .iter()
+        .map(|row| ((row.get(0), row.get(1)), row.get(2)))
+        .collect())
+}
+
+fn apply(trans: &Transaction, migration: &Migration) -> Result<(), Error> {
+    trans.batch_execute(migration.up)?;
+    trans.execute(INSERT_QUERY, &[&migration.module, &migration.name])?;
+
+    Ok(())
+}
+
+fn revert(trans: &Transaction, migration: &Migration) -> Result<(), Error> {
+    trans.batch_execute(migration.down)?;
+    trans.execute(DELETE_QUERY, &[&migration.module, &migration.name])?;
+
+    Ok(())
+}
+
+fn applied_in_order(migrations: Vec<Migration>, applied: &Applied) -> Vec<Migration> {
+    let mut list = migrations
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, migration)| {
+            applied
+                .get(&migration.key())
+                .map(|time| (*time, index, migration))
+        })
+        .collect::<Vec<(DateTime<Utc>, usize, Migration)>>();
+
+    list.sort_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+    list.into_iter()
+        .map(|(_, _, migration)| migration)
+        .collect()
+}
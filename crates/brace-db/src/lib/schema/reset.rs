@@ -0,0 +1,41 @@
+use actix::{Handler, Message};
+use failure::{format_err, Error};
+use futures::future::Future;
+
+use super::{applied_in_order, prepare, revert, Migration};
+use crate::{Database, DatabaseInner};
+
+pub fn reset(
+    database: &Database,
+    migrations: Vec<Migration>,
+) -> impl Future<Item = Vec<Migration>, Error = Error> {
+    database
+        .send(Reset(migrations))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct Reset(pub Vec<Migration>);
+
+impl Message for Reset {
+    type Result = Result<Vec<Migration>, Error>;
+}
+
+impl Handler<Reset> for DatabaseInner {
+    type Result = Result<Vec<Migration>, Error>;
+
+    fn handle(&mut self, msg: Reset, _: &mut Self::Context) -> Self::Result {
+        self.transaction(|trans| {
+            let applied = prepare(trans)?;
+            let mut migrations = applied_in_order(msg.0, &applied);
+
+            migrations.reverse();
+
+            for migration in migrations.iter() {
+                revert(trans, migration)?;
+            }
+
+            Ok(migrations)
+        })
+    }
+}
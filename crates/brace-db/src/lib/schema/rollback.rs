@@ -0,0 +1,39 @@
+use actix::{Handler, Message};
+use failure::{format_err, Error};
+use futures::future::Future;
+
+use super::{applied_in_order, prepare, revert, Migration};
+use crate::{Database, DatabaseInner};
+
+pub fn rollback(
+    database: &Database,
+    migrations: Vec<Migration>,
+) -> impl Future<Item = Option<Migration>, Error = Error> {
+    database
+        .send(Rollback(migrations))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct Rollback(pub Vec<Migration>);
+
+impl Message for Rollback {
+    type Result = Result<Option<Migration>, Error>;
+}
+
+impl Handler<Rollback> for DatabaseInner {
+    type Result = Result<Option<Migration>, Error>;
+
+    fn handle(&mut self, msg: Rollback, _: &mut Self::Context) -> Self::Result {
+        self.transaction(|trans| {
+            let applied = prepare(trans)?;
+            let migration = applied_in_order(msg.0, &applied).pop();
+
+            if let Some(ref migration) = migration {
+                revert(trans, migration)?;
+            }
+
+            Ok(migration)
+        })
+    }
+}
@@ -0,0 +1,40 @@
+use actix::{Handler, Message};
+use failure::{format_err, Error};
+use futures::future::Future;
+
+use super::{load, Migration, MigrationStatus};
+use crate::{Database, DatabaseInner};
+
+pub fn status(
+    database: &Database,
+    migrations: Vec<Migration>,
+) -> impl Future<Item = Vec<MigrationStatus>, Error = Error> {
+    database
+        .send(Status(migrations))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct Status(pub Vec<Migration>);
+
+impl Message for Status {
+    type Result = Result<Vec<MigrationStatus>, Error>;
+}
+
+impl Handler<Status> for DatabaseInner {
+    type Result = Result<Vec<MigrationStatus>, Error>;
+
+    fn handle(&mut self, msg: Status, _: &mut Self::Context) -> Self::Result {
+        let conn = self.0.get()?;
+        let applied = load(&*conn)?;
+
+        Ok(msg
+            .0
+            .into_iter()
+            .map(|migration| MigrationStatus {
+                applied: applied.get(&migration.key()).cloned(),
+                migration,
+            })
+            .collect())
+    }
+}
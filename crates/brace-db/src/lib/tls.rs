@@ -0,0 +1,64 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read, Write};
+
+use native_tls::TlsConnector;
+use postgres::tls::{Stream, TlsHandshake, TlsStream};
+
+pub struct NativeTls(TlsConnector);
+
+impl NativeTls {
+    pub fn new(connector: TlsConnector) -> Self {
+        NativeTls(connector)
+    }
+}
+
+impl fmt::Debug for NativeTls {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("NativeTls").finish()
+    }
+}
+
+impl TlsHandshake for NativeTls {
+    fn tls_handshake(
+        &self,
+        domain: &str,
+        stream: Stream,
+    ) -> Result<Box<dyn TlsStream>, Box<dyn Error + Sync + Send>> {
+        let stream = self
+            .0
+            .connect(domain, stream)
+            .map_err(|err| err.to_string())?;
+
+        Ok(Box::new(NativeTlsStream(stream)))
+    }
+}
+
+#[derive(Debug)]
+struct NativeTlsStream(native_tls::TlsStream<Stream>);
+
+impl Read for NativeTlsStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl Write for NativeTlsStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl TlsStream for NativeTlsStream {
+    fn get_ref(&self) -> &Stream {
+        self.0.get_ref()
+    }
+
+    fn get_mut(&mut self) -> &mut Stream {
+        self.0.get_mut()
+    }
+}
@@ -0,0 +1,54 @@
+use actix::{Handler, Message};
+use failure::{format_err, Error};
+use futures::future::Future;
+use postgres::transaction::Transaction;
+
+use crate::{Database, DatabaseInner};
+
+pub fn transaction<F, T>(database: &Database, func: F) -> impl Future<Item = T, Error = Error>
+where
+    F: FnOnce(&Transaction) -> Result<T, Error> + Send + 'static,
+    T: Send + 'static,
+{
+    database
+        .send(Transact(func))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct Transact<F>(pub F);
+
+impl<F, T> Message for Transact<F>
+where
+    F: FnOnce(&Transaction) -> Result<T, Error>,
+    T: 'static,
+{
+    type Result = Result<T, Error>;
+}
+
+impl<F, T> Handler<Transact<F>> for DatabaseInner
+where
+    F: FnOnce(&Transaction) -> Result<T, Error>,
+    T: 'static,
+{
+    type Result = Result<T, Error>;
+
+    fn handle(&mut self, msg: Transact<F>, _: &mut Self::Context) -> Self::Result {
+        self.transaction(msg.0)
+    }
+}
+
+impl DatabaseInner {
+    pub fn transaction<F, T>(&self, func: F) -> Result<T, Error>
+    where
+        F: FnOnce(&Transaction) -> Result<T, Error>,
+    {
+        let conn = self.0.get()?;
+        let trans = conn.transaction()?;
+        let res = func(&trans)?;
+
+        trans.commit()?;
+
+        Ok(res)
+    }
+}
@@ -1,8 +1,13 @@
 use actix::System;
 use actix::{Handler, Message};
+use failure::format_err;
 use futures::future::lazy;
 
-use brace_db::{Database, DatabaseConfig, DatabaseInner};
+use brace_db::schema::migrate::migrate;
+use brace_db::schema::reset::reset;
+use brace_db::schema::rollback::rollback;
+use brace_db::schema::status::status;
+use brace_db::{transaction, Database, DatabaseConfig, DatabaseInner, Migration};
 
 struct Msg(i32);
 
@@ -23,6 +28,39 @@ impl Handler<Msg> for DatabaseInner {
     }
 }
 
+struct Execute(&'static str);
+
+impl Message for Execute {
+    type Result = Result<(), failure::Error>;
+}
+
+impl Handler<Execute> for DatabaseInner {
+    type Result = Result<(), failure::Error>;
+
+    fn handle(&mut self, msg: Execute, _: &mut Self::Context) -> Self::Result {
+        self.0.get()?.batch_execute(msg.0)?;
+
+        Ok(())
+    }
+}
+
+struct Exists(&'static str);
+
+impl Message for Exists {
+    type Result = Result<bool, failure::Error>;
+}
+
+impl Handler<Exists> for DatabaseInner {
+    type Result = Result<bool, failure::Error>;
+
+    fn handle(&mut self, msg: Exists, _: &mut Self::Context) -> Self::Result {
+        let pool = self.0.get()?;
+        let rows = pool.query("SELECT to_regclass($1) IS NOT NULL", &[&msg.0])?;
+
+        Ok(rows.get(0).get(0))
+    }
+}
+
 #[test]
 fn test_database_postgres() {
     let mut system = System::new("brace_test");
@@ -38,3 +76,130 @@ fn test_database_postgres() {
 
     assert_eq!(res.0, 5);
 }
+
+fn migrations() -> Vec<Migration> {
+    vec![
+        Migration::new(
+            "test",
+            "0001_create_foo",
+            "CREATE TABLE brace_test_foo (id INT4 PRIMARY KEY)",
+            "DROP TABLE brace_test_foo",
+        ),
+        Migration::new(
+            "test",
+            "0002_alter_foo",
+            "ALTER TABLE brace_test_foo ADD COLUMN name TEXT",
+            "ALTER TABLE brace_test_foo DROP COLUMN name",
+        ),
+    ]
+}
+
+#[test]
+fn test_database_migrations() {
+    let mut system = System::new("brace_test");
+    let database = Database::from_config(DatabaseConfig::default()).unwrap();
+
+    system
+        .block_on(database.send(Execute(
+            "ALTER TABLE IF EXISTS schema_migrations RENAME TO brace_test_schema_migrations",
+        )))
+        .unwrap()
+        .unwrap();
+
+    let res = system.block_on(status(&database, migrations())).unwrap();
+
+    assert!(res.iter().all(|status| status.applied.is_none()));
+    assert!(!system
+        .block_on(database.send(Exists("schema_migrations")))
+        .unwrap()
+        .unwrap());
+
+    system
+        .block_on(database.send(Execute(
+            "ALTER TABLE IF EXISTS brace_test_schema_migrations RENAME TO schema_migrations",
+        )))
+        .unwrap()
+        .unwrap();
+
+    let res = system.block_on(migrate(&database, migrations())).unwrap();
+
+    assert_eq!(res.len(), 2);
+
+    let res = system.block_on(migrate(&database, migrations())).unwrap();
+
+    assert_eq!(res.len(), 0);
+
+    let res = system.block_on(status(&database, migrations())).unwrap();
+
+    assert!(res.iter().all(|status| status.applied.is_some()));
+
+    let res = system.block_on(rollback(&database, migrations())).unwrap();
+
+    assert_eq!(res.unwrap().name, "0002_alter_foo");
+
+    let res = system.block_on(status(&database, migrations())).unwrap();
+
+    assert!(res[0].applied.is_some());
+    assert!(res[1].applied.is_none());
+
+    let res = system.block_on(migrate(&database, migrations())).unwrap();
+
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].name, "0002_alter_foo");
+
+    let res = system.block_on(reset(&database, migrations())).unwrap();
+
+    assert_eq!(res.len(), 2);
+    assert_eq!(res[0].name, "0002_alter_foo");
+    assert_eq!(res[1].name, "0001_create_foo");
+
+    let res = system.block_on(rollback(&database, migrations())).unwrap();
+
+    assert!(res.is_none());
+}
+
+#[test]
+fn test_database_transaction() {
+    let mut system = System::new("brace_test");
+    let database = Database::from_config(DatabaseConfig::default()).unwrap();
+
+    system
+        .block_on(transaction(&database, |trans| {
+            trans.execute(
+                "CREATE TABLE IF NOT EXISTS brace_test_trans (id INT4 PRIMARY KEY)",
+                &[],
+            )?;
+            trans.execute("DELETE FROM brace_test_trans", &[])?;
+
+            Ok(())
+        }))
+        .unwrap();
+
+    let res = system.block_on(transaction(&database, |trans| {
+        trans.execute("INSERT INTO brace_test_trans (id) VALUES (1)", &[])?;
+
+        Err::<(), _>(format_err!("Abort"))
+    }));
+
+    assert!(res.is_err());
+
+    let res = system
+        .block_on(transaction(&database, |trans| {
+            trans.execute("INSERT INTO brace_test_trans (id) VALUES (2)", &[])?;
+
+            let rows = trans.query("SELECT id FROM brace_test_trans", &[])?;
+
+            Ok(rows.iter().map(|row| row.get(0)).collect::<Vec<i32>>())
+        }))
+        .unwrap();
+
+    assert_eq!(res, vec![2]);
+
+    system
+        .block_on(transaction(&database, |trans| {
+            trans.execute("DROP TABLE brace_test_trans", &[])?;
+
+            Ok(())
+        }))
+        .unwrap();
+}
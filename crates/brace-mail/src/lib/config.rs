@@ -0,0 +1,120 @@
+use std::path::PathBuf;
+
+use failure::Error;
+use serde::{Deserialize, Serialize};
+
+use crate::transport::file::FileTransport;
+use crate::transport::log::LogTransport;
+use crate::transport::sendmail::SendmailTransport;
+use crate::transport::smtp::{SmtpSecurity, SmtpTransport};
+use crate::transport::Transport;
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct MailConfig {
+    pub from: String,
+    pub threads: usize,
+    pub transport: MailTransportConfig,
+}
+
+impl Default for MailConfig {
+    fn default() -> Self {
+        Self {
+            from: "brace@localhost".to_string(),
+            threads: 1,
+            transport: MailTransportConfig::Log,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum MailTransportConfig {
+    Smtp {
+        host: String,
+        #[serde(default = "default_smtp_port")]
+        port: u16,
+        #[serde(default)]
+        username: Option<String>,
+        #[serde(default)]
+        password: Option<String>,
+        #[serde(default)]
+        security: SmtpSecurity,
+    },
+    Sendmail {
+        #[serde(default = "default_sendmail_command")]
+        command: PathBuf,
+    },
+    File {
+        path: PathBuf,
+    },
+    Log,
+}
+
+impl MailTransportConfig {
+    pub fn build(&self) -> Result<Box<dyn Transport>, Error> {
+        Ok(match self {
+            MailTransportConfig::Smtp {
+                host,
+                port,
+                username,
+                password,
+                security,
+            } => Box::new(SmtpTransport {
+                host: host.clone(),
+                port: *port,
+                username: username.clone(),
+                password: password.clone(),
+                security: *security,
+            }),
+            MailTransportConfig::Sendmail { command } => Box::new(SendmailTransport {
+                command: command.clone(),
+            }),
+            MailTransportConfig::File { path } => Box::new(FileTransport::new(path)?),
+            MailTransportConfig::Log => Box::new(LogTransport),
+        })
+    }
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
+fn default_sendmail_command() -> PathBuf {
+    PathBuf::from("/usr/sbin/sendmail")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MailConfig, MailTransportConfig};
+    use crate::transport::smtp::SmtpSecurity;
+
+    #[test]
+    fn test_transport_config() {
+        let conf: MailConfig = toml::from_str(
+            r#"
+            from = "Brace <brace@domain.test>"
+
+            [transport]
+            type = "smtp"
+            host = "mail.domain.test"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            conf.transport,
+            MailTransportConfig::Smtp {
+                host: "mail.domain.test".to_string(),
+                port: 587,
+                username: None,
+                password: None,
+                security: SmtpSecurity::StartTls,
+            }
+        );
+        assert_eq!(
+            toml::from_str::<MailConfig>("").unwrap().transport,
+            MailTransportConfig::Log
+        );
+    }
+}
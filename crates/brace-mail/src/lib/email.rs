@@ -0,0 +1,90 @@
+use chrono::Utc;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Email {
+    pub from: String,
+    pub to: String,
+    pub subject: String,
+    pub html: String,
+}
+
+impl Email {
+    pub fn new<T, S, H>(to: T, subject: S, html: H) -> Self
+    where
+        T: Into<String>,
+        S: Into<String>,
+        H: Into<String>,
+    {
+        Self {
+            from: String::new(),
+            to: to.into(),
+            subject: subject.into(),
+            html: html.into(),
+        }
+    }
+
+    pub fn format(&self, id: &Uuid) -> String {
+        let body = self.html.lines().collect::<Vec<_>>().join("\r\n");
+
+        format!(
+            "From: {}\r\nTo: {}\r\nSubject: {}\r\nDate: {}\r\nMessage-ID: <{}@{}>\r\nMIME-Version: 1.0\r\nContent-Type: text/html; charset=utf-8\r\nContent-Transfer-Encoding: 8bit\r\n\r\n{}\r\n",
+            header(&self.from),
+            header(&self.to),
+            header(&self.subject),
+            Utc::now().to_rfc2822(),
+            id,
+            domain(&self.from),
+            body,
+        )
+    }
+}
+
+pub fn address(mailbox: &str) -> &str {
+    match (mailbox.find('<'), mailbox.rfind('>')) {
+        (Some(start), Some(end)) if start < end => &mailbox[start + 1..end],
+        _ => mailbox.trim(),
+    }
+}
+
+fn domain(mailbox: &str) -> &str {
+    match address(mailbox).rfind('@') {
+        Some(index) => &address(mailbox)[index + 1..],
+        None => "localhost",
+    }
+}
+
+fn header(value: &str) -> String {
+    value.replace(|c| c == '\r' || c == '\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::{address, Email};
+
+    #[test]
+    fn test_address() {
+        assert_eq!(address("Brace <brace@domain.test>"), "brace@domain.test");
+        assert_eq!(address(" brace@domain.test "), "brace@domain.test");
+    }
+
+    #[test]
+    fn test_format() {
+        let email = Email {
+            from: "Brace <brace@domain.test>".to_string(),
+            ..Email::new(
+                "user@domain.test",
+                "Hello\r\nBcc: x@domain.test",
+                "<p>Hi</p>\n.",
+            )
+        };
+        let message = email.format(&Uuid::nil());
+
+        assert!(message.starts_with("From: Brace <brace@domain.test>\r\nTo: user@domain.test\r\n"));
+        assert!(message.contains("Subject: Hello  Bcc: x@domain.test\r\n"));
+        assert!(message.contains("Message-ID: <00000000-0000-0000-0000-000000000000@domain.test>"));
+        assert!(message.ends_with("\r\n\r\n<p>Hi</p>\r\n.\r\n"));
+    }
+}
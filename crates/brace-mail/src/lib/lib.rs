@@ -0,0 +1,73 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+use actix::{Actor, Addr, Handler, Message, SyncArbiter, SyncContext};
+use failure::{format_err, Error};
+use futures::future::Future;
+
+pub use self::config::MailConfig;
+pub use self::email::Email;
+pub use self::transport::Transport;
+
+pub mod config;
+pub mod email;
+pub mod transport;
+
+#[derive(Clone)]
+pub struct Mailer(pub Addr<MailerInner>);
+
+impl Mailer {
+    pub fn from_config(conf: MailConfig) -> Result<Self, Error> {
+        let transport: Arc<dyn Transport> = Arc::from(conf.transport.build()?);
+        let from = conf.from;
+
+        Ok(Self(SyncArbiter::start(conf.threads, move || {
+            MailerInner {
+                from: from.clone(),
+                transport: transport.clone(),
+            }
+        })))
+    }
+
+    pub fn send(&self, email: Email) -> impl Future<Item = (), Error = Error> {
+        self.0
+            .send(SendEmail(email))
+            .map_err(|err| format_err!("{}", err))
+            .and_then(|res| res)
+    }
+}
+
+impl Deref for Mailer {
+    type Target = Addr<MailerInner>;
+
+    fn deref(&self) -> &Addr<MailerInner> {
+        &self.0
+    }
+}
+
+pub struct MailerInner {
+    pub from: String,
+    pub transport: Arc<dyn Transport>,
+}
+
+impl Actor for MailerInner {
+    type Context = SyncContext<Self>;
+}
+
+pub struct SendEmail(pub Email);
+
+impl Message for SendEmail {
+    type Result = Result<(), Error>;
+}
+
+impl Handler<SendEmail> for MailerInner {
+    type Result = Result<(), Error>;
+
+    fn handle(&mut self, mut msg: SendEmail, _: &mut Self::Context) -> Self::Result {
+        if msg.0.from.is_empty() {
+            msg.0.from = self.from.clone();
+        }
+
+        self.transport.send(&msg.0)
+    }
+}
@@ -0,0 +1,70 @@
+use std::fs::{create_dir_all, write};
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use failure::Error;
+use uuid::Uuid;
+
+use super::Transport;
+use crate::email::Email;
+
+pub struct FileTransport {
+    path: PathBuf,
+}
+
+impl FileTransport {
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        create_dir_all(path.as_ref())?;
+
+        Ok(Self {
+            path: path.as_ref().to_path_buf(),
+        })
+    }
+}
+
+impl Transport for FileTransport {
+    fn send(&self, email: &Email) -> Result<(), Error> {
+        let id = Uuid::new_v4();
+        let name = format!("{}-{}.eml", Utc::now().format("%Y%m%d%H%M%S"), id);
+
+        write(self.path.join(name), email.format(&id))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+    use std::fs::{read_dir, read_to_string, remove_dir_all};
+
+    use uuid::Uuid;
+
+    use super::FileTransport;
+    use crate::email::Email;
+    use crate::transport::Transport;
+
+    #[test]
+    fn test_file_transport() {
+        let path = temp_dir().join(format!("brace-mail-{}", Uuid::new_v4()));
+        let transport = FileTransport::new(&path).unwrap();
+        let email = Email {
+            from: "brace@domain.test".to_string(),
+            ..Email::new("user@domain.test", "Hello", "<p>Hi</p>")
+        };
+
+        transport.send(&email).unwrap();
+
+        let files = read_dir(&path)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(files.len(), 1);
+        assert!(read_to_string(files[0].path())
+            .unwrap()
+            .contains("To: user@domain.test\r\n"));
+
+        remove_dir_all(&path).unwrap();
+    }
+}
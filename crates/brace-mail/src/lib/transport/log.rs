@@ -0,0 +1,18 @@
+use ::log::info;
+use failure::Error;
+
+use super::Transport;
+use crate::email::Email;
+
+pub struct LogTransport;
+
+impl Transport for LogTransport {
+    fn send(&self, email: &Email) -> Result<(), Error> {
+        info!(
+            "Email to {} with subject \"{}\":\n{}",
+            email.to, email.subject, email.html
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use failure::{format_err, Error};
+use lettre::{EmailAddress, Envelope, SendableEmail};
+use uuid::Uuid;
+
+use crate::email::{address, Email};
+
+pub mod file;
+pub mod log;
+pub mod sendmail;
+pub mod smtp;
+
+pub trait Transport: Send + Sync {
+    fn send(&self, email: &Email) -> Result<(), Error>;
+}
+
+pub(crate) fn sendable(email: &Email) -> Result<SendableEmail, Error> {
+    let id = Uuid::new_v4();
+    let envelope = Envelope::new(
+        Some(EmailAddress::new(address(&email.from).to_string())?),
+        vec![EmailAddress::new(address(&email.to).to_string())?],
+    )
+    .map_err(|err| format_err!("{}", err))?;
+
+    Ok(SendableEmail::new(
+        envelope,
+        id.to_string(),
+        email.format(&id).into_bytes(),
+    ))
+}
@@ -0,0 +1,20 @@
+use std::path::PathBuf;
+
+use failure::{format_err, Error};
+use lettre::sendmail::SendmailTransport as Sendmail;
+use lettre::Transport as _;
+
+use super::{sendable, Transport};
+use crate::email::Email;
+
+pub struct SendmailTransport {
+    pub command: PathBuf,
+}
+
+impl Transport for SendmailTransport {
+    fn send(&self, email: &Email) -> Result<(), Error> {
+        Sendmail::new_with_command(self.command.to_string_lossy())
+            .send(sendable(email)?)
+            .map_err(|err| format_err!("{}", err))
+    }
+}
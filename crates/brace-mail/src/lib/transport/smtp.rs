@@ -0,0 +1,66 @@
+use failure::{format_err, Error};
+use lettre::smtp::authentication::Credentials;
+use lettre::smtp::{ClientSecurity, SmtpClient};
+use lettre::{ClientTlsParameters, Transport as _};
+use native_tls::TlsConnector;
+use serde::{Deserialize, Serialize};
+
+use super::{sendable, Transport};
+use crate::email::Email;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpSecurity {
+    None,
+    StartTls,
+    Tls,
+}
+
+impl Default for SmtpSecurity {
+    fn default() -> Self {
+        SmtpSecurity::StartTls
+    }
+}
+
+pub struct SmtpTransport {
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub security: SmtpSecurity,
+}
+
+impl SmtpTransport {
+    fn security(&self) -> Result<ClientSecurity, Error> {
+        let tls = || -> Result<ClientTlsParameters, Error> {
+            Ok(ClientTlsParameters::new(
+                self.host.clone(),
+                TlsConnector::builder().build()?,
+            ))
+        };
+
+        Ok(match self.security {
+            SmtpSecurity::None => ClientSecurity::None,
+            SmtpSecurity::StartTls => ClientSecurity::Required(tls()?),
+            SmtpSecurity::Tls => ClientSecurity::Wrapper(tls()?),
+        })
+    }
+}
+
+impl Transport for SmtpTransport {
+    fn send(&self, email: &Email) -> Result<(), Error> {
+        let mut client = SmtpClient::new((self.host.as_str(), self.port), self.security()?)
+            .map_err(|err| format_err!("{}", err))?;
+
+        if let (Some(username), Some(password)) = (&self.username, &self.password) {
+            client = client.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        client
+            .transport()
+            .send(sendable(email)?)
+            .map_err(|err| format_err!("{}", err))?;
+
+        Ok(())
+    }
+}
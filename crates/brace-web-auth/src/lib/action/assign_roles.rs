@@ -0,0 +1,74 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error, Fail};
+use futures::future::Future;
+use uuid::Uuid;
+
+use crate::action::list_roles::role;
+use crate::model::Role;
+
+static DELETE_QUERY: &str = r#"
+    DELETE FROM user_roles
+    WHERE "user" = $1
+"#;
+
+static INSERT_QUERY: &str = r#"
+    INSERT INTO user_roles ("user", role)
+    SELECT $1, id
+    FROM roles
+    WHERE name = $2
+"#;
+
+static SELECT_QUERY: &str = r#"
+    SELECT r.id, r.name, r.label, array_remove(array_agg(p.permission ORDER BY p.permission), NULL),
+           r.two_factor
+    FROM user_roles u
+    JOIN roles r ON r.id = u.role
+    LEFT JOIN role_permissions p ON p.role = r.id
+    WHERE u."user" = $1
+    GROUP BY r.id
+    ORDER BY r.name
+"#;
+
+#[derive(Debug, Fail, PartialEq)]
+pub enum RoleError {
+    #[fail(display = "There is no role named \"{}\"", _0)]
+    Unknown(String),
+}
+
+pub fn assign_roles(
+    database: &Database,
+    user: Uuid,
+    roles: Vec<String>,
+) -> impl Future<Item = Vec<Role>, Error = Error> {
+    database
+        .send(AssignRoles(user, roles))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct AssignRoles(pub Uuid, pub Vec<String>);
+
+impl Message for AssignRoles {
+    type Result = Result<Vec<Role>, Error>;
+}
+
+impl Handler<AssignRoles> for DatabaseInner {
+    type Result = Result<Vec<Role>, Error>;
+
+    fn handle(&mut self, msg: AssignRoles, _: &mut Self::Context) -> Self::Result {
+        self.transaction(|trans| {
+            trans.execute(DELETE_QUERY, &[&msg.0])?;
+
+            for name in msg.1.iter() {
+                if trans.execute(INSERT_QUERY, &[&msg.0, name])? == 0 {
+                    return Err(RoleError::Unknown(name.clone()).into());
+                }
+            }
+
+            let rows = trans.query(SELECT_QUERY, &[&msg.0])?;
+
+            Ok(rows.iter().map(|row| role(&row)).collect())
+        })
+    }
+}
@@ -0,0 +1,52 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+use uuid::Uuid;
+
+use crate::action::retrieve::user_from_row;
+use crate::model::{Claims, User};
+
+static QUERY: &str = r#"
+    SELECT id, email, password, created, updated, verified, approved, display_name, avatar, bio
+    FROM users
+    WHERE id = $1 AND verified AND approved AND NOT EXISTS (
+        SELECT 1
+        FROM revoked_tokens
+        WHERE jti = $2
+    )
+"#;
+
+pub fn authenticate(
+    database: &Database,
+    claims: Claims,
+) -> impl Future<Item = User, Error = Error> {
+    database
+        .send(Authenticate(claims))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct Authenticate(pub Claims);
+
+impl Message for Authenticate {
+    type Result = Result<User, Error>;
+}
+
+impl Handler<Authenticate> for DatabaseInner {
+    type Result = Result<User, Error>;
+
+    fn handle(&mut self, msg: Authenticate, _: &mut Self::Context) -> Self::Result {
+        let conn = self.0.get()?;
+        let user = msg.0.sub.parse::<Uuid>()?;
+        let rows = conn.query(QUERY, &[&user, &msg.0.jti])?;
+
+        if rows.is_empty() {
+            return Err(format_err!("Row not found"));
+        }
+
+        let row = rows.get(0);
+
+        Ok(user_from_row(&row))
+    }
+}
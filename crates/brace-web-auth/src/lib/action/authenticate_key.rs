@@ -0,0 +1,68 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+
+use crate::action::list_api_keys::scopes;
+use crate::action::retrieve::user_from_row;
+use crate::action::token::split;
+use crate::model::{Permission, User};
+use crate::util::{constant_eq, digest};
+
+static SELECT_QUERY: &str = r#"
+    SELECT u.id, u.email, u.password, u.created, u.updated, u.verified, u.approved,
+           u.display_name, u.avatar, u.bio, k.hash, k.scopes
+    FROM api_keys k
+    JOIN users u ON u.id = k."user"
+    WHERE k.id = $1 AND (k.expires IS NULL OR k.expires > now())
+    AND u.verified AND u.approved
+"#;
+
+static USED_QUERY: &str = r#"
+    UPDATE api_keys
+    SET last_used = now()
+    WHERE id = $1 AND (last_used IS NULL OR last_used < now() - interval '1 minute')
+"#;
+
+pub fn authenticate_key(
+    database: &Database,
+    key: String,
+) -> impl Future<Item = Option<(User, Option<Vec<Permission>>)>, Error = Error> {
+    database
+        .send(AuthenticateKey(key))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct AuthenticateKey(pub String);
+
+impl Message for AuthenticateKey {
+    type Result = Result<Option<(User, Option<Vec<Permission>>)>, Error>;
+}
+
+impl Handler<AuthenticateKey> for DatabaseInner {
+    type Result = Result<Option<(User, Option<Vec<Permission>>)>, Error>;
+
+    fn handle(&mut self, msg: AuthenticateKey, _: &mut Self::Context) -> Self::Result {
+        let (id, secret) = match split(&msg.0) {
+            Some(parts) => parts,
+            None => return Ok(None),
+        };
+        let conn = self.0.get()?;
+        let rows = conn.query(SELECT_QUERY, &[&id])?;
+
+        if rows.is_empty() {
+            return Ok(None);
+        }
+
+        let row = rows.get(0);
+
+        if !constant_eq(&digest(secret), &row.get::<_, String>(10)) {
+            return Ok(None);
+        }
+
+        conn.execute(USED_QUERY, &[&id])?;
+
+        Ok(Some((user_from_row(&row), scopes(row.get(11)))))
+    }
+}
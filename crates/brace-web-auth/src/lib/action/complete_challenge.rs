@@ -0,0 +1,154 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use chrono::{DateTime, Utc};
+use failure::{format_err, Error};
+use futures::future::Future;
+use postgres::transaction::Transaction;
+use uuid::Uuid;
+
+use crate::action::lockout::{fail, locked};
+use crate::action::retrieve::user_from_row;
+use crate::action::token::split;
+use crate::action::two_factor::check;
+use crate::config::LockoutConfig;
+use crate::model::{ChallengeAttempt, User};
+use crate::util::{constant_eq, digest};
+
+pub const MAX_ATTEMPTS: i32 = 5;
+
+static SELECT_QUERY: &str = r#"
+    SELECT hash, "user", expires, attempts, used
+    FROM login_challenges
+    WHERE id = $1
+    FOR UPDATE
+"#;
+
+static USE_QUERY: &str = r#"
+    UPDATE login_challenges
+    SET used = now()
+    WHERE id = $1
+"#;
+
+static ATTEMPT_QUERY: &str = r#"
+    UPDATE login_challenges
+    SET attempts = attempts + 1
+    WHERE id = $1
+"#;
+
+static USER_QUERY: &str = r#"
+    SELECT id, email, password, created, updated, verified, approved, display_name, avatar, bio
+    FROM users
+    WHERE id = $1
+"#;
+
+pub fn complete_challenge(
+    database: &Database,
+    challenge: String,
+    code: String,
+    address: Option<String>,
+    config: LockoutConfig,
+) -> impl Future<Item = ChallengeAttempt, Error = Error> {
+    database
+        .send(CompleteChallenge(challenge, code, address, config))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct CompleteChallenge(
+    pub String,
+    pub String,
+    pub Option<String>,
+    pub LockoutConfig,
+);
+
+impl Message for CompleteChallenge {
+    type Result = Result<ChallengeAttempt, Error>;
+}
+
+impl Handler<CompleteChallenge> for DatabaseInner {
+    type Result = Result<ChallengeAttempt, Error>;
+
+    fn handle(&mut self, msg: CompleteChallenge, _: &mut Self::Context) -> Self::Result {
+        self.transaction(|trans| {
+            let (address, config) = (msg.2.as_ref().map(String::as_str), &msg.3);
+            let (id, user) = match open(trans, &msg.0, address, config)? {
+                Ok(challenge) => challenge,
+                Err(attempt) => return Ok(attempt),
+            };
+
+            if !check(trans, user.id, &msg.1)? {
+                return reject(trans, id, user, address, config);
+            }
+
+            close(trans, id)?;
+
+            Ok(ChallengeAttempt::Success(user))
+        })
+    }
+}
+
+pub(crate) fn open(
+    trans: &Transaction,
+    challenge: &str,
+    address: Option<&str>,
+    config: &LockoutConfig,
+) -> Result<Result<(Uuid, User), ChallengeAttempt>, Error> {
+    let (id, secret) = match split(challenge) {
+        Some(parts) => parts,
+        None => return Ok(Err(ChallengeAttempt::Expired)),
+    };
+    let rows = trans.query(SELECT_QUERY, &[&id])?;
+
+    if rows.is_empty() {
+        return Ok(Err(ChallengeAttempt::Expired));
+    }
+
+    let row = rows.get(0);
+
+    if !constant_eq(&digest(secret), &row.get::<_, String>(0)) {
+        return Ok(Err(ChallengeAttempt::Expired));
+    }
+
+    let user: Uuid = row.get(1);
+    let expires: DateTime<Utc> = row.get(2);
+    let attempts: i32 = row.get(3);
+    let used: Option<DateTime<Utc>> = row.get(4);
+
+    if used.is_some() || expires < Utc::now() || attempts >= MAX_ATTEMPTS {
+        return Ok(Err(ChallengeAttempt::Expired));
+    }
+
+    let rows = trans.query(USER_QUERY, &[&user])?;
+
+    if rows.is_empty() {
+        return Ok(Err(ChallengeAttempt::Expired));
+    }
+
+    let user = user_from_row(&rows.get(0));
+
+    if let Some(remaining) = locked(trans, &user.email, address, config)? {
+        return Ok(Err(ChallengeAttempt::Locked(remaining)));
+    }
+
+    Ok(Ok((id, user)))
+}
+
+pub(crate) fn reject(
+    trans: &Transaction,
+    id: Uuid,
+    user: User,
+    address: Option<&str>,
+    config: &LockoutConfig,
+) -> Result<ChallengeAttempt, Error> {
+    trans.execute(ATTEMPT_QUERY, &[&id])?;
+
+    let locked = fail(trans, &user.email, address, config)?;
+
+    Ok(ChallengeAttempt::Failure(user, locked))
+}
+
+pub(crate) fn close(trans: &Transaction, id: Uuid) -> Result<(), Error> {
+    trans.execute(USE_QUERY, &[&id])?;
+
+    Ok(())
+}
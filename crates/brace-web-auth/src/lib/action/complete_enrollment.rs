@@ -0,0 +1,56 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+
+use crate::action::complete_challenge::{close, open, reject};
+use crate::action::enable_two_factor::enable;
+use crate::config::LockoutConfig;
+use crate::model::ChallengeAttempt;
+
+pub fn complete_enrollment(
+    database: &Database,
+    challenge: String,
+    code: String,
+    address: Option<String>,
+    config: LockoutConfig,
+) -> impl Future<Item = ChallengeAttempt, Error = Error> {
+    database
+        .send(CompleteEnrollment(challenge, code, address, config))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct CompleteEnrollment(
+    pub String,
+    pub String,
+    pub Option<String>,
+    pub LockoutConfig,
+);
+
+impl Message for CompleteEnrollment {
+    type Result = Result<ChallengeAttempt, Error>;
+}
+
+impl Handler<CompleteEnrollment> for DatabaseInner {
+    type Result = Result<ChallengeAttempt, Error>;
+
+    fn handle(&mut self, msg: CompleteEnrollment, _: &mut Self::Context) -> Self::Result {
+        self.transaction(|trans| {
+            let (address, config) = (msg.2.as_ref().map(String::as_str), &msg.3);
+            let (id, user) = match open(trans, &msg.0, address, config)? {
+                Ok(challenge) => challenge,
+                Err(attempt) => return Ok(attempt),
+            };
+
+            match enable(trans, user.id, &msg.1)? {
+                Some(codes) => {
+                    close(trans, id)?;
+
+                    Ok(ChallengeAttempt::Enrolled(user, codes))
+                }
+                None => reject(trans, id, user, address, config),
+            }
+        })
+    }
+}
@@ -3,13 +3,16 @@ use brace_db::{Database, DatabaseInner};
 use failure::{format_err, Error};
 use futures::future::Future;
 
+use crate::action::email::validate;
+use crate::action::retrieve::user_from_row;
 use crate::model::User;
 use crate::util::hash;
 
 static QUERY: &str = r#"
-    INSERT INTO users (id, email, password, created, updated)
-    VALUES ($1, $2, $3, $4, $5)
-    RETURNING id, email, password, created, updated
+    INSERT INTO users (id, email, password, created, updated, verified, approved, display_name,
+                       avatar, bio)
+    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+    RETURNING id, email, password, created, updated, verified, approved, display_name, avatar, bio
 "#;
 
 pub fn create(database: &Database, user: User) -> impl Future<Item = User, Error = Error> {
@@ -29,30 +32,32 @@ impl Handler<Create> for DatabaseInner {
     type Result = Result<User, Error>;
 
     fn handle(&mut self, msg: Create, _: &mut Self::Context) -> Self::Result {
-        let conn = self.0.get()?;
-        let rows = conn.query(
-            QUERY,
-            &[
-                &msg.0.id,
-                &msg.0.email,
-                &hash(&msg.0.password)?,
-                &msg.0.created,
-                &msg.0.updated,
-            ],
-        )?;
-
-        if rows.is_empty() {
-            return Err(format_err!("Row not returned"));
-        }
-
-        let row = rows.get(0);
-
-        Ok(User {
-            id: row.get(0),
-            email: row.get(1),
-            password: row.get(2),
-            created: row.get(3),
-            updated: row.get(4),
+        self.transaction(|trans| {
+            validate(trans, msg.0.id, &msg.0.email)?;
+
+            let rows = trans.query(
+                QUERY,
+                &[
+                    &msg.0.id,
+                    &msg.0.email,
+                    &hash(&msg.0.password)?,
+                    &msg.0.created,
+                    &msg.0.updated,
+                    &msg.0.verified,
+                    &msg.0.approved,
+                    &msg.0.display_name,
+                    &msg.0.avatar,
+                    &msg.0.bio,
+                ],
+            )?;
+
+            if rows.is_empty() {
+                return Err(format_err!("Row not returned"));
+            }
+
+            let row = rows.get(0);
+
+            Ok(user_from_row(&row))
         })
     }
 }
@@ -0,0 +1,73 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use chrono::{DateTime, Utc};
+use failure::{format_err, Error};
+use futures::future::Future;
+use uuid::Uuid;
+
+use crate::model::Permission;
+use crate::util::{digest, salt};
+
+static QUERY: &str = r#"
+    INSERT INTO api_keys (id, "user", name, hash, scopes, expires)
+    VALUES ($1, $2, $3, $4, $5, $6)
+"#;
+
+pub fn create_api_key(
+    database: &Database,
+    user: Uuid,
+    name: String,
+    scopes: Option<Vec<Permission>>,
+    expires: Option<DateTime<Utc>>,
+) -> impl Future<Item = String, Error = Error> {
+    database
+        .send(CreateApiKey {
+            user,
+            name,
+            scopes,
+            expires,
+        })
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct CreateApiKey {
+    pub user: Uuid,
+    pub name: String,
+    pub scopes: Option<Vec<Permission>>,
+    pub expires: Option<DateTime<Utc>>,
+}
+
+impl Message for CreateApiKey {
+    type Result = Result<String, Error>;
+}
+
+impl Handler<CreateApiKey> for DatabaseInner {
+    type Result = Result<String, Error>;
+
+    fn handle(&mut self, msg: CreateApiKey, _: &mut Self::Context) -> Self::Result {
+        let conn = self.0.get()?;
+        let id = Uuid::new_v4();
+        let secret = format!("{}{}", salt(), salt());
+        let scopes = msg.scopes.map(|scopes| {
+            scopes
+                .iter()
+                .map(|scope| scope.as_str().to_owned())
+                .collect::<Vec<_>>()
+        });
+
+        conn.execute(
+            QUERY,
+            &[
+                &id,
+                &msg.user,
+                &msg.name,
+                &digest(&secret),
+                &scopes,
+                &msg.expires,
+            ],
+        )?;
+
+        Ok(format!("{}.{}", id, secret))
+    }
+}
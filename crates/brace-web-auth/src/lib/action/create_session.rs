@@ -0,0 +1,51 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+use uuid::Uuid;
+
+static QUERY: &str = r#"
+    INSERT INTO sessions (id, "user", user_agent, address)
+    VALUES ($1, $2, $3, $4)
+"#;
+
+pub fn create_session(
+    database: &Database,
+    id: Uuid,
+    user: Uuid,
+    user_agent: Option<String>,
+    address: Option<String>,
+) -> impl Future<Item = (), Error = Error> {
+    database
+        .send(CreateSession {
+            id,
+            user,
+            user_agent,
+            address,
+        })
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct CreateSession {
+    pub id: Uuid,
+    pub user: Uuid,
+    pub user_agent: Option<String>,
+    pub address: Option<String>,
+}
+
+impl Message for CreateSession {
+    type Result = Result<(), Error>;
+}
+
+impl Handler<CreateSession> for DatabaseInner {
+    type Result = Result<(), Error>;
+
+    fn handle(&mut self, msg: CreateSession, _: &mut Self::Context) -> Self::Result {
+        let conn = self.0.get()?;
+
+        conn.execute(QUERY, &[&msg.id, &msg.user, &msg.user_agent, &msg.address])?;
+
+        Ok(())
+    }
+}
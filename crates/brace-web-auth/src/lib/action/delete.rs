@@ -4,12 +4,29 @@ use failure::{format_err, Error};
 use futures::future::Future;
 use uuid::Uuid;
 
+use crate::action::retrieve::user_from_row;
 use crate::model::User;
 
 static QUERY: &str = r#"
     DELETE FROM users *
     WHERE id = $1
-    RETURNING id, email, password, created, updated
+    RETURNING id, email, password, created, updated, verified, approved, display_name, avatar, bio
+"#;
+
+static PAGES_QUERY: &str = r#"
+    SELECT to_regclass('pages') IS NOT NULL
+"#;
+
+static AUTHORS_QUERY: &str = r#"
+    UPDATE pages
+    SET created_by = nullif(created_by, $1), updated_by = nullif(updated_by, $1)
+    WHERE created_by = $1 OR updated_by = $1
+"#;
+
+static REVISIONS_QUERY: &str = r#"
+    UPDATE page_revisions
+    SET author = NULL
+    WHERE author = $1
 "#;
 
 pub fn delete(database: &Database, user: Uuid) -> impl Future<Item = User, Error = Error> {
@@ -29,21 +46,20 @@ impl Handler<Delete> for DatabaseInner {
     type Result = Result<User, Error>;
 
     fn handle(&mut self, msg: Delete, _: &mut Self::Context) -> Self::Result {
-        let conn = self.0.get()?;
-        let rows = conn.query(QUERY, &[&msg.0])?;
+        self.transaction(|trans| {
+            let rows = trans.query(QUERY, &[&msg.0])?;
 
-        if rows.is_empty() {
-            return Err(format_err!("Row not returned"));
-        }
+            if rows.is_empty() {
+                return Err(format_err!("Row not returned"));
+            }
 
-        let row = rows.get(0);
+            // Pages keep their authors without a foreign key into this module.
+            if trans.query(PAGES_QUERY, &[])?.get(0).get::<_, bool>(0) {
+                trans.execute(AUTHORS_QUERY, &[&msg.0])?;
+                trans.execute(REVISIONS_QUERY, &[&msg.0])?;
+            }
 
-        Ok(User {
-            id: row.get(0),
-            email: row.get(1),
-            password: row.get(2),
-            created: row.get(3),
-            updated: row.get(4),
+            Ok(user_from_row(&rows.get(0)))
         })
     }
 }
@@ -0,0 +1,35 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+use uuid::Uuid;
+
+static QUERY: &str = r#"
+    DELETE FROM sessions
+    WHERE id = $1
+"#;
+
+pub fn delete_session(database: &Database, session: Uuid) -> impl Future<Item = (), Error = Error> {
+    database
+        .send(DeleteSession(session))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct DeleteSession(pub Uuid);
+
+impl Message for DeleteSession {
+    type Result = Result<(), Error>;
+}
+
+impl Handler<DeleteSession> for DatabaseInner {
+    type Result = Result<(), Error>;
+
+    fn handle(&mut self, msg: DeleteSession, _: &mut Self::Context) -> Self::Result {
+        let conn = self.0.get()?;
+
+        conn.execute(QUERY, &[&msg.0])?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+use uuid::Uuid;
+
+use crate::action::two_factor::check;
+
+static DELETE_TOTP_QUERY: &str = r#"
+    DELETE FROM user_totp
+    WHERE "user" = $1
+"#;
+
+static DELETE_RECOVERY_QUERY: &str = r#"
+    DELETE FROM recovery_codes
+    WHERE "user" = $1
+"#;
+
+pub fn disable_two_factor(
+    database: &Database,
+    user: Uuid,
+    code: String,
+) -> impl Future<Item = bool, Error = Error> {
+    database
+        .send(DisableTwoFactor(user, code))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct DisableTwoFactor(pub Uuid, pub String);
+
+impl Message for DisableTwoFactor {
+    type Result = Result<bool, Error>;
+}
+
+impl Handler<DisableTwoFactor> for DatabaseInner {
+    type Result = Result<bool, Error>;
+
+    fn handle(&mut self, msg: DisableTwoFactor, _: &mut Self::Context) -> Self::Result {
+        self.transaction(|trans| {
+            if !check(trans, msg.0, &msg.1)? {
+                return Ok(false);
+            }
+
+            trans.execute(DELETE_TOTP_QUERY, &[&msg.0])?;
+            trans.execute(DELETE_RECOVERY_QUERY, &[&msg.0])?;
+
+            Ok(true)
+        })
+    }
+}
@@ -0,0 +1,35 @@
+use failure::{Error, Fail};
+use postgres::transaction::Transaction;
+use uuid::Uuid;
+
+static DUPLICATE_QUERY: &str = r#"
+    SELECT EXISTS (
+        SELECT 1
+        FROM users
+        WHERE id <> $1 AND lower(email) = lower($2)
+    )
+"#;
+
+#[derive(Debug, Fail, PartialEq)]
+pub enum EmailError {
+    #[fail(display = "A user with the email address \"{}\" already exists", _0)]
+    Duplicate(String),
+}
+
+impl EmailError {
+    pub fn field(&self) -> &'static str {
+        match self {
+            EmailError::Duplicate(_) => "email",
+        }
+    }
+}
+
+pub fn validate(trans: &Transaction, id: Uuid, email: &str) -> Result<(), Error> {
+    let rows = trans.query(DUPLICATE_QUERY, &[&id, &email])?;
+
+    if rows.get(0).get::<_, bool>(0) {
+        return Err(EmailError::Duplicate(email.to_owned()).into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use chrono::Utc;
+use failure::{format_err, Error};
+use futures::future::Future;
+use postgres::transaction::Transaction;
+use uuid::Uuid;
+
+use crate::action::two_factor::recovery_codes;
+use crate::totp;
+
+static SELECT_QUERY: &str = r#"
+    SELECT secret
+    FROM user_totp
+    WHERE "user" = $1 AND enabled IS NULL
+    FOR UPDATE
+"#;
+
+static ENABLE_QUERY: &str = r#"
+    UPDATE user_totp
+    SET enabled = now(), last_step = $2
+    WHERE "user" = $1
+"#;
+
+pub fn enable_two_factor(
+    database: &Database,
+    user: Uuid,
+    code: String,
+) -> impl Future<Item = Option<Vec<String>>, Error = Error> {
+    database
+        .send(EnableTwoFactor(user, code))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct EnableTwoFactor(pub Uuid, pub String);
+
+impl Message for EnableTwoFactor {
+    type Result = Result<Option<Vec<String>>, Error>;
+}
+
+impl Handler<EnableTwoFactor> for DatabaseInner {
+    type Result = Result<Option<Vec<String>>, Error>;
+
+    fn handle(&mut self, msg: EnableTwoFactor, _: &mut Self::Context) -> Self::Result {
+        self.transaction(|trans| enable(trans, msg.0, &msg.1))
+    }
+}
+
+pub(crate) fn enable(
+    trans: &Transaction,
+    user: Uuid,
+    code: &str,
+) -> Result<Option<Vec<String>>, Error> {
+    let rows = trans.query(SELECT_QUERY, &[&user])?;
+
+    if rows.is_empty() {
+        return Ok(None);
+    }
+
+    let secret: String = rows.get(0).get(0);
+
+    match totp::verify(&secret, code, Utc::now().timestamp(), None) {
+        Some(step) => {
+            trans.execute(ENABLE_QUERY, &[&user, &step])?;
+
+            Ok(Some(recovery_codes(trans, user)?))
+        }
+        None => Ok(None),
+    }
+}
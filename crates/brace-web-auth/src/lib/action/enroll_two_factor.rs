@@ -0,0 +1,62 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+use uuid::Uuid;
+
+use crate::totp;
+
+static SELECT_QUERY: &str = r#"
+    SELECT enabled IS NOT NULL
+    FROM user_totp
+    WHERE "user" = $1
+    FOR UPDATE
+"#;
+
+static INSERT_QUERY: &str = r#"
+    INSERT INTO user_totp ("user", secret)
+    VALUES ($1, $2)
+"#;
+
+static UPDATE_QUERY: &str = r#"
+    UPDATE user_totp
+    SET secret = $2, last_step = NULL, created = now()
+    WHERE "user" = $1
+"#;
+
+pub fn enroll_two_factor(
+    database: &Database,
+    user: Uuid,
+) -> impl Future<Item = Option<String>, Error = Error> {
+    database
+        .send(EnrollTwoFactor(user))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct EnrollTwoFactor(pub Uuid);
+
+impl Message for EnrollTwoFactor {
+    type Result = Result<Option<String>, Error>;
+}
+
+impl Handler<EnrollTwoFactor> for DatabaseInner {
+    type Result = Result<Option<String>, Error>;
+
+    fn handle(&mut self, msg: EnrollTwoFactor, _: &mut Self::Context) -> Self::Result {
+        self.transaction(|trans| {
+            let rows = trans.query(SELECT_QUERY, &[&msg.0])?;
+            let secret = totp::secret();
+
+            if rows.is_empty() {
+                trans.execute(INSERT_QUERY, &[&msg.0, &secret])?;
+            } else if rows.get(0).get::<_, bool>(0) {
+                return Ok(None);
+            } else {
+                trans.execute(UPDATE_QUERY, &[&msg.0, &secret])?;
+            }
+
+            Ok(Some(secret))
+        })
+    }
+}
@@ -0,0 +1,44 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use chrono::{DateTime, Utc};
+use failure::{format_err, Error};
+use futures::future::Future;
+use uuid::Uuid;
+
+use crate::util::{digest, salt};
+
+static QUERY: &str = r#"
+    INSERT INTO login_challenges (id, "user", hash, expires)
+    VALUES ($1, $2, $3, $4)
+"#;
+
+pub fn issue_challenge(
+    database: &Database,
+    user: Uuid,
+    expires: DateTime<Utc>,
+) -> impl Future<Item = String, Error = Error> {
+    database
+        .send(IssueChallenge(user, expires))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct IssueChallenge(pub Uuid, pub DateTime<Utc>);
+
+impl Message for IssueChallenge {
+    type Result = Result<String, Error>;
+}
+
+impl Handler<IssueChallenge> for DatabaseInner {
+    type Result = Result<String, Error>;
+
+    fn handle(&mut self, msg: IssueChallenge, _: &mut Self::Context) -> Self::Result {
+        let conn = self.0.get()?;
+        let id = Uuid::new_v4();
+        let secret = format!("{}{}", salt(), salt());
+
+        conn.execute(QUERY, &[&id, &msg.0, &digest(&secret), &msg.1])?;
+
+        Ok(format!("{}.{}", id, secret))
+    }
+}
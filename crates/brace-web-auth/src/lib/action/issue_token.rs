@@ -0,0 +1,42 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use chrono::{DateTime, Utc};
+use failure::{format_err, Error};
+use futures::future::Future;
+use uuid::Uuid;
+
+use crate::action::token::issue;
+
+static PURGE_QUERY: &str = r#"
+    DELETE FROM refresh_tokens
+    WHERE "user" = $1 AND expires < now()
+"#;
+
+pub fn issue_token(
+    database: &Database,
+    user: Uuid,
+    expires: DateTime<Utc>,
+) -> impl Future<Item = String, Error = Error> {
+    database
+        .send(IssueToken(user, expires))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct IssueToken(pub Uuid, pub DateTime<Utc>);
+
+impl Message for IssueToken {
+    type Result = Result<String, Error>;
+}
+
+impl Handler<IssueToken> for DatabaseInner {
+    type Result = Result<String, Error>;
+
+    fn handle(&mut self, msg: IssueToken, _: &mut Self::Context) -> Self::Result {
+        self.transaction(|trans| {
+            trans.execute(PURGE_QUERY, &[&msg.0])?;
+
+            issue(trans, msg.0, msg.1)
+        })
+    }
+}
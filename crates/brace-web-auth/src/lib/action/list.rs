@@ -1,44 +1,66 @@
 use actix::{Handler, Message};
 use brace_db::{Database, DatabaseInner};
+use brace_web::pager::Pager;
 use failure::{format_err, Error};
 use futures::future::Future;
 
-use crate::model::User;
+use crate::action::retrieve::user_from_row;
+use crate::model::{User, UserFilter};
 
 static QUERY: &str = r#"
-    SELECT id, email, password, created, updated
+    SELECT id, email, password, created, updated, verified, approved, display_name, avatar, bio
     FROM users
+    WHERE ($1::text IS NULL OR strpos(lower(email), lower($1)) > 0)
+    AND ($2::timestamptz IS NULL OR created >= $2)
 "#;
 
-pub fn list(database: &Database) -> impl Future<Item = Vec<User>, Error = Error> {
+pub fn list(
+    database: &Database,
+    filter: UserFilter,
+    pager: Pager,
+) -> impl Future<Item = (Vec<User>, i64), Error = Error> {
     database
-        .send(List)
+        .send(List(filter, pager))
         .map_err(|err| format_err!("{}", err))
         .and_then(|res| res)
 }
 
-pub struct List;
+pub struct List(pub UserFilter, pub Pager);
 
 impl Message for List {
-    type Result = Result<Vec<User>, Error>;
+    type Result = Result<(Vec<User>, i64), Error>;
 }
 
 impl Handler<List> for DatabaseInner {
-    type Result = Result<Vec<User>, Error>;
+    type Result = Result<(Vec<User>, i64), Error>;
 
-    fn handle(&mut self, _: List, _: &mut Self::Context) -> Self::Result {
+    fn handle(&mut self, msg: List, _: &mut Self::Context) -> Self::Result {
         let conn = self.0.get()?;
-        let rows = conn.query(QUERY, &[])?;
-
-        Ok(rows
-            .iter()
-            .map(|row| User {
-                id: row.get(0),
-                email: row.get(1),
-                password: row.get(2),
-                created: row.get(3),
-                updated: row.get(4),
-            })
-            .collect())
+        let filter = msg.0;
+
+        let count = conn.query(
+            &format!("SELECT count(*) FROM ({}) AS users", QUERY),
+            &[&filter.email, &filter.created_after],
+        )?;
+        let total: i64 = count.get(0).get(0);
+
+        let rows = conn.query(
+            &format!(
+                "{} ORDER BY {} {}, id LIMIT $3 OFFSET $4",
+                QUERY,
+                filter.sort.as_str(),
+                filter.order.as_str()
+            ),
+            &[
+                &filter.email,
+                &filter.created_after,
+                &msg.1.limit(),
+                &msg.1.offset(),
+            ],
+        )?;
+
+        let users = rows.iter().map(|row| user_from_row(&row)).collect();
+
+        Ok((users, total))
     }
 }
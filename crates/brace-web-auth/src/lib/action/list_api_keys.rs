@@ -0,0 +1,60 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+use uuid::Uuid;
+
+use crate::model::{ApiKey, Permission};
+
+static QUERY: &str = r#"
+    SELECT id, name, scopes, created, expires, last_used
+    FROM api_keys
+    WHERE "user" = $1
+    ORDER BY created DESC
+"#;
+
+pub fn list_api_keys(
+    database: &Database,
+    user: Uuid,
+) -> impl Future<Item = Vec<ApiKey>, Error = Error> {
+    database
+        .send(ListApiKeys(user))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct ListApiKeys(pub Uuid);
+
+impl Message for ListApiKeys {
+    type Result = Result<Vec<ApiKey>, Error>;
+}
+
+impl Handler<ListApiKeys> for DatabaseInner {
+    type Result = Result<Vec<ApiKey>, Error>;
+
+    fn handle(&mut self, msg: ListApiKeys, _: &mut Self::Context) -> Self::Result {
+        let conn = self.0.get()?;
+        let rows = conn.query(QUERY, &[&msg.0])?;
+
+        Ok(rows
+            .iter()
+            .map(|row| ApiKey {
+                id: row.get(0),
+                name: row.get(1),
+                scopes: scopes(row.get(2)),
+                created: row.get(3),
+                expires: row.get(4),
+                last_used: row.get(5),
+            })
+            .collect())
+    }
+}
+
+pub(crate) fn scopes(scopes: Option<Vec<String>>) -> Option<Vec<Permission>> {
+    scopes.map(|scopes| {
+        scopes
+            .iter()
+            .filter_map(|scope| scope.parse().ok())
+            .collect()
+    })
+}
@@ -0,0 +1,54 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+use postgres::rows::Row;
+
+use crate::model::Role;
+
+static QUERY: &str = r#"
+    SELECT r.id, r.name, r.label, array_remove(array_agg(p.permission ORDER BY p.permission), NULL),
+           r.two_factor
+    FROM roles r
+    LEFT JOIN role_permissions p ON p.role = r.id
+    GROUP BY r.id
+    ORDER BY r.name
+"#;
+
+pub fn list_roles(database: &Database) -> impl Future<Item = Vec<Role>, Error = Error> {
+    database
+        .send(ListRoles)
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct ListRoles;
+
+impl Message for ListRoles {
+    type Result = Result<Vec<Role>, Error>;
+}
+
+impl Handler<ListRoles> for DatabaseInner {
+    type Result = Result<Vec<Role>, Error>;
+
+    fn handle(&mut self, _: ListRoles, _: &mut Self::Context) -> Self::Result {
+        let conn = self.0.get()?;
+        let rows = conn.query(QUERY, &[])?;
+
+        Ok(rows.iter().map(|row| role(&row)).collect())
+    }
+}
+
+pub(crate) fn role(row: &Row) -> Role {
+    Role {
+        id: row.get(0),
+        name: row.get(1),
+        label: row.get(2),
+        permissions: row
+            .get::<_, Vec<String>>(3)
+            .iter()
+            .filter_map(|permission| permission.parse().ok())
+            .collect(),
+        two_factor: row.get(4),
+    }
+}
@@ -0,0 +1,51 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+use uuid::Uuid;
+
+use crate::model::Session;
+
+static QUERY: &str = r#"
+    SELECT id, "user", user_agent, address, created, last_seen
+    FROM sessions
+    WHERE "user" = $1
+    ORDER BY last_seen DESC
+"#;
+
+pub fn list_sessions(
+    database: &Database,
+    user: Uuid,
+) -> impl Future<Item = Vec<Session>, Error = Error> {
+    database
+        .send(ListSessions(user))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct ListSessions(pub Uuid);
+
+impl Message for ListSessions {
+    type Result = Result<Vec<Session>, Error>;
+}
+
+impl Handler<ListSessions> for DatabaseInner {
+    type Result = Result<Vec<Session>, Error>;
+
+    fn handle(&mut self, msg: ListSessions, _: &mut Self::Context) -> Self::Result {
+        let conn = self.0.get()?;
+        let rows = conn.query(QUERY, &[&msg.0])?;
+
+        Ok(rows
+            .iter()
+            .map(|row| Session {
+                id: row.get(0),
+                user: row.get(1),
+                user_agent: row.get(2),
+                address: row.get(3),
+                created: row.get(4),
+                last_seen: row.get(5),
+            })
+            .collect())
+    }
+}
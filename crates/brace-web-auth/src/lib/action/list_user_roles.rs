@@ -0,0 +1,46 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+use uuid::Uuid;
+
+use crate::action::list_roles::role;
+use crate::model::Role;
+
+static QUERY: &str = r#"
+    SELECT r.id, r.name, r.label, array_remove(array_agg(p.permission ORDER BY p.permission), NULL),
+           r.two_factor
+    FROM user_roles u
+    JOIN roles r ON r.id = u.role
+    LEFT JOIN role_permissions p ON p.role = r.id
+    WHERE u."user" = $1
+    GROUP BY r.id
+    ORDER BY r.name
+"#;
+
+pub fn list_user_roles(
+    database: &Database,
+    user: Uuid,
+) -> impl Future<Item = Vec<Role>, Error = Error> {
+    database
+        .send(ListUserRoles(user))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct ListUserRoles(pub Uuid);
+
+impl Message for ListUserRoles {
+    type Result = Result<Vec<Role>, Error>;
+}
+
+impl Handler<ListUserRoles> for DatabaseInner {
+    type Result = Result<Vec<Role>, Error>;
+
+    fn handle(&mut self, msg: ListUserRoles, _: &mut Self::Context) -> Self::Result {
+        let conn = self.0.get()?;
+        let rows = conn.query(QUERY, &[&msg.0])?;
+
+        Ok(rows.iter().map(|row| role(&row)).collect())
+    }
+}
@@ -3,12 +3,13 @@ use brace_db::{Database, DatabaseInner};
 use failure::{format_err, Error};
 use futures::future::Future;
 
+use crate::action::retrieve::user_from_row;
 use crate::model::User;
 
 static QUERY: &str = r#"
-    SELECT id, email, password, created, updated
+    SELECT id, email, password, created, updated, verified, approved, display_name, avatar, bio
     FROM users
-    WHERE email = $1
+    WHERE lower(email) = lower($1)
 "#;
 
 pub fn locate<S: Into<String>>(
@@ -40,12 +41,6 @@ impl Handler<Locate> for DatabaseInner {
 
         let row = rows.get(0);
 
-        Ok(User {
-            id: row.get(0),
-            email: row.get(1),
-            password: row.get(2),
-            created: row.get(3),
-            updated: row.get(4),
-        })
+        Ok(user_from_row(&row))
     }
 }
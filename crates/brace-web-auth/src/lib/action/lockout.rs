@@ -0,0 +1,110 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use chrono::{DateTime, Duration, Utc};
+use failure::{format_err, Error};
+use futures::future::Future;
+use postgres::transaction::Transaction;
+use uuid::Uuid;
+
+use crate::config::LockoutConfig;
+
+static EMAIL_QUERY: &str = r#"
+    SELECT count(*), max(created)
+    FROM login_failures
+    WHERE lower(email) = lower($1) AND created > $2
+"#;
+
+static ADDRESS_QUERY: &str = r#"
+    SELECT count(*), max(created)
+    FROM login_failures
+    WHERE address = $1 AND created > $2
+"#;
+
+static FAILURE_QUERY: &str = r#"
+    INSERT INTO login_failures (id, email, address)
+    VALUES ($1, $2, $3)
+"#;
+
+static EXPIRE_QUERY: &str = r#"
+    DELETE FROM login_failures
+    WHERE created < $1
+"#;
+
+pub fn lockout(
+    database: &Database,
+    email: String,
+    config: LockoutConfig,
+) -> impl Future<Item = Option<i64>, Error = Error> {
+    database
+        .send(Lockout(email, config))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct Lockout(pub String, pub LockoutConfig);
+
+impl Message for Lockout {
+    type Result = Result<Option<i64>, Error>;
+}
+
+impl Handler<Lockout> for DatabaseInner {
+    type Result = Result<Option<i64>, Error>;
+
+    fn handle(&mut self, msg: Lockout, _: &mut Self::Context) -> Self::Result {
+        self.transaction(|trans| locked(trans, &msg.0, None, &msg.1))
+    }
+}
+
+pub fn locked(
+    trans: &Transaction,
+    email: &str,
+    address: Option<&str>,
+    config: &LockoutConfig,
+) -> Result<Option<i64>, Error> {
+    let since = Utc::now() - Duration::seconds(config.window);
+    let rows = trans.query(EMAIL_QUERY, &[&email, &since])?;
+    let mut remaining = lockout_remaining(
+        config,
+        rows.get(0).get(0),
+        rows.get(0).get(1),
+        config.attempts,
+    );
+
+    if let Some(address) = address {
+        let rows = trans.query(ADDRESS_QUERY, &[&address, &since])?;
+
+        remaining = remaining.max(lockout_remaining(
+            config,
+            rows.get(0).get(0),
+            rows.get(0).get(1),
+            config.address_attempts,
+        ));
+    }
+
+    Ok(remaining)
+}
+
+pub(crate) fn fail(
+    trans: &Transaction,
+    email: &str,
+    address: Option<&str>,
+    config: &LockoutConfig,
+) -> Result<Option<i64>, Error> {
+    let expired = Utc::now() - Duration::seconds(config.window);
+
+    trans.execute(EXPIRE_QUERY, &[&expired])?;
+    trans.execute(FAILURE_QUERY, &[&Uuid::new_v4(), &email, &address])?;
+
+    locked(trans, email, address, config)
+}
+
+fn lockout_remaining(
+    config: &LockoutConfig,
+    failures: i64,
+    last: Option<DateTime<Utc>>,
+    threshold: i64,
+) -> Option<i64> {
+    let elapsed = (Utc::now() - last?).num_seconds();
+
+    config.remaining(failures, threshold, elapsed)
+}
@@ -0,0 +1,91 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+
+use crate::action::lockout::{fail, locked};
+use crate::action::retrieve::user_from_row;
+use crate::action::two_factor::{check, enabled, required};
+use crate::config::LockoutConfig;
+use crate::model::{LoginAttempt, UserAuth};
+use crate::util::{hash, salt, verify};
+
+static SELECT_QUERY: &str = r#"
+    SELECT id, email, password, created, updated, verified, approved, display_name, avatar, bio
+    FROM users
+    WHERE lower(email) = lower($1)
+"#;
+
+static CLEAR_QUERY: &str = r#"
+    DELETE FROM login_failures
+    WHERE lower(email) = lower($1)
+"#;
+
+thread_local! {
+    static DUMMY_HASH: String = hash(&salt()).unwrap_or_default();
+}
+
+pub fn login(
+    database: &Database,
+    auth: UserAuth,
+    address: Option<String>,
+    config: LockoutConfig,
+) -> impl Future<Item = LoginAttempt, Error = Error> {
+    database
+        .send(Login(auth, address, config))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct Login(pub UserAuth, pub Option<String>, pub LockoutConfig);
+
+impl Message for Login {
+    type Result = Result<LoginAttempt, Error>;
+}
+
+impl Handler<Login> for DatabaseInner {
+    type Result = Result<LoginAttempt, Error>;
+
+    fn handle(&mut self, msg: Login, _: &mut Self::Context) -> Self::Result {
+        self.transaction(|trans| {
+            let (auth, address, config) = (&msg.0, msg.1.as_ref().map(String::as_str), &msg.2);
+
+            if let Some(remaining) = locked(trans, &auth.email, address, config)? {
+                return Ok(LoginAttempt::Locked(remaining));
+            }
+
+            let rows = trans.query(SELECT_QUERY, &[&auth.email])?;
+
+            if !rows.is_empty() {
+                let row = rows.get(0);
+                let user = user_from_row(&row);
+
+                if verify(&auth.password, &user.password).unwrap_or(false) {
+                    let passed = match (&auth.code, enabled(trans, user.id)?) {
+                        (_, false) if required(trans, user.id)? => {
+                            return Ok(LoginAttempt::Enroll(user));
+                        }
+                        (_, false) => true,
+                        (None, true) => return Ok(LoginAttempt::TwoFactor(user)),
+                        (Some(code), true) => check(trans, user.id, code)?,
+                    };
+
+                    if passed {
+                        trans.execute(CLEAR_QUERY, &[&auth.email])?;
+
+                        return Ok(LoginAttempt::Success(user));
+                    }
+                }
+            } else {
+                let _ = DUMMY_HASH.with(|hash| verify(&auth.password, hash));
+            }
+
+            Ok(LoginAttempt::Failure(fail(
+                trans,
+                &auth.email,
+                address,
+                config,
+            )?))
+        })
+    }
+}
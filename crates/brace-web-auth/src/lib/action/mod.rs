@@ -1,8 +1,46 @@
+pub mod assign_roles;
+pub mod authenticate;
+pub mod authenticate_key;
+pub mod complete_challenge;
+pub mod complete_enrollment;
 pub mod create;
+pub mod create_api_key;
+pub mod create_session;
 pub mod delete;
+pub mod delete_session;
+pub mod disable_two_factor;
+pub mod email;
+pub mod enable_two_factor;
+pub mod enroll_two_factor;
 pub mod install;
+pub mod issue_challenge;
+pub mod issue_token;
 pub mod list;
+pub mod list_api_keys;
+pub mod list_roles;
+pub mod list_sessions;
+pub mod list_user_roles;
 pub mod locate;
+pub mod lockout;
+pub mod login;
+pub mod names;
+pub mod permissions;
+pub mod refresh_token;
+pub mod register;
+pub mod request_reset;
+pub mod reset_password;
 pub mod retrieve;
+pub mod revoke_api_key;
+pub mod revoke_session;
+pub mod revoke_sessions;
+pub mod revoke_token;
+pub mod throttle_reset;
+pub mod token;
+pub mod touch_session;
+pub mod two_factor;
+pub mod two_factor_status;
 pub mod uninstall;
+pub mod unlock;
 pub mod update;
+pub mod update_role;
+pub mod verify_email;
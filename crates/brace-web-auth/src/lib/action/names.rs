@@ -0,0 +1,47 @@
+use std::collections::HashMap;
+
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+use uuid::Uuid;
+
+static QUERY: &str = r#"
+    SELECT id, coalesce(display_name, email)
+    FROM users
+    WHERE id = ANY($1)
+"#;
+
+pub fn names(
+    database: &Database,
+    users: Vec<Uuid>,
+) -> impl Future<Item = HashMap<Uuid, String>, Error = Error> {
+    database
+        .send(Names(users))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct Names(pub Vec<Uuid>);
+
+impl Message for Names {
+    type Result = Result<HashMap<Uuid, String>, Error>;
+}
+
+impl Handler<Names> for DatabaseInner {
+    type Result = Result<HashMap<Uuid, String>, Error>;
+
+    fn handle(&mut self, mut msg: Names, _: &mut Self::Context) -> Self::Result {
+        msg.0.sort();
+        msg.0.dedup();
+
+        if msg.0.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let conn = self.0.get()?;
+        let rows = conn.query(QUERY, &[&msg.0])?;
+
+        Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+    }
+}
@@ -0,0 +1,50 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+use uuid::Uuid;
+
+use crate::model::Permission;
+
+static QUERY: &str = r#"
+    SELECT DISTINCT p.permission
+    FROM user_roles u
+    JOIN roles r ON r.id = u.role
+    JOIN role_permissions p ON p.role = u.role
+    WHERE u."user" = $1
+    AND (NOT r.two_factor OR EXISTS (
+        SELECT 1
+        FROM user_totp t
+        WHERE t."user" = u."user" AND t.enabled IS NOT NULL
+    ))
+"#;
+
+pub fn permissions(
+    database: &Database,
+    user: Uuid,
+) -> impl Future<Item = Vec<Permission>, Error = Error> {
+    database
+        .send(Permissions(user))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct Permissions(pub Uuid);
+
+impl Message for Permissions {
+    type Result = Result<Vec<Permission>, Error>;
+}
+
+impl Handler<Permissions> for DatabaseInner {
+    type Result = Result<Vec<Permission>, Error>;
+
+    fn handle(&mut self, msg: Permissions, _: &mut Self::Context) -> Self::Result {
+        let conn = self.0.get()?;
+        let rows = conn.query(QUERY, &[&msg.0])?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| row.get::<_, String>(0).parse().ok())
+            .collect())
+    }
+}
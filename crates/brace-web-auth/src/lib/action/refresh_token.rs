@@ -0,0 +1,92 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use chrono::{DateTime, Utc};
+use failure::{format_err, Error};
+use futures::future::Future;
+
+use crate::action::retrieve::user_from_row;
+use crate::action::token::{issue, split};
+use crate::model::User;
+use crate::util::{constant_eq, digest};
+
+static SELECT_QUERY: &str = r#"
+    SELECT u.id, u.email, u.password, u.created, u.updated, u.verified, u.approved,
+           u.display_name, u.avatar, u.bio, r.hash, r.expires, r.revoked
+    FROM refresh_tokens r
+    JOIN users u ON u.id = r."user"
+    WHERE r.id = $1 AND u.verified AND u.approved
+    FOR UPDATE OF r
+"#;
+
+static REVOKE_QUERY: &str = r#"
+    UPDATE refresh_tokens
+    SET revoked = now()
+    WHERE id = $1
+"#;
+
+static REVOKE_ALL_QUERY: &str = r#"
+    UPDATE refresh_tokens
+    SET revoked = now()
+    WHERE "user" = $1 AND revoked IS NULL
+"#;
+
+pub fn refresh_token(
+    database: &Database,
+    token: String,
+    expires: DateTime<Utc>,
+) -> impl Future<Item = Option<(User, String)>, Error = Error> {
+    database
+        .send(RefreshToken(token, expires))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct RefreshToken(pub String, pub DateTime<Utc>);
+
+impl Message for RefreshToken {
+    type Result = Result<Option<(User, String)>, Error>;
+}
+
+impl Handler<RefreshToken> for DatabaseInner {
+    type Result = Result<Option<(User, String)>, Error>;
+
+    fn handle(&mut self, msg: RefreshToken, _: &mut Self::Context) -> Self::Result {
+        self.transaction(|trans| {
+            let (id, secret) = match split(&msg.0) {
+                Some(parts) => parts,
+                None => return Ok(None),
+            };
+            let rows = trans.query(SELECT_QUERY, &[&id])?;
+
+            if rows.is_empty() {
+                return Ok(None);
+            }
+
+            let row = rows.get(0);
+
+            if !constant_eq(&digest(secret), &row.get::<_, String>(10)) {
+                return Ok(None);
+            }
+
+            let expires: DateTime<Utc> = row.get(11);
+            let revoked: Option<DateTime<Utc>> = row.get(12);
+            let user = user_from_row(&row);
+
+            if revoked.is_some() {
+                trans.execute(REVOKE_ALL_QUERY, &[&user.id])?;
+
+                return Ok(None);
+            }
+
+            if expires < Utc::now() {
+                return Ok(None);
+            }
+
+            trans.execute(REVOKE_QUERY, &[&id])?;
+
+            let token = issue(trans, user.id, msg.1)?;
+
+            Ok(Some((user, token)))
+        })
+    }
+}
@@ -0,0 +1,89 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use chrono::{DateTime, Utc};
+use failure::{format_err, Error};
+use futures::future::Future;
+use uuid::Uuid;
+
+use crate::action::retrieve::user_from_row;
+use crate::model::User;
+use crate::util::{digest, hash, salt};
+
+static EXISTS_QUERY: &str = r#"
+    SELECT EXISTS (
+        SELECT 1
+        FROM users
+        WHERE lower(email) = lower($1)
+    )
+"#;
+
+static INSERT_QUERY: &str = r#"
+    INSERT INTO users (id, email, password, created, updated, verified, approved)
+    VALUES ($1, $2, $3, $4, $5, false, $6)
+    RETURNING id, email, password, created, updated, verified, approved, display_name, avatar, bio
+"#;
+
+static VERIFICATION_QUERY: &str = r#"
+    INSERT INTO email_verifications (id, "user", hash, expires)
+    VALUES ($1, $2, $3, $4)
+"#;
+
+pub fn register(
+    database: &Database,
+    user: User,
+    expires: DateTime<Utc>,
+) -> impl Future<Item = Option<(User, String)>, Error = Error> {
+    database
+        .send(Register(user, expires))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct Register(pub User, pub DateTime<Utc>);
+
+impl Message for Register {
+    type Result = Result<Option<(User, String)>, Error>;
+}
+
+impl Handler<Register> for DatabaseInner {
+    type Result = Result<Option<(User, String)>, Error>;
+
+    fn handle(&mut self, msg: Register, _: &mut Self::Context) -> Self::Result {
+        self.transaction(|trans| {
+            let rows = trans.query(EXISTS_QUERY, &[&msg.0.email])?;
+
+            if rows.get(0).get::<_, bool>(0) {
+                return Ok(None);
+            }
+
+            let rows = trans.query(
+                INSERT_QUERY,
+                &[
+                    &msg.0.id,
+                    &msg.0.email,
+                    &hash(&msg.0.password)?,
+                    &msg.0.created,
+                    &msg.0.updated,
+                    &msg.0.approved,
+                ],
+            )?;
+
+            if rows.is_empty() {
+                return Err(format_err!("Row not returned"));
+            }
+
+            let row = rows.get(0);
+            let user = user_from_row(&row);
+
+            let id = Uuid::new_v4();
+            let secret = format!("{}{}", salt(), salt());
+
+            trans.execute(
+                VERIFICATION_QUERY,
+                &[&id, &user.id, &digest(&secret), &msg.1],
+            )?;
+
+            Ok(Some((user, format!("{}.{}", id, secret))))
+        })
+    }
+}
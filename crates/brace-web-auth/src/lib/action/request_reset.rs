@@ -0,0 +1,62 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use chrono::{DateTime, Utc};
+use failure::{format_err, Error};
+use futures::future::Future;
+use uuid::Uuid;
+
+use crate::action::retrieve::user_from_row;
+use crate::model::User;
+use crate::util::{digest, salt};
+
+static SELECT_QUERY: &str = r#"
+    SELECT id, email, password, created, updated, verified, approved, display_name, avatar, bio
+    FROM users
+    WHERE lower(email) = lower($1)
+"#;
+
+static INSERT_QUERY: &str = r#"
+    INSERT INTO password_resets (id, "user", hash, expires)
+    VALUES ($1, $2, $3, $4)
+"#;
+
+pub fn request_reset(
+    database: &Database,
+    email: String,
+    expires: DateTime<Utc>,
+) -> impl Future<Item = Option<(User, String)>, Error = Error> {
+    database
+        .send(RequestReset(email, expires))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct RequestReset(pub String, pub DateTime<Utc>);
+
+impl Message for RequestReset {
+    type Result = Result<Option<(User, String)>, Error>;
+}
+
+impl Handler<RequestReset> for DatabaseInner {
+    type Result = Result<Option<(User, String)>, Error>;
+
+    fn handle(&mut self, msg: RequestReset, _: &mut Self::Context) -> Self::Result {
+        self.transaction(|trans| {
+            let rows = trans.query(SELECT_QUERY, &[&msg.0])?;
+
+            if rows.is_empty() {
+                return Ok(None);
+            }
+
+            let row = rows.get(0);
+            let user = user_from_row(&row);
+
+            let id = Uuid::new_v4();
+            let secret = format!("{}{}", salt(), salt());
+
+            trans.execute(INSERT_QUERY, &[&id, &user.id, &digest(&secret), &msg.1])?;
+
+            Ok(Some((user, format!("{}.{}", id, secret))))
+        })
+    }
+}
@@ -2,12 +2,13 @@ use actix::{Handler, Message};
 use brace_db::{Database, DatabaseInner};
 use failure::{format_err, Error};
 use futures::future::Future;
+use postgres::rows::Row;
 use uuid::Uuid;
 
 use crate::model::User;
 
 static QUERY: &str = r#"
-    SELECT id, email, password, created, updated
+    SELECT id, email, password, created, updated, verified, approved, display_name, avatar, bio
     FROM users
     WHERE id = $1
 "#;
@@ -38,12 +39,21 @@ impl Handler<Retrieve> for DatabaseInner {
 
         let row = rows.get(0);
 
-        Ok(User {
-            id: row.get(0),
-            email: row.get(1),
-            password: row.get(2),
-            created: row.get(3),
-            updated: row.get(4),
-        })
+        Ok(user_from_row(&row))
+    }
+}
+
+pub(crate) fn user_from_row(row: &Row) -> User {
+    User {
+        id: row.get(0),
+        email: row.get(1),
+        password: row.get(2),
+        created: row.get(3),
+        updated: row.get(4),
+        verified: row.get(5),
+        approved: row.get(6),
+        display_name: row.get(7),
+        avatar: row.get(8),
+        bio: row.get(9),
     }
 }
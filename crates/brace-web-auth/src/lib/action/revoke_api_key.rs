@@ -0,0 +1,37 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+use uuid::Uuid;
+
+static QUERY: &str = r#"
+    DELETE FROM api_keys
+    WHERE "user" = $1 AND id = $2
+"#;
+
+pub fn revoke_api_key(
+    database: &Database,
+    user: Uuid,
+    key: Uuid,
+) -> impl Future<Item = bool, Error = Error> {
+    database
+        .send(RevokeApiKey(user, key))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct RevokeApiKey(pub Uuid, pub Uuid);
+
+impl Message for RevokeApiKey {
+    type Result = Result<bool, Error>;
+}
+
+impl Handler<RevokeApiKey> for DatabaseInner {
+    type Result = Result<bool, Error>;
+
+    fn handle(&mut self, msg: RevokeApiKey, _: &mut Self::Context) -> Self::Result {
+        let conn = self.0.get()?;
+
+        Ok(conn.execute(QUERY, &[&msg.0, &msg.1])? > 0)
+    }
+}
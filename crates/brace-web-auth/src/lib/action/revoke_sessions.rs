@@ -0,0 +1,37 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+use uuid::Uuid;
+
+static QUERY: &str = r#"
+    DELETE FROM sessions
+    WHERE "user" = $1 AND id IS DISTINCT FROM $2
+"#;
+
+pub fn revoke_sessions(
+    database: &Database,
+    user: Uuid,
+    keep: Option<Uuid>,
+) -> impl Future<Item = u64, Error = Error> {
+    database
+        .send(RevokeSessions(user, keep))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct RevokeSessions(pub Uuid, pub Option<Uuid>);
+
+impl Message for RevokeSessions {
+    type Result = Result<u64, Error>;
+}
+
+impl Handler<RevokeSessions> for DatabaseInner {
+    type Result = Result<u64, Error>;
+
+    fn handle(&mut self, msg: RevokeSessions, _: &mut Self::Context) -> Self::Result {
+        let conn = self.0.get()?;
+
+        Ok(conn.execute(QUERY, &[&msg.0, &msg.1])?)
+    }
+}
@@ -0,0 +1,86 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use chrono::{TimeZone, Utc};
+use failure::{format_err, Error};
+use futures::future::Future;
+use uuid::Uuid;
+
+use crate::action::token::split;
+use crate::model::{Claims, RevokeAuth};
+use crate::util::{constant_eq, digest};
+
+static INSERT_QUERY: &str = r#"
+    INSERT INTO revoked_tokens (jti, expires)
+    VALUES ($1, $2)
+    ON CONFLICT (jti) DO NOTHING
+"#;
+
+static PURGE_QUERY: &str = r#"
+    DELETE FROM revoked_tokens
+    WHERE expires < now()
+"#;
+
+static SELECT_QUERY: &str = r#"
+    SELECT hash
+    FROM refresh_tokens
+    WHERE id = $1 AND "user" = $2
+"#;
+
+static REVOKE_QUERY: &str = r#"
+    UPDATE refresh_tokens
+    SET revoked = now()
+    WHERE id = $1 AND revoked IS NULL
+"#;
+
+static REVOKE_ALL_QUERY: &str = r#"
+    UPDATE refresh_tokens
+    SET revoked = now()
+    WHERE "user" = $1 AND revoked IS NULL
+"#;
+
+pub fn revoke_token(
+    database: &Database,
+    claims: Claims,
+    revoke: RevokeAuth,
+) -> impl Future<Item = (), Error = Error> {
+    database
+        .send(RevokeToken(claims, revoke))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct RevokeToken(pub Claims, pub RevokeAuth);
+
+impl Message for RevokeToken {
+    type Result = Result<(), Error>;
+}
+
+impl Handler<RevokeToken> for DatabaseInner {
+    type Result = Result<(), Error>;
+
+    fn handle(&mut self, msg: RevokeToken, _: &mut Self::Context) -> Self::Result {
+        self.transaction(|trans| {
+            let user = msg.0.sub.parse::<Uuid>()?;
+
+            trans.execute(PURGE_QUERY, &[])?;
+            trans.execute(INSERT_QUERY, &[&msg.0.jti, &Utc.timestamp(msg.0.exp, 0)])?;
+
+            if let Some((id, secret)) = msg.1.refresh_token.as_ref().and_then(|token| split(token))
+            {
+                let rows = trans.query(SELECT_QUERY, &[&id, &user])?;
+
+                if !rows.is_empty()
+                    && constant_eq(&digest(secret), &rows.get(0).get::<_, String>(0))
+                {
+                    trans.execute(REVOKE_QUERY, &[&id])?;
+                }
+            }
+
+            if msg.1.all {
+                trans.execute(REVOKE_ALL_QUERY, &[&user])?;
+            }
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,98 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use chrono::{DateTime, Duration, Utc};
+use failure::{format_err, Error};
+use futures::future::Future;
+use postgres::transaction::Transaction;
+use uuid::Uuid;
+
+use crate::config::ResetThrottleConfig;
+
+static EMAIL_QUERY: &str = r#"
+    SELECT count(*), min(created)
+    FROM password_reset_requests
+    WHERE lower(email) = lower($1) AND created > $2
+"#;
+
+static ADDRESS_QUERY: &str = r#"
+    SELECT count(*), min(created)
+    FROM password_reset_requests
+    WHERE address = $1 AND created > $2
+"#;
+
+static EXPIRE_QUERY: &str = r#"
+    DELETE FROM password_reset_requests
+    WHERE created < $1
+"#;
+
+static INSERT_QUERY: &str = r#"
+    INSERT INTO password_reset_requests (id, email, address)
+    VALUES ($1, $2, $3)
+"#;
+
+pub fn throttle_reset(
+    database: &Database,
+    email: String,
+    address: Option<String>,
+    config: ResetThrottleConfig,
+) -> impl Future<Item = Option<i64>, Error = Error> {
+    database
+        .send(ThrottleReset(email, address, config))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct ThrottleReset(pub String, pub Option<String>, pub ResetThrottleConfig);
+
+impl Message for ThrottleReset {
+    type Result = Result<Option<i64>, Error>;
+}
+
+impl Handler<ThrottleReset> for DatabaseInner {
+    type Result = Result<Option<i64>, Error>;
+
+    fn handle(&mut self, msg: ThrottleReset, _: &mut Self::Context) -> Self::Result {
+        self.transaction(|trans| {
+            let (email, address, config) = (&msg.0, &msg.1, &msg.2);
+            let since = Utc::now() - Duration::seconds(config.window);
+            let mut remaining = throttled(trans, EMAIL_QUERY, email, since, config.attempts)?;
+
+            if let Some(address) = address {
+                remaining = remaining.max(throttled(
+                    trans,
+                    ADDRESS_QUERY,
+                    address,
+                    since,
+                    config.address_attempts,
+                )?);
+            }
+
+            if remaining.is_none() {
+                trans.execute(EXPIRE_QUERY, &[&since])?;
+                trans.execute(INSERT_QUERY, &[&Uuid::new_v4(), email, address])?;
+            }
+
+            Ok(remaining)
+        })
+    }
+}
+
+fn throttled(
+    trans: &Transaction,
+    query: &str,
+    key: &str,
+    since: DateTime<Utc>,
+    threshold: i64,
+) -> Result<Option<i64>, Error> {
+    let rows = trans.query(query, &[&key, &since])?;
+    let row = rows.get(0);
+    let requests: i64 = row.get(0);
+    let first: Option<DateTime<Utc>> = row.get(1);
+
+    match first {
+        Some(first) if threshold > 0 && requests >= threshold => {
+            Ok(Some((first - since).num_seconds().max(1)))
+        }
+        _ => Ok(None),
+    }
+}
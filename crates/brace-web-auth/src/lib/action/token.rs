@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+use failure::Error;
+use postgres::transaction::Transaction;
+use uuid::Uuid;
+
+use crate::util::{digest, salt};
+
+static INSERT_QUERY: &str = r#"
+    INSERT INTO refresh_tokens (id, "user", hash, expires)
+    VALUES ($1, $2, $3, $4)
+"#;
+
+pub fn issue(trans: &Transaction, user: Uuid, expires: DateTime<Utc>) -> Result<String, Error> {
+    let id = Uuid::new_v4();
+    let secret = format!("{}{}", salt(), salt());
+
+    trans.execute(INSERT_QUERY, &[&id, &user, &digest(&secret), &expires])?;
+
+    Ok(format!("{}.{}", id, secret))
+}
+
+pub fn split(token: &str) -> Option<(Uuid, &str)> {
+    let mut parts = token.splitn(2, '.');
+    let id = parts.next()?.parse().ok()?;
+    let secret = parts.next()?;
+
+    Some((id, secret))
+}
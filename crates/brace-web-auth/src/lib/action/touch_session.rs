@@ -0,0 +1,50 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+use uuid::Uuid;
+
+static SELECT_QUERY: &str = r#"
+    SELECT "user"
+    FROM sessions
+    WHERE id = $1
+"#;
+
+static SEEN_QUERY: &str = r#"
+    UPDATE sessions
+    SET last_seen = now()
+    WHERE id = $1 AND last_seen < now() - interval '1 minute'
+"#;
+
+pub fn touch_session(
+    database: &Database,
+    session: Uuid,
+) -> impl Future<Item = Option<Uuid>, Error = Error> {
+    database
+        .send(TouchSession(session))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct TouchSession(pub Uuid);
+
+impl Message for TouchSession {
+    type Result = Result<Option<Uuid>, Error>;
+}
+
+impl Handler<TouchSession> for DatabaseInner {
+    type Result = Result<Option<Uuid>, Error>;
+
+    fn handle(&mut self, msg: TouchSession, _: &mut Self::Context) -> Self::Result {
+        let conn = self.0.get()?;
+        let rows = conn.query(SELECT_QUERY, &[&msg.0])?;
+
+        if rows.is_empty() {
+            return Ok(None);
+        }
+
+        conn.execute(SEEN_QUERY, &[&msg.0])?;
+
+        Ok(Some(rows.get(0).get(0)))
+    }
+}
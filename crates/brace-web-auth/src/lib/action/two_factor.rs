@@ -0,0 +1,128 @@
+use chrono::Utc;
+use failure::Error;
+use hmac::{Hmac, Mac};
+use postgres::transaction::Transaction;
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::totp;
+use crate::util::salt;
+
+pub const RECOVERY_CODES: usize = 10;
+
+static ENABLED_QUERY: &str = r#"
+    SELECT EXISTS (
+        SELECT 1
+        FROM user_totp
+        WHERE "user" = $1 AND enabled IS NOT NULL
+    )
+"#;
+
+static REQUIRED_QUERY: &str = r#"
+    SELECT EXISTS (
+        SELECT 1
+        FROM user_roles u
+        JOIN roles r ON r.id = u.role
+        WHERE u."user" = $1 AND r.two_factor
+    )
+"#;
+
+static TOTP_QUERY: &str = r#"
+    SELECT secret, last_step
+    FROM user_totp
+    WHERE "user" = $1 AND enabled IS NOT NULL
+    FOR UPDATE
+"#;
+
+static STEP_QUERY: &str = r#"
+    UPDATE user_totp
+    SET last_step = $2
+    WHERE "user" = $1
+"#;
+
+static USE_RECOVERY_QUERY: &str = r#"
+    UPDATE recovery_codes
+    SET used = now()
+    WHERE "user" = $1 AND hash = $2 AND used IS NULL
+"#;
+
+static DELETE_RECOVERY_QUERY: &str = r#"
+    DELETE FROM recovery_codes
+    WHERE "user" = $1
+"#;
+
+static INSERT_RECOVERY_QUERY: &str = r#"
+    INSERT INTO recovery_codes (id, "user", hash)
+    VALUES ($1, $2, $3)
+"#;
+
+pub fn enabled(trans: &Transaction, user: Uuid) -> Result<bool, Error> {
+    let rows = trans.query(ENABLED_QUERY, &[&user])?;
+
+    Ok(rows.get(0).get(0))
+}
+
+pub fn required(trans: &Transaction, user: Uuid) -> Result<bool, Error> {
+    let rows = trans.query(REQUIRED_QUERY, &[&user])?;
+
+    Ok(rows.get(0).get(0))
+}
+
+pub fn check(trans: &Transaction, user: Uuid, code: &str) -> Result<bool, Error> {
+    let rows = trans.query(TOTP_QUERY, &[&user])?;
+
+    if rows.is_empty() {
+        return Ok(false);
+    }
+
+    let row = rows.get(0);
+    let secret: String = row.get(0);
+    let last: Option<i64> = row.get(1);
+
+    if let Some(step) = totp::verify(&secret, code, Utc::now().timestamp(), last) {
+        trans.execute(STEP_QUERY, &[&user, &step])?;
+
+        return Ok(true);
+    }
+
+    let used = trans.execute(USE_RECOVERY_QUERY, &[&user, &recovery_hash(user, code)])?;
+
+    Ok(used > 0)
+}
+
+pub fn recovery_codes(trans: &Transaction, user: Uuid) -> Result<Vec<String>, Error> {
+    trans.execute(DELETE_RECOVERY_QUERY, &[&user])?;
+
+    let mut codes = Vec::with_capacity(RECOVERY_CODES);
+
+    for _ in 0..RECOVERY_CODES {
+        let code = salt()[..10].to_lowercase();
+        let code = format!("{}-{}", &code[..5], &code[5..]);
+
+        trans.execute(
+            INSERT_RECOVERY_QUERY,
+            &[&Uuid::new_v4(), &user, &recovery_hash(user, &code)],
+        )?;
+
+        codes.push(code);
+    }
+
+    Ok(codes)
+}
+
+fn recovery_hash(user: Uuid, code: &str) -> String {
+    let code = code
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect::<String>()
+        .to_lowercase();
+    let mut mac =
+        Hmac::<Sha256>::new_varkey(user.as_bytes()).expect("HMAC can take a key of any size");
+
+    mac.input(code.as_bytes());
+    mac.result()
+        .code()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
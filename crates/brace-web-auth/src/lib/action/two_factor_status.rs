@@ -0,0 +1,59 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+use uuid::Uuid;
+
+use crate::model::TwoFactorStatus;
+
+static QUERY: &str = r#"
+    SELECT
+        EXISTS (
+            SELECT 1
+            FROM user_totp
+            WHERE "user" = $1 AND enabled IS NOT NULL
+        ),
+        EXISTS (
+            SELECT 1
+            FROM user_roles u
+            JOIN roles r ON r.id = u.role
+            WHERE u."user" = $1 AND r.two_factor
+        ),
+        (
+            SELECT count(*)
+            FROM recovery_codes
+            WHERE "user" = $1 AND used IS NULL
+        )
+"#;
+
+pub fn two_factor_status(
+    database: &Database,
+    user: Uuid,
+) -> impl Future<Item = TwoFactorStatus, Error = Error> {
+    database
+        .send(GetTwoFactorStatus(user))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct GetTwoFactorStatus(pub Uuid);
+
+impl Message for GetTwoFactorStatus {
+    type Result = Result<TwoFactorStatus, Error>;
+}
+
+impl Handler<GetTwoFactorStatus> for DatabaseInner {
+    type Result = Result<TwoFactorStatus, Error>;
+
+    fn handle(&mut self, msg: GetTwoFactorStatus, _: &mut Self::Context) -> Self::Result {
+        let conn = self.0.get()?;
+        let rows = conn.query(QUERY, &[&msg.0])?;
+        let row = rows.get(0);
+
+        Ok(TwoFactorStatus {
+            enabled: row.get(0),
+            required: row.get(1),
+            recovery_codes: row.get(2),
+        })
+    }
+}
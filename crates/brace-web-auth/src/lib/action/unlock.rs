@@ -0,0 +1,34 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+
+static QUERY: &str = r#"
+    DELETE FROM login_failures
+    WHERE lower(email) = lower($1)
+"#;
+
+pub fn unlock(database: &Database, email: String) -> impl Future<Item = (), Error = Error> {
+    database
+        .send(Unlock(email))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct Unlock(pub String);
+
+impl Message for Unlock {
+    type Result = Result<(), Error>;
+}
+
+impl Handler<Unlock> for DatabaseInner {
+    type Result = Result<(), Error>;
+
+    fn handle(&mut self, msg: Unlock, _: &mut Self::Context) -> Self::Result {
+        let conn = self.0.get()?;
+
+        conn.execute(QUERY, &[&msg.0])?;
+
+        Ok(())
+    }
+}
@@ -2,25 +2,33 @@ use actix::{Handler, Message};
 use brace_db::{Database, DatabaseInner};
 use failure::{format_err, Error};
 use futures::future::Future;
+use uuid::Uuid;
 
-use crate::model::User;
+use crate::action::email::validate;
+use crate::action::retrieve::user_from_row;
+use crate::model::{User, UserUpdate};
 use crate::util::hash;
 
 static QUERY: &str = r#"
     UPDATE users
-    SET email = $2, password = $3, created = $4, updated = $5
+    SET email = $2, password = COALESCE($3, password), updated = now(),
+        verified = COALESCE($4, verified), approved = COALESCE($5, approved), display_name = $6, avatar = $7, bio = $8
     WHERE id = $1
-    RETURNING id, email, password, created, updated
+    RETURNING id, email, password, created, updated, verified, approved, display_name, avatar, bio
 "#;
 
-pub fn update(database: &Database, user: User) -> impl Future<Item = User, Error = Error> {
+pub fn update(
+    database: &Database,
+    id: Uuid,
+    user: UserUpdate,
+) -> impl Future<Item = User, Error = Error> {
     database
-        .send(Update(user))
+        .send(Update(id, user))
         .map_err(|err| format_err!("{}", err))
         .and_then(|res| res)
 }
 
-pub struct Update(pub User);
+pub struct Update(pub Uuid, pub UserUpdate);
 
 impl Message for Update {
     type Result = Result<User, Error>;
@@ -30,30 +38,34 @@ impl Handler<Update> for DatabaseInner {
     type Result = Result<User, Error>;
 
     fn handle(&mut self, msg: Update, _: &mut Self::Context) -> Self::Result {
-        let conn = self.0.get()?;
-        let rows = conn.query(
-            QUERY,
-            &[
-                &msg.0.id,
-                &msg.0.email,
-                &hash(&msg.0.password)?,
-                &msg.0.created,
-                &msg.0.updated,
-            ],
-        )?;
-
-        if rows.is_empty() {
-            return Err(format_err!("Row not returned"));
-        }
-
-        let row = rows.get(0);
-
-        Ok(User {
-            id: row.get(0),
-            email: row.get(1),
-            password: row.get(2),
-            created: row.get(3),
-            updated: row.get(4),
+        self.transaction(|trans| {
+            validate(trans, msg.0, &msg.1.email)?;
+
+            let password = match msg.1.password() {
+                Some(password) => Some(hash(password)?),
+                None => None,
+            };
+            let rows = trans.query(
+                QUERY,
+                &[
+                    &msg.0,
+                    &msg.1.email,
+                    &password,
+                    &msg.1.verified,
+                    &msg.1.approved,
+                    &msg.1.display_name,
+                    &msg.1.avatar,
+                    &msg.1.bio,
+                ],
+            )?;
+
+            if rows.is_empty() {
+                return Err(format_err!("Row not returned"));
+            }
+
+            let row = rows.get(0);
+
+            Ok(user_from_row(&row))
         })
     }
 }
@@ -0,0 +1,53 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+
+use crate::action::list_roles::role;
+use crate::model::{Role, RoleUpdate};
+
+static UPDATE_QUERY: &str = r#"
+    UPDATE roles
+    SET two_factor = $2
+    WHERE name = $1
+"#;
+
+static SELECT_QUERY: &str = r#"
+    SELECT r.id, r.name, r.label, array_remove(array_agg(p.permission ORDER BY p.permission), NULL),
+           r.two_factor
+    FROM roles r
+    LEFT JOIN role_permissions p ON p.role = r.id
+    WHERE r.name = $1
+    GROUP BY r.id
+"#;
+
+pub fn update_role(
+    database: &Database,
+    name: String,
+    update: RoleUpdate,
+) -> impl Future<Item = Option<Role>, Error = Error> {
+    database
+        .send(UpdateRole(name, update))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct UpdateRole(pub String, pub RoleUpdate);
+
+impl Message for UpdateRole {
+    type Result = Result<Option<Role>, Error>;
+}
+
+impl Handler<UpdateRole> for DatabaseInner {
+    type Result = Result<Option<Role>, Error>;
+
+    fn handle(&mut self, msg: UpdateRole, _: &mut Self::Context) -> Self::Result {
+        self.transaction(|trans| {
+            trans.execute(UPDATE_QUERY, &[&msg.0, &msg.1.two_factor])?;
+
+            let rows = trans.query(SELECT_QUERY, &[&msg.0])?;
+
+            Ok(rows.iter().next().map(|row| role(&row)))
+        })
+    }
+}
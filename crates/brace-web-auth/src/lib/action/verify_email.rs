@@ -0,0 +1,91 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use chrono::{DateTime, Utc};
+use failure::{format_err, Error};
+use futures::future::Future;
+use uuid::Uuid;
+
+use crate::action::retrieve::user_from_row;
+use crate::action::token::split;
+use crate::model::User;
+use crate::util::{constant_eq, digest};
+
+static SELECT_QUERY: &str = r#"
+    SELECT hash, "user", expires, used
+    FROM email_verifications
+    WHERE id = $1
+    FOR UPDATE
+"#;
+
+static USE_QUERY: &str = r#"
+    UPDATE email_verifications
+    SET used = now()
+    WHERE "user" = $1 AND used IS NULL
+"#;
+
+static UPDATE_QUERY: &str = r#"
+    UPDATE users
+    SET verified = true
+    WHERE id = $1
+    RETURNING id, email, password, created, updated, verified, approved, display_name, avatar, bio
+"#;
+
+pub fn verify_email(
+    database: &Database,
+    token: String,
+) -> impl Future<Item = Option<User>, Error = Error> {
+    database
+        .send(VerifyEmail(token))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct VerifyEmail(pub String);
+
+impl Message for VerifyEmail {
+    type Result = Result<Option<User>, Error>;
+}
+
+impl Handler<VerifyEmail> for DatabaseInner {
+    type Result = Result<Option<User>, Error>;
+
+    fn handle(&mut self, msg: VerifyEmail, _: &mut Self::Context) -> Self::Result {
+        self.transaction(|trans| {
+            let (id, secret) = match split(&msg.0) {
+                Some(parts) => parts,
+                None => return Ok(None),
+            };
+            let rows = trans.query(SELECT_QUERY, &[&id])?;
+
+            if rows.is_empty() {
+                return Ok(None);
+            }
+
+            let row = rows.get(0);
+
+            if !constant_eq(&digest(secret), &row.get::<_, String>(0)) {
+                return Ok(None);
+            }
+
+            let user: Uuid = row.get(1);
+            let expires: DateTime<Utc> = row.get(2);
+            let used: Option<DateTime<Utc>> = row.get(3);
+
+            if used.is_some() || expires < Utc::now() {
+                return Ok(None);
+            }
+
+            trans.execute(USE_QUERY, &[&user])?;
+
+            let rows = trans.query(UPDATE_QUERY, &[&user])?;
+
+            if rows.is_empty() {
+                return Err(format_err!("Row not returned"));
+            }
+
+            let row = rows.get(0);
+
+            Ok(Some(user_from_row(&row)))
+        })
+    }
+}
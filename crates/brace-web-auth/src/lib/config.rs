@@ -0,0 +1,429 @@
+use std::fs::read;
+use std::path::PathBuf;
+
+use actix_identity::CookieIdentityPolicy;
+use actix_web::cookie::SameSite;
+use chrono::{DateTime, Duration, Utc};
+use failure::{format_err, Error};
+use jsonwebtoken::{decode, encode, Algorithm, Header, Validation};
+use serde::{Deserialize, Serialize};
+
+use crate::model::{Claims, User};
+use crate::util::salt;
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct AuthConfig {
+    pub secret: Option<String>,
+    pub key_file: Option<PathBuf>,
+    pub public_key_file: Option<PathBuf>,
+    pub algorithm: AuthAlgorithm,
+    pub issuer: String,
+    pub audience: Option<String>,
+    pub lifetime: i64,
+    pub refresh_lifetime: i64,
+    pub reset_lifetime: i64,
+    pub reset_throttle: ResetThrottleConfig,
+    pub registration: RegistrationMode,
+    pub verify_lifetime: i64,
+    pub lockout: LockoutConfig,
+    pub cookie: AuthCookieConfig,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            secret: None,
+            key_file: None,
+            public_key_file: None,
+            algorithm: AuthAlgorithm::HS256,
+            issuer: "localhost".to_string(),
+            audience: None,
+            lifetime: 900,
+            refresh_lifetime: 2_592_000,
+            reset_lifetime: 3600,
+            reset_throttle: ResetThrottleConfig::default(),
+            registration: RegistrationMode::Closed,
+            verify_lifetime: 86400,
+            lockout: LockoutConfig::default(),
+            cookie: AuthCookieConfig::default(),
+        }
+    }
+}
+
+impl AuthConfig {
+    pub fn generate() -> Self {
+        Self {
+            secret: Some(format!("{}{}", salt(), salt())),
+            cookie: AuthCookieConfig {
+                key: Some(format!("{}{}", salt(), salt())),
+                ..AuthCookieConfig::default()
+            },
+            ..Self::default()
+        }
+    }
+
+    pub fn has_secret(&self) -> bool {
+        self.secret.is_some() || self.key_file.is_some()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(default)]
+pub struct ResetThrottleConfig {
+    pub attempts: i64,
+    pub address_attempts: i64,
+    pub window: i64,
+    pub forwarded: bool,
+}
+
+impl Default for ResetThrottleConfig {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            address_attempts: 10,
+            window: 3600,
+            forwarded: false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RegistrationMode {
+    Closed,
+    Open,
+    Approval,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(default)]
+pub struct LockoutConfig {
+    pub attempts: i64,
+    pub address_attempts: i64,
+    pub window: i64,
+    pub duration: i64,
+    pub max_duration: i64,
+    pub forwarded: bool,
+}
+
+impl Default for LockoutConfig {
+    fn default() -> Self {
+        Self {
+            attempts: 5,
+            address_attempts: 20,
+            window: 3600,
+            duration: 60,
+            max_duration: 3600,
+            forwarded: false,
+        }
+    }
+}
+
+impl LockoutConfig {
+    pub fn remaining(&self, failures: i64, threshold: i64, elapsed: i64) -> Option<i64> {
+        if threshold <= 0 || failures < threshold {
+            return None;
+        }
+
+        let exponent = (failures - threshold).min(20) as u32;
+        let duration = self
+            .duration
+            .saturating_mul(2i64.saturating_pow(exponent))
+            .min(self.max_duration);
+        let remaining = duration - elapsed;
+
+        if remaining > 0 {
+            Some(remaining)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct AuthCookieConfig {
+    pub key: Option<String>,
+    pub key_file: Option<PathBuf>,
+    pub name: String,
+    pub domain: Option<String>,
+    pub path: String,
+    pub secure: bool,
+    pub same_site: Option<AuthCookieSameSite>,
+    pub max_age: Option<i64>,
+    pub idle_timeout: Option<i64>,
+}
+
+impl Default for AuthCookieConfig {
+    fn default() -> Self {
+        Self {
+            key: None,
+            key_file: None,
+            name: "auth".to_string(),
+            domain: None,
+            path: "/".to_string(),
+            secure: false,
+            same_site: Some(AuthCookieSameSite::Lax),
+            max_age: None,
+            idle_timeout: None,
+        }
+    }
+}
+
+impl AuthCookieConfig {
+    pub fn has_key(&self) -> bool {
+        self.key.is_some() || self.key_file.is_some()
+    }
+
+    pub fn key(&self) -> Result<Vec<u8>, Error> {
+        let key = match (&self.key_file, &self.key) {
+            (Some(file), _) => read(file)?,
+            (None, Some(key)) => key.as_bytes().to_vec(),
+            (None, None) => return Err(format_err!("No cookie key configured")),
+        };
+
+        if key.len() < 32 {
+            return Err(format_err!("The cookie key must be at least 32 bytes long"));
+        }
+
+        Ok(key)
+    }
+
+    pub fn policy(&self, key: &[u8]) -> CookieIdentityPolicy {
+        let mut policy = CookieIdentityPolicy::new(key)
+            .name(self.name.as_str())
+            .path(self.path.as_str())
+            .secure(self.secure);
+
+        if let Some(domain) = &self.domain {
+            policy = policy.domain(domain.as_str());
+        }
+
+        if let Some(same_site) = self.same_site {
+            policy = policy.same_site(same_site.into());
+        }
+
+        if let Some(max_age) = self.max_age {
+            policy = policy.max_age(max_age);
+        }
+
+        if let Some(idle_timeout) = self.idle_timeout {
+            policy = policy.visit_deadline(Duration::seconds(idle_timeout));
+        }
+
+        policy
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthCookieSameSite {
+    Strict,
+    Lax,
+    None,
+}
+
+impl Into<SameSite> for AuthCookieSameSite {
+    fn into(self) -> SameSite {
+        match self {
+            AuthCookieSameSite::Strict => SameSite::Strict,
+            AuthCookieSameSite::Lax => SameSite::Lax,
+            AuthCookieSameSite::None => SameSite::None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum AuthAlgorithm {
+    HS256,
+    RS256,
+    ES256,
+}
+
+impl Into<Algorithm> for AuthAlgorithm {
+    fn into(self) -> Algorithm {
+        match self {
+            AuthAlgorithm::HS256 => Algorithm::HS256,
+            AuthAlgorithm::RS256 => Algorithm::RS256,
+            AuthAlgorithm::ES256 => Algorithm::ES256,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Jwt {
+    algorithm: Algorithm,
+    encoding_key: Vec<u8>,
+    decoding_key: Vec<u8>,
+    issuer: String,
+    audience: Option<String>,
+    lifetime: i64,
+    refresh_lifetime: i64,
+}
+
+impl Jwt {
+    pub fn from_config(config: &AuthConfig) -> Result<Self, Error> {
+        let (encoding_key, decoding_key) = match config.algorithm {
+            AuthAlgorithm::HS256 => {
+                let key = match (&config.key_file, &config.secret) {
+                    (Some(file), _) => read(file)?,
+                    (None, Some(secret)) => secret.as_bytes().to_vec(),
+                    (None, None) => return Err(format_err!("No token signing secret configured")),
+                };
+
+                if key.is_empty() {
+                    return Err(format_err!("The token signing secret is empty"));
+                }
+
+                (key.clone(), key)
+            }
+            AuthAlgorithm::RS256 | AuthAlgorithm::ES256 => {
+                match (&config.key_file, &config.public_key_file) {
+                    (Some(key_file), Some(public_key_file)) => {
+                        (read(key_file)?, read(public_key_file)?)
+                    }
+                    _ => {
+                        return Err(format_err!(
+                            "The {:?} algorithm requires a key file and a public key file",
+                            config.algorithm
+                        ))
+                    }
+                }
+            }
+        };
+
+        if config.lifetime <= 0 || config.refresh_lifetime <= 0 {
+            return Err(format_err!("The token lifetimes must be positive"));
+        }
+
+        Ok(Self {
+            algorithm: config.algorithm.into(),
+            encoding_key,
+            decoding_key,
+            issuer: config.issuer.clone(),
+            audience: config.audience.clone(),
+            lifetime: config.lifetime,
+            refresh_lifetime: config.refresh_lifetime,
+        })
+    }
+
+    pub fn lifetime(&self) -> i64 {
+        self.lifetime
+    }
+
+    pub fn refresh_expires(&self) -> DateTime<Utc> {
+        Utc::now() + Duration::seconds(self.refresh_lifetime)
+    }
+
+    pub fn encode(&self, user: &User) -> Result<String, Error> {
+        let claims = Claims::new(user, &self.issuer, self.audience.clone(), self.lifetime);
+
+        Ok(encode(
+            &Header::new(self.algorithm),
+            &claims,
+            &self.encoding_key,
+        )?)
+    }
+
+    pub fn decode(&self, token: &str) -> Result<Claims, Error> {
+        let mut validation = Validation::new(self.algorithm);
+
+        validation.iss = Some(self.issuer.clone());
+
+        if let Some(audience) = &self.audience {
+            validation.set_audience(audience);
+        }
+
+        let res = decode::<Claims>(token, &self.decoding_key, &validation)?;
+
+        Ok(res.claims)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AuthAlgorithm, AuthConfig, AuthCookieConfig, Jwt, LockoutConfig};
+    use crate::model::User;
+
+    fn user() -> User {
+        User {
+            email: "user@domain.test".to_string(),
+            ..User::default()
+        }
+    }
+
+    #[test]
+    fn test_jwt_requires_secret() {
+        assert!(Jwt::from_config(&AuthConfig::default()).is_err());
+        assert!(Jwt::from_config(&AuthConfig {
+            algorithm: AuthAlgorithm::RS256,
+            ..AuthConfig::generate()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_cookie_key() {
+        assert!(AuthConfig::default().cookie.key().is_err());
+        assert!(AuthCookieConfig {
+            key: Some("short".to_string()),
+            ..AuthCookieConfig::default()
+        }
+        .key()
+        .is_err());
+        assert_eq!(AuthConfig::generate().cookie.key().unwrap().len(), 64);
+    }
+
+    #[test]
+    fn test_jwt_round_trip() {
+        let jwt = Jwt::from_config(&AuthConfig::generate()).unwrap();
+        let token = jwt.encode(&user()).unwrap();
+
+        assert_eq!(jwt.decode(&token).unwrap().email, "user@domain.test");
+        assert!(Jwt::from_config(&AuthConfig::generate())
+            .unwrap()
+            .decode(&token)
+            .is_err());
+    }
+
+    #[test]
+    fn test_jwt_validates_claims() {
+        let config = AuthConfig {
+            audience: Some("api".to_string()),
+            ..AuthConfig::generate()
+        };
+        let jwt = Jwt::from_config(&config).unwrap();
+        let token = jwt.encode(&user()).unwrap();
+
+        assert!(jwt.decode(&token).is_ok());
+
+        let other = Jwt::from_config(&AuthConfig {
+            issuer: "elsewhere".to_string(),
+            ..config.clone()
+        })
+        .unwrap();
+
+        assert!(other.decode(&token).is_err());
+
+        let other = Jwt::from_config(&AuthConfig {
+            audience: Some("web".to_string()),
+            ..config
+        })
+        .unwrap();
+
+        assert!(other.decode(&token).is_err());
+    }
+
+    #[test]
+    fn test_lockout_remaining() {
+        let config = LockoutConfig::default();
+
+        assert_eq!(config.remaining(4, 5, 0), None);
+        assert_eq!(config.remaining(5, 5, 10), Some(50));
+        assert_eq!(config.remaining(5, 5, 60), None);
+        assert_eq!(config.remaining(7, 5, 0), Some(240));
+        assert_eq!(config.remaining(100, 5, 0), Some(3600));
+        assert_eq!(config.remaining(100, 0, 0), None);
+    }
+}
@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use brace_web_form::{action, field, Form, FormBuilder};
+use failure::Error;
+
+pub struct ApiKeyForm;
+
+impl FormBuilder for ApiKeyForm {
+    type Future = Result<Form, Error>;
+
+    fn build(&self, mut form: Form) -> Self::Future {
+        form.insert(
+            field::text("name")
+                .label("Name")
+                .description("A name that reminds you of where the key is used.")
+                .value(form.data().get::<String>("name")?),
+        );
+
+        form.insert(
+            field::select("expires")
+                .label("Expires")
+                .description("Leave empty for a key that does not expire.")
+                .value(form.data().get::<String>("expires")?)
+                .options(expiry()),
+        );
+
+        form.insert(
+            field::text("scopes")
+                .label("Scopes")
+                .description(
+                    "A comma separated list of permissions to limit the key to, such as \
+                     \"create page, edit own page\". Leave empty to allow all of your \
+                     permissions.",
+                )
+                .value(form.data().get::<String>("scopes")?),
+        );
+
+        form.action(action::submit("/account/api-keys").label("Create API key"));
+        form.action(action::cancel("/"));
+
+        Ok(form)
+    }
+}
+
+fn expiry() -> HashMap<String, String> {
+    [
+        ("30", "In 30 days"),
+        ("90", "In 90 days"),
+        ("365", "In a year"),
+    ]
+    .iter()
+    .map(|(value, label)| ((*value).to_owned(), (*label).to_owned()))
+    .collect()
+}
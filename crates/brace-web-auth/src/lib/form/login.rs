@@ -20,6 +20,7 @@ impl FormBuilder for LoginForm {
 
         form.action(action::submit(""));
         form.action(action::cancel("/"));
+        form.action(action::cancel("/password/forgot").label("Forgot password"));
 
         Ok(form)
     }
@@ -1,2 +1,6 @@
+pub mod api_key;
 pub mod login;
+pub mod password;
+pub mod register;
+pub mod two_factor;
 pub mod user;
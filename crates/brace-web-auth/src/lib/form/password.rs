@@ -0,0 +1,37 @@
+use brace_web_form::{action, field, Form, FormBuilder};
+use failure::Error;
+
+pub struct PasswordRequestForm;
+
+impl FormBuilder for PasswordRequestForm {
+    type Future = Result<Form, Error>;
+
+    fn build(&self, mut form: Form) -> Self::Future {
+        form.insert(
+            field::email("email")
+                .label("Email")
+                .value(form.data().get::<String>("email")?),
+        );
+
+        form.action(action::submit("").label("Send reset link"));
+        form.action(action::cancel("/login"));
+
+        Ok(form)
+    }
+}
+
+pub struct PasswordChangeForm;
+
+impl FormBuilder for PasswordChangeForm {
+    type Future = Result<Form, Error>;
+
+    fn build(&self, mut form: Form) -> Self::Future {
+        form.insert(field::password("password").label("New password"));
+        form.insert(field::password("confirm").label("Confirm password"));
+
+        form.action(action::submit("").label("Change password"));
+        form.action(action::cancel("/login"));
+
+        Ok(form)
+    }
+}
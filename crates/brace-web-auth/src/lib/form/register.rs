@@ -0,0 +1,24 @@
+use brace_web_form::{action, field, Form, FormBuilder};
+use failure::Error;
+
+pub struct RegisterForm;
+
+impl FormBuilder for RegisterForm {
+    type Future = Result<Form, Error>;
+
+    fn build(&self, mut form: Form) -> Self::Future {
+        form.insert(
+            field::email("email")
+                .label("Email")
+                .value(form.data().get::<String>("email")?),
+        );
+
+        form.insert(field::password("password").label("Password"));
+        form.insert(field::password("confirm").label("Confirm password"));
+
+        form.action(action::submit("").label("Register"));
+        form.action(action::cancel("/login"));
+
+        Ok(form)
+    }
+}
@@ -0,0 +1,68 @@
+use brace_web_form::{action, field, Form, FormBuilder};
+use failure::Error;
+
+pub struct TwoFactorForm;
+
+impl FormBuilder for TwoFactorForm {
+    type Future = Result<Form, Error>;
+
+    fn build(&self, mut form: Form) -> Self::Future {
+        form.insert(field::hidden("challenge").value(form.data().get::<String>("challenge")?));
+
+        form.insert(
+            field::text("code")
+                .label("Authentication code")
+                .description("The code from your authenticator app or one of your recovery codes."),
+        );
+
+        form.action(action::submit("/login/two-factor").label("Log in"));
+        form.action(action::cancel("/login"));
+
+        Ok(form)
+    }
+}
+
+pub struct TwoFactorEnrollForm;
+
+impl FormBuilder for TwoFactorEnrollForm {
+    type Future = Result<Form, Error>;
+
+    fn build(&self, mut form: Form) -> Self::Future {
+        form.insert(field::hidden("challenge").value(form.data().get::<String>("challenge")?));
+
+        form.insert(
+            field::text("code")
+                .label("Authentication code")
+                .description("The code that your authenticator app currently shows."),
+        );
+
+        form.action(
+            action::submit("/login/two-factor/enroll").label("Enable two-factor authentication"),
+        );
+        form.action(action::cancel("/login"));
+
+        Ok(form)
+    }
+}
+
+pub struct TwoFactorCodeForm {
+    pub url: &'static str,
+    pub label: &'static str,
+}
+
+impl FormBuilder for TwoFactorCodeForm {
+    type Future = Result<Form, Error>;
+
+    fn build(&self, mut form: Form) -> Self::Future {
+        form.insert(
+            field::text("code")
+                .label("Authentication code")
+                .description("The code that your authenticator app currently shows."),
+        );
+
+        form.action(action::submit(self.url).label(self.label));
+        form.action(action::cancel("/"));
+
+        Ok(form)
+    }
+}
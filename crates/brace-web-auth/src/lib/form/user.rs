@@ -1,5 +1,6 @@
 use brace_web_form::{action, field, Form, FormBuilder};
-use chrono::{DateTime, NaiveDateTime, Utc};
+use std::collections::HashMap;
+
 use failure::Error;
 
 pub struct UserForm;
@@ -8,8 +9,6 @@ impl FormBuilder for UserForm {
     type Future = Result<Form, Error>;
 
     fn build(&self, mut form: Form) -> Self::Future {
-        form.insert(field::hidden("id").value(form.data().get::<String>("id")?));
-
         form.insert(
             field::email("email")
                 .label("Email")
@@ -20,29 +19,68 @@ impl FormBuilder for UserForm {
         form.insert(
             field::password("password")
                 .label("Password")
-                .description("The password of the user."),
+                .description("The password of the user. Leave empty to keep the current one."),
+        );
+
+        form.insert(
+            field::select("verified")
+                .label("Verified")
+                .description("Whether the user has confirmed their email address.")
+                .value(
+                    form.data()
+                        .get::<Option<bool>>("verified")?
+                        .map(|value| value.to_string())
+                        .unwrap_or_default(),
+                )
+                .options(yes_no())
+                .required(true),
         );
 
-        let created = DateTime::<Utc>::from_utc(
-            NaiveDateTime::parse_from_str(
-                &form.data().get::<String>("created")?,
-                "%Y-%m-%dT%H:%M",
-            )?,
-            Utc,
+        form.insert(
+            field::select("approved")
+                .label("Approved")
+                .description("Only approved users can log in.")
+                .value(
+                    form.data()
+                        .get::<Option<bool>>("approved")?
+                        .map(|value| value.to_string())
+                        .unwrap_or_default(),
+                )
+                .options(yes_no())
+                .required(true),
         );
 
         form.insert(
-            field::datetime("created")
-                .label("Created")
-                .description("The date/time of when the user was first created.")
-                .value(created),
+            field::text("display_name")
+                .label("Display name")
+                .description("The name shown as the author of pages.")
+                .value(
+                    form.data()
+                        .get::<Option<String>>("display_name")?
+                        .unwrap_or_default(),
+                ),
         );
 
         form.insert(
-            field::datetime("updated")
-                .label("Updated")
-                .description("The date/time of when the user was last updated.")
-                .value(Utc::now()),
+            field::text("avatar")
+                .label("Avatar")
+                .description("The URL of an image of the user.")
+                .value(
+                    form.data()
+                        .get::<Option<String>>("avatar")?
+                        .unwrap_or_default(),
+                ),
+        );
+
+        form.insert(
+            field::textarea("bio")
+                .label("Bio")
+                .description("A short introduction of the user.")
+                .value(
+                    form.data()
+                        .get::<Option<String>>("bio")?
+                        .unwrap_or_default(),
+                ),
         );
 
         form.action(action::submit(""));
@@ -51,3 +89,10 @@ impl FormBuilder for UserForm {
         Ok(form)
     }
 }
+
+fn yes_no() -> HashMap<String, String> {
+    [("true", "Yes"), ("false", "No")]
+        .iter()
+        .map(|(value, label)| ((*value).to_owned(), (*label).to_owned()))
+        .collect()
+}
@@ -0,0 +1,187 @@
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use actix_web::dev::Payload;
+use actix_web::error::{Error, ErrorForbidden, ErrorInternalServerError, InternalError};
+use actix_web::web::Data;
+use actix_web::{FromRequest, HttpRequest, HttpResponse};
+use brace_db::Database;
+use futures::future::{err, Either, Future};
+
+use crate::model::{CurrentAuth, CurrentUser, Permission, User};
+
+type BoxedFuture<I, E> = Box<dyn Future<Item = I, Error = E>>;
+
+#[derive(Debug, Clone)]
+pub struct Access {
+    pub user: User,
+    pub permissions: Vec<Permission>,
+}
+
+impl Access {
+    pub fn has(&self, permission: Permission) -> bool {
+        self.permissions.contains(&permission)
+    }
+
+    pub fn scoped(mut self, scopes: Option<Vec<Permission>>) -> Self {
+        if let Some(scopes) = scopes {
+            self.permissions
+                .retain(|permission| scopes.contains(permission));
+        }
+
+        self
+    }
+
+    pub fn require(&self, permission: Permission) -> Result<(), Error> {
+        if self.has(permission) {
+            Ok(())
+        } else {
+            Err(ErrorForbidden("Forbidden"))
+        }
+    }
+}
+
+pub trait Permit: 'static {
+    fn permits(access: &Access) -> bool;
+}
+
+pub struct Authenticated;
+
+impl Permit for Authenticated {
+    fn permits(_: &Access) -> bool {
+        true
+    }
+}
+
+macro_rules! permit {
+    ($($name:ident),*) => {
+        $(
+            pub struct $name;
+
+            impl Permit for $name {
+                fn permits(access: &Access) -> bool {
+                    access.has(Permission::$name)
+                }
+            }
+        )*
+    };
+}
+
+permit!(
+    CreatePage,
+    EditAnyPage,
+    EditOwnPage,
+    DeleteAnyPage,
+    DeleteOwnPage,
+    ManageUsers
+);
+
+pub struct WebGuard<P: Permit>(Access, PhantomData<P>);
+
+impl<P: Permit> WebGuard<P> {
+    pub fn into_inner(self) -> Access {
+        self.0
+    }
+}
+
+impl<P: Permit> Deref for WebGuard<P> {
+    type Target = Access;
+
+    fn deref(&self) -> &Access {
+        &self.0
+    }
+}
+
+impl<P: Permit> FromRequest for WebGuard<P> {
+    type Error = Error;
+    type Future = BoxedFuture<Self, Self::Error>;
+    type Config = ();
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let database = Data::<Database>::from_request(req, payload);
+
+        Box::new(
+            CurrentUser::from_request(req, payload).and_then(move |user| match (user, database) {
+                (CurrentUser::Authenticated(user), Ok(database)) => Either::A(
+                    access(&database, user)
+                        .and_then(permit::<P>)
+                        .map(|access| WebGuard(access, PhantomData)),
+                ),
+                _ => Either::B(err(ErrorForbidden("Forbidden"))),
+            }),
+        )
+    }
+}
+
+pub struct ApiGuard<P: Permit>(Access, PhantomData<P>);
+
+impl<P: Permit> ApiGuard<P> {
+    pub fn into_inner(self) -> Access {
+        self.0
+    }
+}
+
+impl<P: Permit> Deref for ApiGuard<P> {
+    type Target = Access;
+
+    fn deref(&self) -> &Access {
+        &self.0
+    }
+}
+
+impl<P: Permit> FromRequest for ApiGuard<P> {
+    type Error = Error;
+    type Future = BoxedFuture<Self, Self::Error>;
+    type Config = ();
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let database = Data::<Database>::from_request(req, payload);
+
+        Box::new(
+            CurrentAuth::from_request(req, payload).and_then(move |auth| {
+                let (user, scopes) = match auth {
+                    CurrentAuth::Authenticated(user) => (user, None),
+                    CurrentAuth::Key(user, scopes) => (user, scopes),
+                    CurrentAuth::Unauthenticated => return Either::B(err(unauthorized())),
+                };
+
+                match database {
+                    Ok(database) => Either::A(
+                        access(&database, user)
+                            .map(move |access| access.scoped(scopes))
+                            .and_then(permit::<P>)
+                            .map(|access| ApiGuard(access, PhantomData)),
+                    ),
+                    Err(_) => Either::B(err(unauthorized())),
+                }
+            }),
+        )
+    }
+}
+
+fn access(database: &Database, user: User) -> impl Future<Item = Access, Error = Error> {
+    crate::action::permissions::permissions(database, user.id)
+        .map_err(ErrorInternalServerError)
+        .map(move |permissions| Access { user, permissions })
+}
+
+fn permit<P: Permit>(access: Access) -> Result<Access, Error> {
+    if P::permits(&access) {
+        Ok(access)
+    } else {
+        Err(ErrorForbidden("Forbidden"))
+    }
+}
+
+fn unauthorized() -> Error {
+    InternalError::from_response(
+        "Unauthorized",
+        HttpResponse::Unauthorized()
+            .header(
+                "WWW-Authenticate",
+                r#"Bearer realm="localhost", charset="UTF-8""#,
+            )
+            .finish(),
+    )
+    .into()
+}
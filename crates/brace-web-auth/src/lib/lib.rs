@@ -1,5 +1,10 @@
 pub mod action;
+pub mod config;
 pub mod form;
+pub mod guard;
 pub mod model;
 pub mod route;
+pub mod schema;
+pub mod session;
+pub mod totp;
 pub mod util;
@@ -1,26 +1,35 @@
+use std::str::FromStr;
+
 use actix_identity::Identity;
 use actix_web::dev::Payload;
 use actix_web::error::Error;
 use actix_web::web::Data;
 use actix_web::{FromRequest, HttpRequest};
+use brace_db::Database;
+use brace_web::pager::Order;
 use chrono::{DateTime, Duration, Local, Utc};
+use failure::{format_err, Error as FailureError};
 use futures::future::{ok, Either, Future, FutureResult};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use uuid::Uuid;
 
-use crate::util::decode_token;
+use crate::config::Jwt;
+use crate::util::{bearer_token, decode_token};
 
 type BoxedFuture<I, E> = Box<dyn Future<Item = I, Error = E>>;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Debug, Clone)]
 pub struct User {
     pub id: Uuid,
     pub email: String,
     pub password: String,
-    #[serde(with = "serde_datetime_utc")]
     pub created: DateTime<Utc>,
-    #[serde(with = "serde_datetime_utc")]
     pub updated: DateTime<Utc>,
+    pub verified: bool,
+    pub approved: bool,
+    pub display_name: Option<String>,
+    pub avatar: Option<String>,
+    pub bio: Option<String>,
 }
 
 impl Default for User {
@@ -31,14 +40,163 @@ impl Default for User {
             password: "".to_string(),
             created: Utc::now(),
             updated: Utc::now(),
+            verified: true,
+            approved: true,
+            display_name: None,
+            avatar: None,
+            bio: None,
+        }
+    }
+}
+
+impl User {
+    pub fn inactive_reason(&self) -> Option<&'static str> {
+        if !self.verified {
+            Some("The email address of this account has not been verified yet")
+        } else if !self.approved {
+            Some("This account is awaiting approval by an administrator")
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PublicUser {
+    pub id: Uuid,
+    pub email: String,
+    #[serde(with = "serde_datetime_utc")]
+    pub created: DateTime<Utc>,
+    #[serde(with = "serde_datetime_utc")]
+    pub updated: DateTime<Utc>,
+    pub verified: bool,
+    pub approved: bool,
+    pub display_name: Option<String>,
+    pub avatar: Option<String>,
+    pub bio: Option<String>,
+}
+
+impl PublicUser {
+    pub fn name(&self) -> &str {
+        self.display_name.as_ref().unwrap_or(&self.email)
+    }
+}
+
+impl From<User> for PublicUser {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id,
+            email: user.email,
+            created: user.created,
+            updated: user.updated,
+            verified: user.verified,
+            approved: user.approved,
+            display_name: user.display_name,
+            avatar: user.avatar,
+            bio: user.bio,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct NewUser {
+    pub id: Uuid,
+    pub email: String,
+    pub password: String,
+    pub verified: bool,
+    pub approved: bool,
+    #[serde(deserialize_with = "empty_as_none")]
+    pub display_name: Option<String>,
+    #[serde(deserialize_with = "empty_as_none")]
+    pub avatar: Option<String>,
+    #[serde(deserialize_with = "empty_as_none")]
+    pub bio: Option<String>,
+}
+
+impl Default for NewUser {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            email: "".to_string(),
+            password: "".to_string(),
+            verified: true,
+            approved: true,
+            display_name: None,
+            avatar: None,
+            bio: None,
+        }
+    }
+}
+
+impl From<NewUser> for User {
+    fn from(user: NewUser) -> Self {
+        Self {
+            id: user.id,
+            email: user.email,
+            password: user.password,
+            verified: user.verified,
+            approved: user.approved,
+            display_name: user.display_name,
+            avatar: user.avatar,
+            bio: user.bio,
+            ..User::default()
         }
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UserUpdate {
+    pub email: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(default)]
+    pub verified: Option<bool>,
+    #[serde(default)]
+    pub approved: Option<bool>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub display_name: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub avatar: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub bio: Option<String>,
+}
+
+impl UserUpdate {
+    pub fn password(&self) -> Option<&str> {
+        self.password
+            .as_ref()
+            .map(String::as_str)
+            .filter(|password| !password.is_empty())
+    }
+}
+
+impl From<User> for UserUpdate {
+    fn from(user: User) -> Self {
+        Self {
+            email: user.email,
+            password: None,
+            verified: Some(user.verified),
+            approved: Some(user.approved),
+            display_name: user.display_name,
+            avatar: user.avatar,
+            bio: user.bio,
+        }
+    }
+}
+
+fn empty_as_none<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    let value: Option<String> = Deserialize::deserialize(deserializer)?;
+
+    Ok(value.filter(|value| !value.trim().is_empty()))
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct UserAuth {
     pub email: String,
     pub password: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
 }
 
 impl Default for UserAuth {
@@ -46,10 +204,105 @@ impl Default for UserAuth {
         Self {
             email: "".to_string(),
             password: "".to_string(),
+            code: None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct TwoFactorAuth {
+    pub challenge: String,
+    pub code: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct TwoFactorCode {
+    pub code: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct TwoFactorStatus {
+    pub enabled: bool,
+    pub required: bool,
+    pub recovery_codes: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct PasswordRequest {
+    pub email: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct Registration {
+    pub email: String,
+    pub password: String,
+    pub confirm: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct PasswordChange {
+    pub password: String,
+    pub confirm: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct UserFilter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_after: Option<DateTime<Utc>>,
+    pub sort: UserSort,
+    pub order: Order,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum UserSort {
+    Email,
+    Created,
+    Updated,
+}
+
+impl UserSort {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UserSort::Email => "email",
+            UserSort::Created => "created",
+            UserSort::Updated => "updated",
         }
     }
 }
 
+impl Default for UserSort {
+    fn default() -> Self {
+        UserSort::Email
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum LoginAttempt {
+    Success(User),
+    TwoFactor(User),
+    Enroll(User),
+    Failure(Option<i64>),
+    Locked(i64),
+}
+
+#[derive(Debug, Clone)]
+pub enum ChallengeAttempt {
+    Success(User),
+    Enrolled(User, Vec<String>),
+    Failure(User, Option<i64>),
+    Expired,
+    Locked(i64),
+}
+
 pub enum CurrentUser {
     Anonymous,
     Authenticated(User),
@@ -68,8 +321,10 @@ impl FromRequest for CurrentUser {
                         return Either::B(Box::new(
                             crate::action::retrieve::retrieve(&database, uuid).then(move |user| {
                                 match user {
-                                    Ok(user) => ok(CurrentUser::Authenticated(user)),
-                                    Err(_) => {
+                                    Ok(ref user) if user.inactive_reason().is_none() => {
+                                        ok(CurrentUser::Authenticated(user.clone()))
+                                    }
+                                    _ => {
                                         id.forget();
                                         ok(CurrentUser::Anonymous)
                                     }
@@ -90,6 +345,7 @@ impl FromRequest for CurrentUser {
 pub enum CurrentAuth {
     Unauthenticated,
     Authenticated(User),
+    Key(User, Option<Vec<Permission>>),
 }
 
 impl FromRequest for CurrentAuth {
@@ -98,24 +354,28 @@ impl FromRequest for CurrentAuth {
     type Config = ();
 
     fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
-        if let Some(header) = req.headers().get("Authorization") {
-            if let Ok(header) = header.to_str() {
-                let parts = header.split(' ').collect::<Vec<&str>>();
-
-                if parts.len() == 2 && parts[0] == "Bearer" {
-                    if let Ok(auth) = decode_token(parts[1]) {
-                        if let Ok(database) = Data::from_request(req, payload) {
-                            return Either::B(Box::new(
-                                crate::action::locate::locate(&database, auth.email).then(
-                                    move |res| match res {
-                                        Ok(user) => ok(CurrentAuth::Authenticated(user)),
-                                        Err(_) => ok(CurrentAuth::Unauthenticated),
-                                    },
-                                ),
-                            ));
-                        }
+        if let Some(token) = bearer_token(req) {
+            if let Ok(database) = Data::<Database>::from_request(req, payload) {
+                if let Ok(jwt) = Data::<Jwt>::from_request(req, payload) {
+                    if let Ok(claims) = decode_token(&jwt, token) {
+                        return Either::B(Box::new(
+                            crate::action::authenticate::authenticate(&database, claims).then(
+                                move |res| match res {
+                                    Ok(user) => ok(CurrentAuth::Authenticated(user)),
+                                    Err(_) => ok(CurrentAuth::Unauthenticated),
+                                },
+                            ),
+                        ));
                     }
                 }
+
+                return Either::B(Box::new(
+                    crate::action::authenticate_key::authenticate_key(&database, token.to_owned())
+                        .then(move |res| match res {
+                            Ok(Some((user, scopes))) => ok(CurrentAuth::Key(user, scopes)),
+                            _ => ok(CurrentAuth::Unauthenticated),
+                        }),
+                ));
             }
         }
 
@@ -146,23 +406,156 @@ impl From<User> for SlimUser {
 pub struct Claims {
     pub email: String,
     pub iss: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aud: Option<String>,
     pub sub: String,
+    pub jti: Uuid,
     pub iat: i64,
     pub exp: i64,
 }
 
 impl Claims {
-    pub fn with_email(email: &str) -> Self {
+    pub fn new(user: &User, issuer: &str, audience: Option<String>, lifetime: i64) -> Self {
         Claims {
-            iss: "localhost".into(),
-            sub: "auth".into(),
-            email: email.to_owned(),
+            iss: issuer.to_owned(),
+            aud: audience,
+            sub: user.id.to_string(),
+            jti: Uuid::new_v4(),
+            email: user.email.clone(),
             iat: Local::now().timestamp(),
-            exp: (Local::now() + Duration::hours(24)).timestamp(),
+            exp: (Local::now() + Duration::seconds(lifetime)).timestamp(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RefreshAuth {
+    pub refresh_token: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct RevokeAuth {
+    pub refresh_token: Option<String>,
+    pub all: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Permission {
+    #[serde(rename = "create page")]
+    CreatePage,
+    #[serde(rename = "edit any page")]
+    EditAnyPage,
+    #[serde(rename = "edit own page")]
+    EditOwnPage,
+    #[serde(rename = "delete any page")]
+    DeleteAnyPage,
+    #[serde(rename = "delete own page")]
+    DeleteOwnPage,
+    #[serde(rename = "manage users")]
+    ManageUsers,
+}
+
+impl Permission {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Permission::CreatePage => "create page",
+            Permission::EditAnyPage => "edit any page",
+            Permission::EditOwnPage => "edit own page",
+            Permission::DeleteAnyPage => "delete any page",
+            Permission::DeleteOwnPage => "delete own page",
+            Permission::ManageUsers => "manage users",
+        }
+    }
+}
+
+impl FromStr for Permission {
+    type Err = FailureError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "create page" => Ok(Permission::CreatePage),
+            "edit any page" => Ok(Permission::EditAnyPage),
+            "edit own page" => Ok(Permission::EditOwnPage),
+            "delete any page" => Ok(Permission::DeleteAnyPage),
+            "delete own page" => Ok(Permission::DeleteOwnPage),
+            "manage users" => Ok(Permission::ManageUsers),
+            _ => Err(format_err!("Invalid permission: {}", s)),
         }
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Role {
+    pub id: Uuid,
+    pub name: String,
+    pub label: String,
+    pub permissions: Vec<Permission>,
+    pub two_factor: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct RoleUpdate {
+    pub two_factor: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub name: String,
+    pub scopes: Option<Vec<Permission>>,
+    pub created: DateTime<Utc>,
+    pub expires: Option<DateTime<Utc>>,
+    pub last_used: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ApiKeyRequest {
+    pub name: String,
+    pub expires: String,
+    pub scopes: String,
+}
+
+impl ApiKeyRequest {
+    pub fn expires(&self) -> Result<Option<DateTime<Utc>>, FailureError> {
+        match self.expires.trim() {
+            "" => Ok(None),
+            days => match days.parse::<i64>() {
+                Ok(days) if days > 0 => Ok(Some(Utc::now() + Duration::days(days))),
+                _ => Err(format_err!("Invalid number of days: {}", days)),
+            },
+        }
+    }
+
+    pub fn scopes(&self) -> Result<Option<Vec<Permission>>, FailureError> {
+        let scopes = self
+            .scopes
+            .split(',')
+            .map(str::trim)
+            .filter(|scope| !scope.is_empty())
+            .map(str::parse)
+            .collect::<Result<Vec<Permission>, _>>()?;
+
+        if scopes.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(scopes))
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Session {
+    pub id: Uuid,
+    pub user: Uuid,
+    pub user_agent: Option<String>,
+    pub address: Option<String>,
+    pub created: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+}
+
 mod serde_datetime_utc {
     use chrono::{DateTime, NaiveDateTime, Utc};
     use serde::de::Error;
@@ -1,33 +1,108 @@
-use actix_web::error::Error;
+use actix_web::error::{Error, ErrorInternalServerError};
+use actix_web::http::header::RETRY_AFTER;
 use actix_web::web::{Data, Json};
-use actix_web::HttpResponse;
+use actix_web::{HttpRequest, HttpResponse};
 use brace_db::Database;
-use futures::future::Future;
+use futures::future::{ok, Either, Future};
+use log::warn;
 use serde_json::json;
 
-use crate::model::UserAuth;
-use crate::util::{create_token, verify};
+use crate::config::{AuthConfig, Jwt};
+use crate::model::{LoginAttempt, UserAuth};
+use crate::util::{client_address, create_token};
 
 pub fn post(
+    req: HttpRequest,
     data: Json<UserAuth>,
     database: Data<Database>,
+    jwt: Data<Jwt>,
+    config: Data<AuthConfig>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
-    crate::action::locate::locate(&database, data.email.clone()).then(move |res| {
-        if let Ok(user) = res {
-            if let Ok(is_match) = verify(&data.password, &user.password) {
-                if is_match {
-                    if let Ok(token) = create_token(user.into()) {
-                        return HttpResponse::Ok().json(json!({ "token": token }));
-                    }
-                }
+    let email = data.email.clone();
+    let address = client_address(&req, config.lockout.forwarded);
+
+    crate::action::login::login(
+        &database,
+        data.into_inner(),
+        address.clone(),
+        config.lockout,
+    )
+    .map_err(ErrorInternalServerError)
+    .and_then(move |attempt| match attempt {
+        LoginAttempt::Success(user) => {
+            if let Some(reason) = user.inactive_reason() {
+                return Either::B(ok(HttpResponse::Forbidden().json(json!({
+                    "error": {
+                        "message": reason,
+                    },
+                }))));
             }
-        }
 
-        HttpResponse::Unauthorized()
+            match create_token(&jwt, &user) {
+                Ok(token) => Either::A(
+                    crate::action::issue_token::issue_token(
+                        &database,
+                        user.id,
+                        jwt.refresh_expires(),
+                    )
+                    .map_err(ErrorInternalServerError)
+                    .map(move |refresh_token| tokens(&jwt, token, refresh_token)),
+                ),
+                Err(_) => Either::B(ok(unauthorized())),
+            }
+        }
+        LoginAttempt::TwoFactor(_) => Either::B(ok(HttpResponse::Unauthorized()
             .header(
                 "WWW-Authenticate",
                 r#"Bearer realm="localhost", charset="UTF-8""#,
             )
-            .finish()
+            .json(json!({
+                "error": {
+                    "field": "code",
+                    "message": "A two-factor code is required",
+                },
+            })))),
+        LoginAttempt::Enroll(_) => Either::B(ok(HttpResponse::Forbidden().json(json!({
+            "error": {
+                "message": "Two-factor authentication is required, log in on the website to enable it",
+            },
+        })))),
+        LoginAttempt::Failure(locked) => {
+            if let Some(seconds) = locked {
+                warn!(
+                    "Locked logins for {} from {} for {} seconds after repeated failures",
+                    email,
+                    address.as_ref().map(String::as_str).unwrap_or("unknown"),
+                    seconds
+                );
+            }
+
+            Either::B(ok(unauthorized()))
+        }
+        LoginAttempt::Locked(seconds) => Either::B(ok(HttpResponse::TooManyRequests()
+            .header(RETRY_AFTER, seconds.to_string())
+            .json(json!({
+                "error": {
+                    "message": "Too many failed login attempts, please try again later",
+                },
+            })))),
     })
 }
+
+pub(crate) fn tokens(jwt: &Jwt, token: String, refresh_token: String) -> HttpResponse {
+    HttpResponse::Ok().json(json!({
+        "token": token,
+        "token_type": "Bearer",
+        "expires_in": jwt.lifetime(),
+        "refresh_token": refresh_token,
+    }))
+}
+
+pub(crate) fn unauthorized() -> HttpResponse {
+    HttpResponse::Unauthorized()
+        .header(
+            "WWW-Authenticate",
+            r#"Bearer realm="localhost", charset="UTF-8""#,
+        )
+        .finish()
+}
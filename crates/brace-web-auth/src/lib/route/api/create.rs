@@ -1,35 +1,26 @@
-use actix_web::error::{Error, ErrorInternalServerError};
+use actix_web::error::Error;
 use actix_web::http::header;
 use actix_web::web::{Data, Json};
 use actix_web::HttpResponse;
 use brace_db::Database;
-use futures::future::{ok, Either, Future};
+use futures::future::Future;
 use serde_json::json;
 
-use crate::model::{CurrentAuth, User};
+use crate::guard::{ApiGuard, ManageUsers};
+use crate::model::{NewUser, PublicUser};
+use crate::route::api::email_error;
 
 pub fn create(
-    auth: CurrentAuth,
+    _: ApiGuard<ManageUsers>,
     database: Data<Database>,
-    user: Json<User>,
+    user: Json<NewUser>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
-    match auth {
-        CurrentAuth::Unauthenticated => Either::A(ok(HttpResponse::Unauthorized()
-            .header(
-                "WWW-Authenticate",
-                r#"Bearer realm="localhost", charset="UTF-8""#,
-            )
-            .finish())),
-        CurrentAuth::Authenticated(_) => Either::B(
-            crate::action::create::create(&database, user.into_inner())
-                .map_err(ErrorInternalServerError)
-                .and_then(|user| {
-                    HttpResponse::Created()
-                        .header(header::LOCATION, format!("/api/users/{}", user.id))
-                        .json(json!({
-                            "value": user,
-                        }))
-                }),
-        ),
-    }
+    crate::action::create::create(&database, user.into_inner().into()).then(|res| match res {
+        Ok(user) => Ok(HttpResponse::Created()
+            .header(header::LOCATION, format!("/api/users/{}", user.id))
+            .json(json!({
+                "value": PublicUser::from(user),
+            }))),
+        Err(err) => email_error(err),
+    })
 }
@@ -1,31 +1,32 @@
 use actix_web::error::{Error, ErrorInternalServerError};
 use actix_web::web::Data;
-use actix_web::HttpResponse;
+use actix_web::{HttpRequest, HttpResponse};
 use brace_db::Database;
-use futures::future::{ok, Either, Future};
+use brace_web::extract::Query;
+use brace_web::pager::Pager;
+use futures::future::Future;
 use serde_json::json;
 
-use crate::model::CurrentAuth;
+use crate::guard::{ApiGuard, ManageUsers};
+use crate::model::{PublicUser, UserFilter};
 
 pub fn list(
-    auth: CurrentAuth,
+    _: ApiGuard<ManageUsers>,
+    req: HttpRequest,
     database: Data<Database>,
+    filter: Query<UserFilter>,
+    pager: Query<Pager>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
-    match auth {
-        CurrentAuth::Unauthenticated => Either::A(ok(HttpResponse::Unauthorized()
-            .header(
-                "WWW-Authenticate",
-                r#"Bearer realm="localhost", charset="UTF-8""#,
-            )
-            .finish())),
-        CurrentAuth::Authenticated(_) => Either::B(
-            crate::action::list::list(&database)
-                .map_err(ErrorInternalServerError)
-                .and_then(|users| {
-                    HttpResponse::Ok().json(json!({
-                        "value": users,
-                    }))
-                }),
-        ),
-    }
+    let filter = filter.into_inner();
+    let pager = pager.into_inner();
+    let path = req.path().to_owned();
+
+    crate::action::list::list(&database, filter.clone(), pager)
+        .map_err(ErrorInternalServerError)
+        .and_then(move |(users, total)| {
+            HttpResponse::Ok().json(json!({
+                "value": users.into_iter().map(PublicUser::from).collect::<Vec<_>>(),
+                "pager": pager.paginate(&path, &filter, total),
+            }))
+        })
 }
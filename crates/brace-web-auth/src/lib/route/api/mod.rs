@@ -1,14 +1,26 @@
+use actix_web::error::{Error, ErrorInternalServerError};
 use actix_web::web::{self, ServiceConfig};
+use actix_web::HttpResponse;
+use serde_json::json;
+
+use crate::action::email::EmailError;
 
 pub mod auth;
 pub mod create;
 pub mod delete;
 pub mod list;
+pub mod refresh;
 pub mod retrieve;
+pub mod revoke;
+pub mod roles;
 pub mod update;
 
 pub fn config(conf: &mut ServiceConfig) {
     conf.service(web::resource("/api/auth").route(web::post().to_async(auth::post)))
+        .service(web::resource("/api/auth/refresh").route(web::post().to_async(refresh::post)))
+        .service(web::resource("/api/auth/revoke").route(web::post().to_async(revoke::post)))
+        .service(web::resource("/api/roles/").route(web::get().to_async(roles::list)))
+        .service(web::resource("/api/roles/{role}").route(web::put().to_async(roles::update_role)))
         .service(
             web::scope("/api/users")
                 .service(
@@ -21,6 +33,23 @@ pub fn config(conf: &mut ServiceConfig) {
                         .route(web::get().to_async(retrieve::retrieve))
                         .route(web::put().to_async(update::update))
                         .route(web::delete().to_async(delete::delete)),
+                )
+                .service(
+                    web::resource("/{user}/roles")
+                        .route(web::get().to_async(roles::retrieve))
+                        .route(web::put().to_async(roles::update)),
                 ),
         );
 }
+
+pub(crate) fn email_error(err: failure::Error) -> Result<HttpResponse, Error> {
+    match err.downcast::<EmailError>() {
+        Ok(err) => Ok(HttpResponse::UnprocessableEntity().json(json!({
+            "error": {
+                "field": err.field(),
+                "message": err.to_string(),
+            },
+        }))),
+        Err(err) => Err(ErrorInternalServerError(err)),
+    }
+}
@@ -0,0 +1,30 @@
+use actix_web::error::{Error, ErrorInternalServerError};
+use actix_web::web::{Data, Json};
+use actix_web::HttpResponse;
+use brace_db::Database;
+use futures::future::Future;
+
+use super::auth::{tokens, unauthorized};
+use crate::config::Jwt;
+use crate::model::RefreshAuth;
+use crate::util::create_token;
+
+pub fn post(
+    data: Json<RefreshAuth>,
+    database: Data<Database>,
+    jwt: Data<Jwt>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    crate::action::refresh_token::refresh_token(
+        &database,
+        data.into_inner().refresh_token,
+        jwt.refresh_expires(),
+    )
+    .map_err(ErrorInternalServerError)
+    .and_then(move |res| match res {
+        Some((user, refresh_token)) => match create_token(&jwt, &user) {
+            Ok(token) => Ok(tokens(&jwt, token, refresh_token)),
+            Err(err) => Err(ErrorInternalServerError(err)),
+        },
+        None => Ok(unauthorized()),
+    })
+}
@@ -2,35 +2,26 @@ use actix_web::error::{Error, ErrorInternalServerError};
 use actix_web::web::{Data, Path};
 use actix_web::HttpResponse;
 use brace_db::Database;
-use futures::future::{ok, Either, Future};
+use futures::future::Future;
 use serde::Deserialize;
 use serde_json::json;
 use uuid::Uuid;
 
-use crate::model::CurrentAuth;
+use crate::guard::{ApiGuard, ManageUsers};
+use crate::model::PublicUser;
 
 pub fn retrieve(
-    auth: CurrentAuth,
+    _: ApiGuard<ManageUsers>,
     database: Data<Database>,
     path: Path<Info>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
-    match auth {
-        CurrentAuth::Unauthenticated => Either::A(ok(HttpResponse::Unauthorized()
-            .header(
-                "WWW-Authenticate",
-                r#"Bearer realm="localhost", charset="UTF-8""#,
-            )
-            .finish())),
-        CurrentAuth::Authenticated(_) => Either::B(
-            crate::action::retrieve::retrieve(&database, path.user)
-                .map_err(ErrorInternalServerError)
-                .and_then(|user| {
-                    HttpResponse::Ok().json(json!({
-                        "value": user,
-                    }))
-                }),
-        ),
-    }
+    crate::action::retrieve::retrieve(&database, path.user)
+        .map_err(ErrorInternalServerError)
+        .and_then(|user| {
+            HttpResponse::Ok().json(json!({
+                "value": PublicUser::from(user),
+            }))
+        })
 }
 
 #[derive(Deserialize)]
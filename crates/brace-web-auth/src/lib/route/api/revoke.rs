@@ -0,0 +1,30 @@
+use actix_web::error::{Error, ErrorInternalServerError};
+use actix_web::web::{Data, Json};
+use actix_web::{HttpRequest, HttpResponse};
+use brace_db::Database;
+use futures::future::{ok, Either, Future};
+
+use super::auth::unauthorized;
+use crate::config::Jwt;
+use crate::model::RevokeAuth;
+use crate::util::{bearer_token, decode_token};
+
+pub fn post(
+    req: HttpRequest,
+    data: Option<Json<RevokeAuth>>,
+    database: Data<Database>,
+    jwt: Data<Jwt>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    match bearer_token(&req).and_then(|token| decode_token(&jwt, token).ok()) {
+        Some(claims) => Either::A(
+            crate::action::revoke_token::revoke_token(
+                &database,
+                claims,
+                data.map(Json::into_inner).unwrap_or_default(),
+            )
+            .map_err(ErrorInternalServerError)
+            .map(|_| HttpResponse::NoContent().finish()),
+        ),
+        None => Either::B(ok(unauthorized())),
+    }
+}
@@ -0,0 +1,89 @@
+use actix_web::error::{Error, ErrorInternalServerError};
+use actix_web::web::{Data, Json, Path};
+use actix_web::HttpResponse;
+use brace_db::Database;
+use futures::future::Future;
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::action::assign_roles::RoleError;
+use crate::guard::{ApiGuard, ManageUsers};
+use crate::model::RoleUpdate;
+
+pub fn list(
+    _: ApiGuard<ManageUsers>,
+    database: Data<Database>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    crate::action::list_roles::list_roles(&database)
+        .map_err(ErrorInternalServerError)
+        .and_then(|roles| {
+            HttpResponse::Ok().json(json!({
+                "value": roles,
+            }))
+        })
+}
+
+pub fn update_role(
+    _: ApiGuard<ManageUsers>,
+    database: Data<Database>,
+    path: Path<RoleInfo>,
+    data: Json<RoleUpdate>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    crate::action::update_role::update_role(&database, path.role.clone(), data.into_inner())
+        .map_err(ErrorInternalServerError)
+        .and_then(|role| match role {
+            Some(role) => HttpResponse::Ok().json(json!({
+                "value": role,
+            })),
+            None => HttpResponse::NotFound().finish(),
+        })
+}
+
+pub fn retrieve(
+    _: ApiGuard<ManageUsers>,
+    database: Data<Database>,
+    path: Path<Info>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    crate::action::list_user_roles::list_user_roles(&database, path.user)
+        .map_err(ErrorInternalServerError)
+        .and_then(|roles| {
+            HttpResponse::Ok().json(json!({
+                "value": roles,
+            }))
+        })
+}
+
+pub fn update(
+    _: ApiGuard<ManageUsers>,
+    database: Data<Database>,
+    path: Path<Info>,
+    roles: Json<Vec<String>>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    crate::action::assign_roles::assign_roles(&database, path.user, roles.into_inner()).then(
+        |res| match res {
+            Ok(roles) => Ok(HttpResponse::Ok().json(json!({
+                "value": roles,
+            }))),
+            Err(err) => match err.downcast::<RoleError>() {
+                Ok(err) => Ok(HttpResponse::UnprocessableEntity().json(json!({
+                    "error": {
+                        "field": "roles",
+                        "message": err.to_string(),
+                    },
+                }))),
+                Err(err) => Err(ErrorInternalServerError(err)),
+            },
+        },
+    )
+}
+
+#[derive(Deserialize)]
+pub struct Info {
+    user: Uuid,
+}
+
+#[derive(Deserialize)]
+pub struct RoleInfo {
+    role: String,
+}
@@ -1,32 +1,45 @@
-use actix_web::error::{Error, ErrorInternalServerError};
-use actix_web::web::{Data, Json};
+use actix_web::error::Error;
+use actix_web::web::{Data, Json, Path};
 use actix_web::HttpResponse;
 use brace_db::Database;
 use futures::future::{ok, Either, Future};
+use serde::Deserialize;
 use serde_json::json;
+use uuid::Uuid;
 
-use crate::model::{CurrentAuth, User};
+use crate::guard::{ApiGuard, ManageUsers};
+use crate::model::{PublicUser, UserUpdate};
+use crate::route::api::email_error;
 
 pub fn update(
-    auth: CurrentAuth,
+    _: ApiGuard<ManageUsers>,
     database: Data<Database>,
-    user: Json<User>,
+    path: Path<Info>,
+    user: Json<UserUpdate>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
-    match auth {
-        CurrentAuth::Unauthenticated => Either::A(ok(HttpResponse::Unauthorized()
-            .header(
-                "WWW-Authenticate",
-                r#"Bearer realm="localhost", charset="UTF-8""#,
-            )
-            .finish())),
-        CurrentAuth::Authenticated(_) => Either::B(
-            crate::action::update::update(&database, user.into_inner())
-                .map_err(ErrorInternalServerError)
-                .and_then(|user| {
-                    HttpResponse::Ok().json(json!({
-                        "value": user,
-                    }))
-                }),
-        ),
-    }
+    let user = user.into_inner();
+    let revoke = user.password().is_some();
+
+    crate::action::update::update(&database, path.user, user)
+        .and_then(move |user| {
+            if revoke {
+                Either::A(
+                    crate::action::revoke_sessions::revoke_sessions(&database, user.id, None)
+                        .map(move |_| user),
+                )
+            } else {
+                Either::B(ok(user))
+            }
+        })
+        .then(|res| match res {
+            Ok(user) => Ok(HttpResponse::Ok().json(json!({
+                "value": PublicUser::from(user),
+            }))),
+            Err(err) => email_error(err),
+        })
+}
+
+#[derive(Deserialize)]
+pub struct Info {
+    user: Uuid,
 }
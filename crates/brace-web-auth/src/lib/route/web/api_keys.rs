@@ -0,0 +1,204 @@
+use actix_web::error::{Error, ErrorInternalServerError, ErrorNotFound};
+use actix_web::web::{Data, Path};
+use actix_web::HttpResponse;
+use brace_db::Database;
+use brace_web::redirect::HttpRedirect;
+use brace_web::render::{Renderer, Template};
+use brace_web_form::csrf::{forbidden, REJECTED_MESSAGE};
+use brace_web_form::{Csrf, CsrfForm, Form, FormData};
+use futures::future::{err, Either, Future};
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::form::api_key::ApiKeyForm;
+use crate::guard::{Authenticated, WebGuard};
+use crate::model::{ApiKeyRequest, User};
+
+type BoxedFuture<I, E> = Box<dyn Future<Item = I, Error = E>>;
+
+pub fn get(
+    access: WebGuard<Authenticated>,
+    form: Form,
+    database: Data<Database>,
+    renderer: Data<Renderer>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    render(
+        access.into_inner().user,
+        form,
+        database,
+        renderer,
+        ApiKeyRequest::default(),
+        None,
+        None,
+    )
+}
+
+pub fn post(
+    access: WebGuard<Authenticated>,
+    data: CsrfForm<ApiKeyRequest>,
+    form: Form,
+    database: Data<Database>,
+    renderer: Data<Renderer>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    let user = access.into_inner().user;
+
+    if !data.is_valid() {
+        return Either::B(Either::A(
+            render(
+                user,
+                form,
+                database,
+                renderer,
+                data.into_inner(),
+                Some(REJECTED_MESSAGE.to_string()),
+                None,
+            )
+            .map(forbidden),
+        ));
+    }
+
+    let data = data.into_inner();
+
+    if data.name.trim().is_empty() {
+        return Either::A(render(
+            user,
+            form,
+            database,
+            renderer,
+            data,
+            Some("The name must not be empty".to_string()),
+            None,
+        ));
+    }
+
+    let (scopes, expires) = match (data.scopes(), data.expires()) {
+        (Ok(scopes), Ok(expires)) => (scopes, expires),
+        (Err(e), _) | (_, Err(e)) => {
+            return Either::A(render(
+                user,
+                form,
+                database,
+                renderer,
+                data,
+                Some(e.to_string()),
+                None,
+            ))
+        }
+    };
+
+    Either::B(Either::B(
+        crate::action::create_api_key::create_api_key(
+            &database,
+            user.id,
+            data.name.trim().to_owned(),
+            scopes,
+            expires,
+        )
+        .map_err(ErrorInternalServerError)
+        .and_then(move |key| {
+            render(
+                user,
+                form,
+                database,
+                renderer,
+                ApiKeyRequest::default(),
+                None,
+                Some(key),
+            )
+        }),
+    ))
+}
+
+pub fn revoke(
+    access: WebGuard<Authenticated>,
+    info: Path<Info>,
+    data: Csrf,
+    form: Form,
+    database: Data<Database>,
+    renderer: Data<Renderer>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    if !data.is_valid() {
+        return Either::A(
+            render(
+                access.into_inner().user,
+                form,
+                database,
+                renderer,
+                ApiKeyRequest::default(),
+                Some(REJECTED_MESSAGE.to_string()),
+                None,
+            )
+            .map(forbidden),
+        );
+    }
+
+    Either::B(
+        crate::action::revoke_api_key::revoke_api_key(&database, access.user.id, info.key)
+            .map_err(ErrorInternalServerError)
+            .and_then(|revoked| {
+                if revoked {
+                    Ok(HttpRedirect::to("/account/api-keys").into_response())
+                } else {
+                    Err(ErrorNotFound("Not Found"))
+                }
+            }),
+    )
+}
+
+fn render(
+    user: User,
+    form: Form,
+    database: Data<Database>,
+    renderer: Data<Renderer>,
+    data: ApiKeyRequest,
+    message: Option<String>,
+    key: Option<String>,
+) -> BoxedFuture<HttpResponse, Error> {
+    let data = match FormData::with(data) {
+        Ok(data) => data,
+        Err(e) => return Box::new(err(ErrorInternalServerError(e))),
+    };
+
+    Box::new(
+        crate::action::list_api_keys::list_api_keys(&database, user.id)
+            .map_err(ErrorInternalServerError)
+            .and_then(move |keys| {
+                let csrf = form.token().cloned();
+                let mut form = form.with(data);
+
+                form.builder(ApiKeyForm);
+
+                form.build()
+                    .map_err(ErrorInternalServerError)
+                    .and_then(move |form| {
+                        let template = Template::new(
+                            "user-api-keys",
+                            json!({
+                                "title": "API keys",
+                                "message": message,
+                                "key": key,
+                                "keys": keys,
+                                "form": form,
+                                "csrf_token": csrf,
+                            }),
+                        );
+
+                        renderer
+                            .send(template)
+                            .map_err(ErrorInternalServerError)
+                            .and_then(|res| match res {
+                                Ok(body) => {
+                                    Ok(HttpResponse::Ok().content_type("text/html").body(body))
+                                }
+                                Err(err) => Err(ErrorInternalServerError(err)),
+                            })
+                    })
+            }),
+    )
+}
+
+#[derive(Deserialize)]
+pub struct Info {
+    key: Uuid,
+}
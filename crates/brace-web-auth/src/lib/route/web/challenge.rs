@@ -0,0 +1,158 @@
+use actix_identity::Identity;
+use actix_web::error::{Error, ErrorInternalServerError};
+use actix_web::web::Data;
+use actix_web::{HttpRequest, HttpResponse};
+use brace_db::Database;
+use brace_web::render::Renderer;
+use brace_web_form::csrf::{forbidden, REJECTED_MESSAGE};
+use brace_web_form::{CsrfForm, Form};
+use futures::future::{ok, Either, Future};
+use log::warn;
+
+use crate::config::AuthConfig;
+use crate::model::{ChallengeAttempt, TwoFactorAuth, User, UserAuth};
+use crate::route::web::login::{
+    render, render_challenge, render_enrollment, sign_in, too_many_requests,
+};
+use crate::route::web::two_factor;
+use crate::util::client_address;
+
+type BoxedFuture<I, E> = Box<dyn Future<Item = I, Error = E>>;
+
+pub fn post(
+    req: HttpRequest,
+    id: Identity,
+    data: CsrfForm<TwoFactorAuth>,
+    form: Form,
+    database: Data<Database>,
+    renderer: Data<Renderer>,
+    config: Data<AuthConfig>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    let valid = data.is_valid();
+    let data = data.into_inner();
+    let challenge = data.challenge.clone();
+
+    if !valid {
+        return Either::A(
+            render_challenge(challenge, form, renderer, Some(REJECTED_MESSAGE)).map(forbidden),
+        );
+    }
+
+    let address = client_address(&req, config.lockout.forwarded);
+
+    Either::B(
+        crate::action::complete_challenge::complete_challenge(
+            &database,
+            data.challenge,
+            data.code,
+            address.clone(),
+            config.lockout,
+        )
+        .map_err(ErrorInternalServerError)
+        .and_then(move |attempt| -> BoxedFuture<HttpResponse, Error> {
+            match attempt {
+                ChallengeAttempt::Success(user) => Box::new(ok(sign_in(&id, user))),
+                ChallengeAttempt::Failure(user, locked) => {
+                    warn_locked(&user, address, locked);
+
+                    Box::new(render_challenge(
+                        challenge,
+                        form,
+                        renderer,
+                        Some("Invalid authentication code"),
+                    ))
+                }
+                attempt => failed(attempt, form, renderer),
+            }
+        }),
+    )
+}
+
+pub fn enroll(
+    req: HttpRequest,
+    id: Identity,
+    data: CsrfForm<TwoFactorAuth>,
+    form: Form,
+    database: Data<Database>,
+    renderer: Data<Renderer>,
+    config: Data<AuthConfig>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    if !data.is_valid() {
+        return Either::A(
+            render(UserAuth::default(), form, renderer, Some(REJECTED_MESSAGE)).map(forbidden),
+        );
+    }
+
+    let data = data.into_inner();
+    let challenge = data.challenge.clone();
+    let address = client_address(&req, config.lockout.forwarded);
+
+    Either::B(
+        crate::action::complete_enrollment::complete_enrollment(
+            &database,
+            data.challenge,
+            data.code,
+            address.clone(),
+            config.lockout,
+        )
+        .map_err(ErrorInternalServerError)
+        .and_then(move |attempt| -> BoxedFuture<HttpResponse, Error> {
+            match attempt {
+                ChallengeAttempt::Enrolled(user, codes) => {
+                    id.remember(user.id.to_string());
+
+                    two_factor::render(user, form, database, renderer, config, None, Some(codes))
+                }
+                ChallengeAttempt::Failure(user, locked) => {
+                    warn_locked(&user, address, locked);
+
+                    Box::new(render_enrollment(
+                        user,
+                        challenge,
+                        form,
+                        database,
+                        renderer,
+                        config,
+                        Some("Invalid authentication code"),
+                    ))
+                }
+                attempt => failed(attempt, form, renderer),
+            }
+        }),
+    )
+}
+
+fn failed(
+    attempt: ChallengeAttempt,
+    form: Form,
+    renderer: Data<Renderer>,
+) -> BoxedFuture<HttpResponse, Error> {
+    match attempt {
+        ChallengeAttempt::Locked(seconds) => Box::new(
+            render(
+                UserAuth::default(),
+                form,
+                renderer,
+                Some("Too many failed login attempts, please try again later"),
+            )
+            .map(move |res| too_many_requests(res, seconds)),
+        ),
+        _ => Box::new(render(
+            UserAuth::default(),
+            form,
+            renderer,
+            Some("The login has expired, please log in again"),
+        )),
+    }
+}
+
+fn warn_locked(user: &User, address: Option<String>, locked: Option<i64>) {
+    if let Some(seconds) = locked {
+        warn!(
+            "Locked logins for {} from {} for {} seconds after repeated failures",
+            user.email,
+            address.as_ref().map(String::as_str).unwrap_or("unknown"),
+            seconds
+        );
+    }
+}
@@ -1,45 +1,73 @@
-use actix_web::error::{Error, ErrorForbidden, ErrorInternalServerError};
-use actix_web::web::{Data, Form as FormExtractor};
+use actix_web::error::{Error, ErrorInternalServerError};
+use actix_web::web::Data;
 use actix_web::HttpResponse;
 use brace_db::Database;
 use brace_web::redirect::HttpRedirect;
 use brace_web::render::{Renderer, Template};
-use brace_web_form::{Form, FormData};
-use futures::future::{err, Either, Future};
+use brace_web_form::csrf::{forbidden, REJECTED_MESSAGE};
+use brace_web_form::{CsrfForm, Form, FormData};
+use futures::future::{err, ok, Either, Future};
 use serde_json::json;
 
+use crate::action::email::EmailError;
 use crate::form::user::UserForm;
-use crate::model::{CurrentUser, User};
+use crate::guard::{ManageUsers, WebGuard};
+use crate::model::NewUser;
 
 pub fn get(
-    user: CurrentUser,
+    _: WebGuard<ManageUsers>,
+    form: Form,
     renderer: Data<Renderer>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
-    match user {
-        CurrentUser::Anonymous => Either::A(err(ErrorForbidden("Forbidden"))),
-        CurrentUser::Authenticated(_) => Either::B(render(renderer)),
-    }
+    render(NewUser::default(), form, renderer, None)
 }
 
 pub fn post(
-    user: CurrentUser,
-    data: FormExtractor<User>,
+    _: WebGuard<ManageUsers>,
+    data: CsrfForm<NewUser>,
+    form: Form,
     database: Data<Database>,
-) -> impl Future<Item = HttpRedirect, Error = Error> {
-    match user {
-        CurrentUser::Anonymous => Either::A(err(ErrorForbidden("Forbidden"))),
-        CurrentUser::Authenticated(_) => Either::B(
-            crate::action::create::create(&database, data.into_inner())
-                .map_err(ErrorInternalServerError)
-                .and_then(|user| HttpRedirect::to(format!("/users/{}", user.id))),
-        ),
+    renderer: Data<Renderer>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    if !data.is_valid() {
+        return Either::A(
+            render(
+                data.into_inner(),
+                form,
+                renderer,
+                Some(REJECTED_MESSAGE.to_string()),
+            )
+            .map(forbidden),
+        );
     }
+
+    let data = data.into_inner();
+
+    Either::B(
+        crate::action::create::create(&database, data.clone().into()).then(move |res| match res {
+            Ok(user) => Either::A(ok(
+                HttpRedirect::to(format!("/users/{}", user.id)).into_response()
+            )),
+            Err(e) => match e.downcast::<EmailError>() {
+                Ok(e) => Either::B(render(data, form, renderer, Some(e.to_string()))),
+                Err(e) => Either::A(err(ErrorInternalServerError(e))),
+            },
+        }),
+    )
 }
 
-fn render(renderer: Data<Renderer>) -> impl Future<Item = HttpResponse, Error = Error> {
-    match FormData::with(User::default()) {
+fn render(
+    user: NewUser,
+    form: Form,
+    renderer: Data<Renderer>,
+    message: Option<String>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    match FormData::with(NewUser {
+        password: String::new(),
+        ..user
+    }) {
         Ok(data) => {
-            let mut form = Form::new(()).with(data);
+            let mut form = form.with(data);
 
             form.builder(UserForm);
 
@@ -51,6 +79,7 @@ fn render(renderer: Data<Renderer>) -> impl Future<Item = HttpResponse, Error =
                             "form-layout",
                             json!({
                                 "title": "Create user",
+                                "message": message,
                                 "form": form,
                             }),
                         );
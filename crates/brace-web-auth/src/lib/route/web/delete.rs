@@ -1,53 +1,67 @@
-use actix_web::error::{Error, ErrorForbidden, ErrorInternalServerError};
+use actix_web::error::{Error, ErrorInternalServerError};
 use actix_web::web::{Data, Path};
 use actix_web::HttpResponse;
 use brace_db::Database;
 use brace_web::redirect::HttpRedirect;
 use brace_web::render::{Renderer, Template};
-use futures::future::{err, Either, Future};
+use brace_web_form::csrf::{forbidden, REJECTED_MESSAGE};
+use brace_web_form::{Csrf, CsrfToken};
+use futures::future::{Either, Future};
 use serde::Deserialize;
 use serde_json::json;
 use uuid::Uuid;
 
-use crate::model::{CurrentUser, User};
+use crate::guard::{ManageUsers, WebGuard};
+use crate::model::User;
 
 pub fn get(
-    user: CurrentUser,
+    _: WebGuard<ManageUsers>,
     info: Path<Info>,
+    csrf: CsrfToken,
     renderer: Data<Renderer>,
     database: Data<Database>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
-    match user {
-        CurrentUser::Anonymous => Either::A(err(ErrorForbidden("Forbidden"))),
-        CurrentUser::Authenticated(_) => Either::B(
-            crate::action::retrieve::retrieve(&database, info.user)
-                .map_err(ErrorInternalServerError)
-                .and_then(move |user| render(user, &renderer)),
-        ),
-    }
+    crate::action::retrieve::retrieve(&database, info.user)
+        .map_err(ErrorInternalServerError)
+        .and_then(move |user| render(user, csrf, &renderer, None))
 }
 
 pub fn post(
-    user: CurrentUser,
+    _: WebGuard<ManageUsers>,
     info: Path<Info>,
+    data: Csrf,
+    renderer: Data<Renderer>,
     database: Data<Database>,
-) -> impl Future<Item = HttpRedirect, Error = Error> {
-    match user {
-        CurrentUser::Anonymous => Either::A(err(ErrorForbidden("Forbidden"))),
-        CurrentUser::Authenticated(_) => Either::B(
-            crate::action::delete::delete(&database, info.user)
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    if !data.is_valid() {
+        return Either::A(
+            crate::action::retrieve::retrieve(&database, info.user)
                 .map_err(ErrorInternalServerError)
-                .and_then(|_| HttpRedirect::to("/users/")),
-        ),
+                .and_then(move |user| render(user, data.token(), &renderer, Some(REJECTED_MESSAGE)))
+                .map(forbidden),
+        );
     }
+
+    Either::B(
+        crate::action::delete::delete(&database, info.user)
+            .map_err(ErrorInternalServerError)
+            .map(|_| HttpRedirect::to("/users/").into_response()),
+    )
 }
 
-fn render(user: User, renderer: &Renderer) -> impl Future<Item = HttpResponse, Error = Error> {
+fn render(
+    user: User,
+    csrf: CsrfToken,
+    renderer: &Renderer,
+    error: Option<&'static str>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
     let template = Template::new(
         "form-confirm",
         json!({
             "title": format!("Delete user <em>{}</em>?", user.email),
             "message": format!("Are you sure that you want to delete the user <em>{}</em>?", user.email),
+            "error": error,
+            "csrf_token": csrf,
         }),
     );
 
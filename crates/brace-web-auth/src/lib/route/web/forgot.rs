@@ -0,0 +1,164 @@
+use actix::Arbiter;
+use actix_web::error::{Error, ErrorInternalServerError};
+use actix_web::http::header::RETRY_AFTER;
+use actix_web::http::StatusCode;
+use actix_web::web::Data;
+use actix_web::{HttpRequest, HttpResponse};
+use brace_db::Database;
+use brace_mail::Mailer;
+use brace_web::config::WebConfig;
+use brace_web::render::{Renderer, Template};
+use brace_web_form::csrf::{forbidden, REJECTED_MESSAGE};
+use brace_web_form::{CsrfForm, Form, FormData};
+use chrono::{Duration, Utc};
+use futures::future::{err, ok, Either, Future};
+use log::error;
+use serde_json::json;
+
+use crate::config::AuthConfig;
+use crate::form::password::PasswordRequestForm;
+use crate::model::{PasswordRequest, PublicUser};
+use crate::util::{base_url, client_address, send_email};
+
+static SENT_MESSAGE: &str =
+    "If an account exists for this email address, a link to reset its password has been sent";
+
+static THROTTLED_MESSAGE: &str = "Too many password reset requests, please try again later";
+
+pub fn get(
+    form: Form,
+    renderer: Data<Renderer>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    render(PasswordRequest::default(), form, renderer, None)
+}
+
+pub fn post(
+    req: HttpRequest,
+    data: CsrfForm<PasswordRequest>,
+    form: Form,
+    database: Data<Database>,
+    renderer: Data<Renderer>,
+    mailer: Data<Mailer>,
+    web: Data<WebConfig>,
+    auth: Data<AuthConfig>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    if !data.is_valid() {
+        return Either::A(
+            render(data.into_inner(), form, renderer, Some(REJECTED_MESSAGE)).map(forbidden),
+        );
+    }
+
+    let email = data.into_inner().email;
+    let address = client_address(&req, auth.reset_throttle.forwarded);
+
+    Either::B(
+        crate::action::throttle_reset::throttle_reset(
+            &database,
+            email.clone(),
+            address,
+            auth.reset_throttle,
+        )
+        .map_err(ErrorInternalServerError)
+        .and_then(move |throttled| match throttled {
+            Some(seconds) => Either::A(
+                render(
+                    PasswordRequest::default(),
+                    form,
+                    renderer,
+                    Some(THROTTLED_MESSAGE),
+                )
+                .map(move |mut res| {
+                    *res.status_mut() = StatusCode::TOO_MANY_REQUESTS;
+                    res.headers_mut().insert(RETRY_AFTER, seconds.into());
+
+                    res
+                }),
+            ),
+            None => {
+                Arbiter::spawn(send_reset(
+                    email, &database, &renderer, &mailer, &web, &auth,
+                ));
+
+                Either::B(render(
+                    PasswordRequest::default(),
+                    form,
+                    renderer,
+                    Some(SENT_MESSAGE),
+                ))
+            }
+        }),
+    )
+}
+
+fn send_reset(
+    email: String,
+    database: &Database,
+    renderer: &Renderer,
+    mailer: &Mailer,
+    web: &WebConfig,
+    auth: &AuthConfig,
+) -> impl Future<Item = (), Error = ()> {
+    let base = base_url(web);
+    let expires = Utc::now() + Duration::seconds(auth.reset_lifetime);
+    let renderer = renderer.clone();
+    let mailer = mailer.clone();
+
+    crate::action::request_reset::request_reset(database, email, expires)
+        .and_then(move |res| match res {
+            Some((user, token)) => Either::A(send_email(
+                &renderer,
+                &mailer,
+                user.email.clone(),
+                "Reset your password",
+                "email-password-reset",
+                json!({
+                    "user": PublicUser::from(user),
+                    "url": format!("{}/password/reset/{}", base, token),
+                    "expires": expires.format("%Y-%m-%d %H:%M UTC").to_string(),
+                }),
+            )),
+            None => Either::B(ok(())),
+        })
+        .map_err(|err| error!("Failed to send a password reset email: {}", err))
+}
+
+fn render(
+    data: PasswordRequest,
+    form: Form,
+    renderer: Data<Renderer>,
+    message: Option<&'static str>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    match FormData::with(data) {
+        Ok(data) => {
+            let mut form = form.with(data);
+
+            form.builder(PasswordRequestForm);
+
+            Either::A(
+                form.build()
+                    .map_err(ErrorInternalServerError)
+                    .and_then(move |form| {
+                        let template = Template::new(
+                            "form-layout",
+                            json!({
+                                "title": "Forgot password",
+                                "message": message,
+                                "form": form,
+                            }),
+                        );
+
+                        renderer
+                            .send(template)
+                            .map_err(ErrorInternalServerError)
+                            .and_then(|res| match res {
+                                Ok(body) => {
+                                    Ok(HttpResponse::Ok().content_type("text/html").body(body))
+                                }
+                                Err(err) => Err(ErrorInternalServerError(err)),
+                            })
+                    }),
+            )
+        }
+        Err(e) => Either::B(err(ErrorInternalServerError(e))),
+    }
+}
@@ -1,37 +1,46 @@
-use actix_web::error::{Error, ErrorForbidden, ErrorInternalServerError};
+use actix_web::error::{Error, ErrorInternalServerError};
 use actix_web::web::Data;
-use actix_web::HttpResponse;
+use actix_web::{HttpRequest, HttpResponse};
 use brace_db::Database;
+use brace_web::extract::Query;
+use brace_web::pager::{Pager, Pagination};
 use brace_web::render::{Renderer, Template};
-use futures::future::{err, Either, Future};
+use futures::future::Future;
 use serde_json::json;
 
-use crate::model::{CurrentUser, User};
+use crate::guard::{ManageUsers, WebGuard};
+use crate::model::{PublicUser, User, UserFilter};
 
 pub fn get(
-    user: CurrentUser,
+    _: WebGuard<ManageUsers>,
+    req: HttpRequest,
+    filter: Query<UserFilter>,
+    pager: Query<Pager>,
     renderer: Data<Renderer>,
     database: Data<Database>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
-    match user {
-        CurrentUser::Anonymous => Either::A(err(ErrorForbidden("Forbidden"))),
-        CurrentUser::Authenticated(_) => Either::B(
-            crate::action::list::list(&database)
-                .map_err(ErrorInternalServerError)
-                .and_then(move |users| render(users, &renderer)),
-        ),
-    }
+    let filter = filter.into_inner();
+    let pager = pager.into_inner();
+    let path = req.path().to_owned();
+
+    crate::action::list::list(&database, filter.clone(), pager)
+        .map_err(ErrorInternalServerError)
+        .and_then(move |(users, total)| {
+            render(users, pager.paginate(&path, &filter, total), &renderer)
+        })
 }
 
 fn render(
     users: Vec<User>,
+    pager: Pagination,
     renderer: &Renderer,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
     let template = Template::new(
         "user-list",
         json!({
             "title": "Users",
-            "users": users,
+            "users": users.into_iter().map(PublicUser::from).collect::<Vec<_>>(),
+            "pager": pager,
         }),
     );
 
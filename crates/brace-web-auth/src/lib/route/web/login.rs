@@ -1,66 +1,274 @@
 use actix_identity::Identity;
 use actix_web::error::{Error, ErrorInternalServerError};
-use actix_web::web::{Data, Form as FormExtractor};
-use actix_web::HttpResponse;
+use actix_web::http::header::RETRY_AFTER;
+use actix_web::http::StatusCode;
+use actix_web::web::Data;
+use actix_web::{HttpRequest, HttpResponse};
 use brace_db::Database;
 use brace_web::redirect::HttpRedirect;
 use brace_web::render::{Renderer, Template};
-use brace_web_form::{Form, FormData};
+use brace_web_form::csrf::{forbidden, REJECTED_MESSAGE};
+use brace_web_form::{CsrfForm, Form, FormData};
+use chrono::{Duration, Utc};
 use futures::future::{err, ok, Either, Future};
+use log::warn;
 use serde_json::json;
 
+use crate::config::AuthConfig;
 use crate::form::login::LoginForm;
-use crate::model::UserAuth;
-use crate::util::verify;
+use crate::form::two_factor::{TwoFactorEnrollForm, TwoFactorForm};
+use crate::model::{LoginAttempt, TwoFactorAuth, TwoFactorStatus, User, UserAuth};
+use crate::totp;
+use crate::util::client_address;
+
+type BoxedFuture<I, E> = Box<dyn Future<Item = I, Error = E>>;
+
+const CHALLENGE_LIFETIME: i64 = 300;
 
 pub fn get(
     id: Identity,
+    form: Form,
     renderer: Data<Renderer>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
     match id.identity() {
         Some(_) => Either::A(ok(HttpRedirect::to("/").into_response())),
-        None => Either::B(render(UserAuth::default(), renderer, None)),
+        None => Either::B(render(UserAuth::default(), form, renderer, None)),
     }
 }
 
 pub fn post(
+    req: HttpRequest,
     id: Identity,
-    auth: FormExtractor<UserAuth>,
+    auth: CsrfForm<UserAuth>,
+    form: Form,
+    database: Data<Database>,
+    renderer: Data<Renderer>,
+    config: Data<AuthConfig>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    if !auth.is_valid() {
+        return Either::A(
+            render(auth.into_inner(), form, renderer, Some(REJECTED_MESSAGE)).map(forbidden),
+        );
+    }
+
+    let auth = auth.into_inner();
+    let email = auth.email.clone();
+    let address = client_address(&req, config.lockout.forwarded);
+
+    Either::B(
+        crate::action::login::login(&database, auth.clone(), address.clone(), config.lockout)
+            .map_err(ErrorInternalServerError)
+            .and_then(move |attempt| -> BoxedFuture<HttpResponse, Error> {
+                let (message, retry) = match attempt {
+                    LoginAttempt::Success(user) => match user.inactive_reason() {
+                        Some(reason) => (reason, None),
+                        None => return Box::new(ok(sign_in(&id, user))),
+                    },
+                    LoginAttempt::TwoFactor(user) => match user.inactive_reason() {
+                        Some(reason) => (reason, None),
+                        None => {
+                            let expires = Utc::now() + Duration::seconds(CHALLENGE_LIFETIME);
+
+                            return Box::new(
+                                crate::action::issue_challenge::issue_challenge(
+                                    &database, user.id, expires,
+                                )
+                                .map_err(ErrorInternalServerError)
+                                .and_then(move |challenge| {
+                                    render_challenge(challenge, form, renderer, None)
+                                }),
+                            );
+                        }
+                    },
+                    LoginAttempt::Enroll(user) => match user.inactive_reason() {
+                        Some(reason) => (reason, None),
+                        None => {
+                            let expires = Utc::now() + Duration::seconds(CHALLENGE_LIFETIME);
+
+                            return Box::new(
+                                crate::action::issue_challenge::issue_challenge(
+                                    &database, user.id, expires,
+                                )
+                                .map_err(ErrorInternalServerError)
+                                .and_then(move |challenge| {
+                                    render_enrollment(
+                                        user, challenge, form, database, renderer, config, None,
+                                    )
+                                }),
+                            );
+                        }
+                    },
+                    LoginAttempt::Failure(locked) => {
+                        if let Some(seconds) = locked {
+                            warn!(
+                            "Locked logins for {} from {} for {} seconds after repeated failures",
+                            email,
+                            address.as_ref().map(String::as_str).unwrap_or("unknown"),
+                            seconds
+                        );
+                        }
+
+                        ("Invalid user credentials", None)
+                    }
+                    LoginAttempt::Locked(seconds) => (
+                        "Too many failed login attempts, please try again later",
+                        Some(seconds),
+                    ),
+                };
+
+                Box::new(
+                    render(auth, form, renderer, Some(message)).map(move |res| match retry {
+                        Some(seconds) => too_many_requests(res, seconds),
+                        None => res,
+                    }),
+                )
+            }),
+    )
+}
+
+pub(crate) fn too_many_requests(mut res: HttpResponse, seconds: i64) -> HttpResponse {
+    *res.status_mut() = StatusCode::TOO_MANY_REQUESTS;
+    res.headers_mut().insert(RETRY_AFTER, seconds.into());
+
+    res
+}
+
+pub(crate) fn sign_in(id: &Identity, user: User) -> HttpResponse {
+    id.remember(user.id.to_string());
+
+    HttpRedirect::to("/").into_response()
+}
+
+pub(crate) fn render_challenge(
+    challenge: String,
+    form: Form,
+    renderer: Data<Renderer>,
+    message: Option<&'static str>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    let data = TwoFactorAuth {
+        challenge,
+        code: String::new(),
+    };
+
+    match FormData::with(data) {
+        Ok(data) => {
+            let mut form = form.with(data);
+
+            form.builder(TwoFactorForm);
+
+            Either::A(
+                form.build()
+                    .map_err(ErrorInternalServerError)
+                    .and_then(move |form| {
+                        let template = Template::new(
+                            "form-layout",
+                            json!({
+                                "title": "Two-factor authentication",
+                                "message": message,
+                                "form": form,
+                            }),
+                        );
+
+                        renderer
+                            .send(template)
+                            .map_err(ErrorInternalServerError)
+                            .and_then(|res| match res {
+                                Ok(body) => {
+                                    Ok(HttpResponse::Ok().content_type("text/html").body(body))
+                                }
+                                Err(err) => Err(ErrorInternalServerError(err)),
+                            })
+                    }),
+            )
+        }
+        Err(e) => Either::B(err(ErrorInternalServerError(e))),
+    }
+}
+
+pub(crate) fn render_enrollment(
+    user: User,
+    challenge: String,
+    form: Form,
     database: Data<Database>,
     renderer: Data<Renderer>,
+    config: Data<AuthConfig>,
+    message: Option<&'static str>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
-    crate::action::locate::locate(&database, auth.email.clone()).then(move |res| match res {
-        Ok(user) => match verify(&auth.password, &user.password) {
-            Ok(is_match) => {
-                if is_match {
-                    id.remember(user.id.to_string());
-                    Either::A(ok(HttpRedirect::to("/").into_response()))
-                } else {
-                    Either::B(Box::new(render(
-                        auth.into_inner(),
-                        renderer,
-                        Some("Invalid user credentials"),
-                    )))
+    crate::action::enroll_two_factor::enroll_two_factor(&database, user.id)
+        .map_err(ErrorInternalServerError)
+        .and_then(move |secret| {
+            let setup = match &secret {
+                Some(secret) => {
+                    let uri = totp::uri(&config.issuer, &user.email, secret);
+
+                    match totp::qr_code(&uri) {
+                        Ok(qr_code) => Some(json!({
+                            "secret": secret,
+                            "uri": uri,
+                            "qr_code": qr_code,
+                        })),
+                        Err(e) => return Either::B(err(ErrorInternalServerError(e))),
+                    }
                 }
-            }
-            Err(e) => Either::A(err(ErrorInternalServerError(e))),
-        },
-        Err(_) => Either::B(Box::new(render(
-            auth.into_inner(),
-            renderer,
-            Some("Invalid user credentials"),
-        ))),
-    })
+                None => None,
+            };
+
+            let data = TwoFactorAuth {
+                challenge,
+                code: String::new(),
+            };
+
+            let data = match FormData::with(data) {
+                Ok(data) => data,
+                Err(e) => return Either::B(err(ErrorInternalServerError(e))),
+            };
+
+            let mut form = form.with(data);
+
+            form.builder(TwoFactorEnrollForm);
+
+            Either::A(
+                form.build()
+                    .map_err(ErrorInternalServerError)
+                    .and_then(move |form| {
+                        let template = Template::new(
+                            "user-two-factor",
+                            json!({
+                                "title": "Two-factor authentication",
+                                "message": message,
+                                "status": TwoFactorStatus {
+                                    enabled: false,
+                                    required: true,
+                                    recovery_codes: 0,
+                                },
+                                "setup": setup,
+                                "form": form,
+                            }),
+                        );
+
+                        renderer
+                            .send(template)
+                            .map_err(ErrorInternalServerError)
+                            .and_then(|res| match res {
+                                Ok(body) => {
+                                    Ok(HttpResponse::Ok().content_type("text/html").body(body))
+                                }
+                                Err(err) => Err(ErrorInternalServerError(err)),
+                            })
+                    }),
+            )
+        })
 }
 
-fn render(
+pub(crate) fn render(
     auth: UserAuth,
+    form: Form,
     renderer: Data<Renderer>,
     message: Option<&'static str>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
     match FormData::with(auth) {
         Ok(data) => {
-            let mut form = Form::new(()).with(data);
+            let mut form = form.with(data);
 
             form.builder(LoginForm);
 
@@ -5,6 +5,8 @@ use actix_web::HttpResponse;
 use brace_db::Database;
 use brace_web::redirect::HttpRedirect;
 use brace_web::render::{Renderer, Template};
+use brace_web_form::csrf::{forbidden, REJECTED_MESSAGE};
+use brace_web_form::{Csrf, CsrfToken};
 use futures::future::{err, ok, Either, Future};
 use serde_json::json;
 use uuid::Uuid;
@@ -13,15 +15,45 @@ use crate::model::User;
 
 pub fn get(
     id: Identity,
+    csrf: CsrfToken,
+    database: Data<Database>,
+    renderer: Data<Renderer>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    confirm(id, csrf, database, renderer, None)
+}
+
+pub fn post(
+    id: Identity,
+    data: Csrf,
     database: Data<Database>,
     renderer: Data<Renderer>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    match id.identity() {
+        Some(_) if data.is_valid() => {
+            id.forget();
+
+            Either::A(ok(HttpRedirect::to("/").into_response()))
+        }
+        Some(_) => Either::B(
+            confirm(id, data.token(), database, renderer, Some(REJECTED_MESSAGE)).map(forbidden),
+        ),
+        None => Either::A(err(ErrorForbidden("Forbidden"))),
+    }
+}
+
+fn confirm(
+    id: Identity,
+    csrf: CsrfToken,
+    database: Data<Database>,
+    renderer: Data<Renderer>,
+    error: Option<&'static str>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
     match id.identity() {
         Some(user) => match user.parse::<Uuid>() {
             Ok(uuid) => Either::B(
                 crate::action::retrieve::retrieve(&database, uuid)
                     .map_err(ErrorInternalServerError)
-                    .and_then(move |user| render(user, &renderer)),
+                    .and_then(move |user| render(user, csrf, &renderer, error)),
             ),
             Err(e) => {
                 id.forget();
@@ -33,23 +65,19 @@ pub fn get(
     }
 }
 
-pub fn post(id: Identity) -> impl Future<Item = HttpRedirect, Error = Error> {
-    match id.identity() {
-        Some(_) => {
-            id.forget();
-
-            ok(HttpRedirect::to("/"))
-        }
-        None => err(ErrorForbidden("Forbidden")),
-    }
-}
-
-fn render(user: User, renderer: &Renderer) -> impl Future<Item = HttpResponse, Error = Error> {
+fn render(
+    user: User,
+    csrf: CsrfToken,
+    renderer: &Renderer,
+    error: Option<&'static str>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
     let template = Template::new(
         "form-confirm",
         json!({
             "title": "Log out",
             "message": format!("Are you sure that you want to log out of user <em>{}</em>?", user.email),
+            "error": error,
+            "csrf_token": csrf,
         }),
     );
 
@@ -1,12 +1,21 @@
 use actix_web::web::{self, ServiceConfig};
 
+pub mod api_keys;
+pub mod challenge;
 pub mod create;
 pub mod delete;
+pub mod forgot;
 pub mod list;
 pub mod login;
 pub mod logout;
+pub mod register;
+pub mod reset;
 pub mod retrieve;
+pub mod sessions;
+pub mod two_factor;
+pub mod unlock;
 pub mod update;
+pub mod verify;
 
 pub fn config(conf: &mut ServiceConfig) {
     conf.service(
@@ -14,11 +23,58 @@ pub fn config(conf: &mut ServiceConfig) {
             .route(web::get().to_async(login::get))
             .route(web::post().to_async(login::post)),
     )
+    .service(web::resource("/login/two-factor").route(web::post().to_async(challenge::post)))
+    .service(
+        web::resource("/login/two-factor/enroll").route(web::post().to_async(challenge::enroll)),
+    )
     .service(
         web::resource("/logout")
             .route(web::get().to_async(logout::get))
             .route(web::post().to_async(logout::post)),
     )
+    .service(
+        web::resource("/register")
+            .route(web::get().to_async(register::get))
+            .route(web::post().to_async(register::post)),
+    )
+    .service(web::resource("/register/verify/{token}").route(web::get().to_async(verify::get)))
+    .service(
+        web::resource("/password/forgot")
+            .route(web::get().to_async(forgot::get))
+            .route(web::post().to_async(forgot::post)),
+    )
+    .service(
+        web::resource("/password/reset/{token}")
+            .route(web::get().to_async(reset::get))
+            .route(web::post().to_async(reset::post)),
+    )
+    .service(
+        web::resource("/account/api-keys")
+            .route(web::get().to_async(api_keys::get))
+            .route(web::post().to_async(api_keys::post)),
+    )
+    .service(
+        web::resource("/account/api-keys/{key}/revoke")
+            .route(web::post().to_async(api_keys::revoke)),
+    )
+    .service(web::resource("/account/sessions").route(web::get().to_async(sessions::get)))
+    .service(
+        web::resource("/account/sessions/revoke")
+            .route(web::post().to_async(sessions::revoke_others)),
+    )
+    .service(
+        web::resource("/account/sessions/{session}/revoke")
+            .route(web::post().to_async(sessions::revoke)),
+    )
+    .service(
+        web::resource("/account/two-factor")
+            .route(web::get().to_async(two_factor::get))
+            .route(web::post().to_async(two_factor::post)),
+    )
+    .service(
+        web::resource("/account/two-factor/disable")
+            .route(web::post().to_async(two_factor::disable)),
+    )
     .service(
         web::scope("/users")
             .service(web::resource("/").route(web::get().to_async(list::get)))
@@ -33,6 +89,11 @@ pub fn config(conf: &mut ServiceConfig) {
                     .route(web::get().to_async(update::get))
                     .route(web::post().to_async(update::post)),
             )
+            .service(
+                web::resource("/{user}/unlock")
+                    .route(web::get().to_async(unlock::get))
+                    .route(web::post().to_async(unlock::post)),
+            )
             .service(
                 web::resource("/{user}/delete")
                     .route(web::get().to_async(delete::get))
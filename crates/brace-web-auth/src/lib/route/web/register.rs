@@ -0,0 +1,161 @@
+use actix_web::error::{Error, ErrorInternalServerError, ErrorNotFound};
+use actix_web::web::Data;
+use actix_web::HttpResponse;
+use brace_db::Database;
+use brace_mail::Mailer;
+use brace_web::config::WebConfig;
+use brace_web::render::{Renderer, Template};
+use brace_web_form::csrf::{forbidden, REJECTED_MESSAGE};
+use brace_web_form::{CsrfForm, Form, FormData};
+use chrono::{Duration, Utc};
+use futures::future::{err, ok, Either, Future};
+use log::error;
+use serde_json::json;
+
+use crate::config::{AuthConfig, RegistrationMode};
+use crate::form::register::RegisterForm;
+use crate::model::{PublicUser, Registration, User};
+use crate::util::{base_url, send_email};
+
+static SENT_MESSAGE: &str = "A link to verify your email address has been sent to it";
+
+static APPROVAL_MESSAGE: &str = "A link to verify your email address has been sent to it, \
+                                 an administrator has to approve the account before you can \
+                                 log in";
+
+pub fn get(
+    form: Form,
+    auth: Data<AuthConfig>,
+    renderer: Data<Renderer>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    if auth.registration == RegistrationMode::Closed {
+        return Either::A(err(ErrorNotFound("Not Found")));
+    }
+
+    Either::B(render(Registration::default(), form, renderer, None))
+}
+
+pub fn post(
+    data: CsrfForm<Registration>,
+    form: Form,
+    database: Data<Database>,
+    renderer: Data<Renderer>,
+    mailer: Data<Mailer>,
+    web: Data<WebConfig>,
+    auth: Data<AuthConfig>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    let valid = data.is_valid();
+    let data = data.into_inner();
+
+    let message = match auth.registration {
+        RegistrationMode::Closed => return Either::A(Either::A(err(ErrorNotFound("Not Found")))),
+        RegistrationMode::Open => SENT_MESSAGE,
+        RegistrationMode::Approval => APPROVAL_MESSAGE,
+    };
+
+    if !valid {
+        return Either::A(Either::B(Either::A(
+            render(data, form, renderer, Some(REJECTED_MESSAGE)).map(forbidden),
+        )));
+    }
+
+    if data.email.is_empty() || data.password.is_empty() {
+        return Either::A(Either::B(Either::B(render(
+            data,
+            form,
+            renderer,
+            Some("The email address and password must not be empty"),
+        ))));
+    }
+
+    if data.password != data.confirm {
+        return Either::A(Either::B(Either::B(render(
+            data,
+            form,
+            renderer,
+            Some("The passwords do not match"),
+        ))));
+    }
+
+    let base = base_url(&web);
+    let expires = Utc::now() + Duration::seconds(auth.verify_lifetime);
+    let mail_renderer = renderer.clone();
+    let user = User {
+        email: data.email,
+        password: data.password,
+        verified: false,
+        approved: auth.registration == RegistrationMode::Open,
+        ..User::default()
+    };
+
+    Either::B(
+        crate::action::register::register(&database, user, expires)
+            .and_then(move |res| match res {
+                Some((user, token)) => Either::A(send_email(
+                    &mail_renderer,
+                    &mailer,
+                    user.email.clone(),
+                    "Verify your email address",
+                    "email-verify",
+                    json!({
+                        "user": PublicUser::from(user),
+                        "url": format!("{}/register/verify/{}", base, token),
+                        "expires": expires.format("%Y-%m-%d %H:%M UTC").to_string(),
+                    }),
+                )),
+                None => Either::B(ok(())),
+            })
+            .then(move |res| {
+                if let Err(err) = res {
+                    error!("Failed to register a user: {}", err);
+                }
+
+                render(Registration::default(), form, renderer, Some(message))
+            }),
+    )
+}
+
+fn render(
+    data: Registration,
+    form: Form,
+    renderer: Data<Renderer>,
+    message: Option<&'static str>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    match FormData::with(Registration {
+        password: String::new(),
+        confirm: String::new(),
+        ..data
+    }) {
+        Ok(data) => {
+            let mut form = form.with(data);
+
+            form.builder(RegisterForm);
+
+            Either::A(
+                form.build()
+                    .map_err(ErrorInternalServerError)
+                    .and_then(move |form| {
+                        let template = Template::new(
+                            "form-layout",
+                            json!({
+                                "title": "Register",
+                                "message": message,
+                                "form": form,
+                            }),
+                        );
+
+                        renderer
+                            .send(template)
+                            .map_err(ErrorInternalServerError)
+                            .and_then(|res| match res {
+                                Ok(body) => {
+                                    Ok(HttpResponse::Ok().content_type("text/html").body(body))
+                                }
+                                Err(err) => Err(ErrorInternalServerError(err)),
+                            })
+                    }),
+            )
+        }
+        Err(e) => Either::B(err(ErrorInternalServerError(e))),
+    }
+}
@@ -0,0 +1,105 @@
+use actix_web::error::{Error, ErrorInternalServerError};
+use actix_web::web::{Data, Path};
+use actix_web::HttpResponse;
+use brace_db::Database;
+use brace_web::redirect::HttpRedirect;
+use brace_web::render::{Renderer, Template};
+use brace_web_form::csrf::{forbidden, REJECTED_MESSAGE};
+use brace_web_form::{CsrfForm, Form, FormData};
+use futures::future::{err, ok, Either, Future};
+use serde_json::json;
+
+use crate::form::password::PasswordChangeForm;
+use crate::model::PasswordChange;
+
+pub fn get(
+    form: Form,
+    renderer: Data<Renderer>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    render(form, renderer, None)
+}
+
+pub fn post(
+    info: Path<String>,
+    data: CsrfForm<PasswordChange>,
+    form: Form,
+    database: Data<Database>,
+    renderer: Data<Renderer>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    if !data.is_valid() {
+        return Either::A(Either::A(
+            render(form, renderer, Some(REJECTED_MESSAGE)).map(forbidden),
+        ));
+    }
+
+    let data = data.into_inner();
+
+    if data.password.is_empty() {
+        return Either::A(Either::B(render(
+            form,
+            renderer,
+            Some("The password must not be empty"),
+        )));
+    }
+
+    if data.password != data.confirm {
+        return Either::A(Either::B(render(
+            form,
+            renderer,
+            Some("The passwords do not match"),
+        )));
+    }
+
+    Either::B(
+        crate::action::reset_password::reset_password(&database, info.into_inner(), data.password)
+            .map_err(ErrorInternalServerError)
+            .and_then(move |user| match user {
+                Some(_) => Either::A(ok(HttpRedirect::to("/login").into_response())),
+                None => Either::B(render(
+                    form,
+                    renderer,
+                    Some("This password reset link is invalid or has expired"),
+                )),
+            }),
+    )
+}
+
+fn render(
+    form: Form,
+    renderer: Data<Renderer>,
+    message: Option<&'static str>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    match FormData::with(PasswordChange::default()) {
+        Ok(data) => {
+            let mut form = form.with(data);
+
+            form.builder(PasswordChangeForm);
+
+            Either::A(
+                form.build()
+                    .map_err(ErrorInternalServerError)
+                    .and_then(move |form| {
+                        let template = Template::new(
+                            "form-layout",
+                            json!({
+                                "title": "Reset password",
+                                "message": message,
+                                "form": form,
+                            }),
+                        );
+
+                        renderer
+                            .send(template)
+                            .map_err(ErrorInternalServerError)
+                            .and_then(|res| match res {
+                                Ok(body) => {
+                                    Ok(HttpResponse::Ok().content_type("text/html").body(body))
+                                }
+                                Err(err) => Err(ErrorInternalServerError(err)),
+                            })
+                    }),
+            )
+        }
+        Err(e) => Either::B(err(ErrorInternalServerError(e))),
+    }
+}
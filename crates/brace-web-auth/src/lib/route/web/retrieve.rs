@@ -1,37 +1,47 @@
-use actix_web::error::{Error, ErrorForbidden, ErrorInternalServerError};
+use actix_web::error::{Error, ErrorInternalServerError};
 use actix_web::web::{Data, Path};
 use actix_web::HttpResponse;
 use brace_db::Database;
 use brace_web::render::{Renderer, Template};
-use futures::future::{err, Either, Future};
+use futures::future::Future;
 use serde::Deserialize;
 use serde_json::json;
 use uuid::Uuid;
 
-use crate::model::{CurrentUser, User};
+use crate::config::AuthConfig;
+use crate::guard::{ManageUsers, WebGuard};
+use crate::model::{PublicUser, User};
 
 pub fn get(
-    user: CurrentUser,
+    _: WebGuard<ManageUsers>,
     info: Path<Info>,
     database: Data<Database>,
     renderer: Data<Renderer>,
+    config: Data<AuthConfig>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
-    match user {
-        CurrentUser::Anonymous => Either::A(err(ErrorForbidden("Forbidden"))),
-        CurrentUser::Authenticated(_) => Either::B(
-            crate::action::retrieve::retrieve(&database, info.user)
-                .map_err(ErrorInternalServerError)
-                .and_then(move |user| render(user, &renderer)),
-        ),
-    }
+    let lockout_database = database.clone();
+    let lockout = config.lockout;
+
+    crate::action::retrieve::retrieve(&database, info.user)
+        .and_then(move |user| {
+            crate::action::lockout::lockout(&lockout_database, user.email.clone(), lockout)
+                .map(|locked| (user, locked))
+        })
+        .map_err(ErrorInternalServerError)
+        .and_then(move |(user, locked)| render(user, locked, &renderer))
 }
 
-fn render(user: User, renderer: &Renderer) -> impl Future<Item = HttpResponse, Error = Error> {
+fn render(
+    user: User,
+    locked: Option<i64>,
+    renderer: &Renderer,
+) -> impl Future<Item = HttpResponse, Error = Error> {
     let template = Template::new(
         "user",
         json!({
             "title": format!("User <em>{}</em>", user.email),
-            "user": user,
+            "user": PublicUser::from(user),
+            "locked": locked,
         }),
     );
 
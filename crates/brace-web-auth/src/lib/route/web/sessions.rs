@@ -0,0 +1,131 @@
+use actix_web::error::{Error, ErrorInternalServerError, ErrorNotFound};
+use actix_web::web::{Data, Path};
+use actix_web::{HttpRequest, HttpResponse};
+use brace_db::Database;
+use brace_web::redirect::HttpRedirect;
+use brace_web::render::{Renderer, Template};
+use brace_web_form::csrf::{forbidden, REJECTED_MESSAGE};
+use brace_web_form::{Csrf, CsrfToken};
+use futures::future::{Either, Future};
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::guard::{Authenticated, WebGuard};
+use crate::session::SessionId;
+
+pub fn get(
+    req: HttpRequest,
+    access: WebGuard<Authenticated>,
+    csrf: CsrfToken,
+    database: Data<Database>,
+    renderer: Data<Renderer>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    render(&req, access.user.id, csrf, &database, renderer, None)
+}
+
+pub fn revoke(
+    req: HttpRequest,
+    access: WebGuard<Authenticated>,
+    info: Path<Info>,
+    data: Csrf,
+    database: Data<Database>,
+    renderer: Data<Renderer>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    if !data.is_valid() {
+        return Either::A(
+            render(
+                &req,
+                access.user.id,
+                data.token(),
+                &database,
+                renderer,
+                Some(REJECTED_MESSAGE),
+            )
+            .map(forbidden),
+        );
+    }
+
+    Either::B(
+        crate::action::revoke_session::revoke_session(&database, access.user.id, info.session)
+            .map_err(ErrorInternalServerError)
+            .and_then(|revoked| {
+                if revoked {
+                    Ok(HttpRedirect::to("/account/sessions").into_response())
+                } else {
+                    Err(ErrorNotFound("Not Found"))
+                }
+            }),
+    )
+}
+
+pub fn revoke_others(
+    req: HttpRequest,
+    access: WebGuard<Authenticated>,
+    data: Csrf,
+    database: Data<Database>,
+    renderer: Data<Renderer>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    if !data.is_valid() {
+        return Either::A(
+            render(
+                &req,
+                access.user.id,
+                data.token(),
+                &database,
+                renderer,
+                Some(REJECTED_MESSAGE),
+            )
+            .map(forbidden),
+        );
+    }
+
+    Either::B(
+        crate::action::revoke_sessions::revoke_sessions(
+            &database,
+            access.user.id,
+            SessionId::of(&req),
+        )
+        .map_err(ErrorInternalServerError)
+        .map(|_| HttpRedirect::to("/account/sessions").into_response()),
+    )
+}
+
+fn render(
+    req: &HttpRequest,
+    user: Uuid,
+    csrf: CsrfToken,
+    database: &Database,
+    renderer: Data<Renderer>,
+    message: Option<&'static str>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    let current = SessionId::of(req);
+
+    crate::action::list_sessions::list_sessions(database, user)
+        .map_err(ErrorInternalServerError)
+        .and_then(move |sessions| {
+            let template = Template::new(
+                "user-sessions",
+                json!({
+                    "title": "Sessions",
+                    "message": message,
+                    "sessions": sessions,
+                    "current": current,
+                    "csrf_token": csrf,
+                }),
+            );
+
+            renderer
+                .send(template)
+                .map_err(ErrorInternalServerError)
+                .and_then(|res| match res {
+                    Ok(body) => Ok(HttpResponse::Ok().content_type("text/html").body(body)),
+                    Err(err) => Err(ErrorInternalServerError(err)),
+                })
+        })
+}
+
+#[derive(Deserialize)]
+pub struct Info {
+    session: Uuid,
+}
@@ -0,0 +1,218 @@
+use actix_web::error::{Error, ErrorInternalServerError};
+use actix_web::web::Data;
+use actix_web::HttpResponse;
+use brace_db::Database;
+use brace_web::redirect::HttpRedirect;
+use brace_web::render::{Renderer, Template};
+use brace_web_form::csrf::{forbidden, REJECTED_MESSAGE};
+use brace_web_form::{CsrfForm, Form};
+use futures::future::{err, ok, Either, Future};
+use serde_json::json;
+
+use crate::config::AuthConfig;
+use crate::form::two_factor::TwoFactorCodeForm;
+use crate::guard::{Authenticated, WebGuard};
+use crate::model::{TwoFactorCode, User};
+use crate::totp;
+
+pub fn get(
+    access: WebGuard<Authenticated>,
+    form: Form,
+    database: Data<Database>,
+    renderer: Data<Renderer>,
+    config: Data<AuthConfig>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    render(
+        access.into_inner().user,
+        form,
+        database,
+        renderer,
+        config,
+        None,
+        None,
+    )
+}
+
+pub fn post(
+    access: WebGuard<Authenticated>,
+    data: CsrfForm<TwoFactorCode>,
+    form: Form,
+    database: Data<Database>,
+    renderer: Data<Renderer>,
+    config: Data<AuthConfig>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    let user = access.into_inner().user;
+
+    if !data.is_valid() {
+        return Either::A(
+            render(
+                user,
+                form,
+                database,
+                renderer,
+                config,
+                Some(REJECTED_MESSAGE),
+                None,
+            )
+            .map(forbidden),
+        );
+    }
+
+    Either::B(
+        crate::action::enable_two_factor::enable_two_factor(
+            &database,
+            user.id,
+            data.into_inner().code,
+        )
+        .map_err(ErrorInternalServerError)
+        .and_then(move |codes| match codes {
+            Some(codes) => render(user, form, database, renderer, config, None, Some(codes)),
+            None => render(
+                user,
+                form,
+                database,
+                renderer,
+                config,
+                Some("Invalid authentication code"),
+                None,
+            ),
+        }),
+    )
+}
+
+pub fn disable(
+    access: WebGuard<Authenticated>,
+    data: CsrfForm<TwoFactorCode>,
+    form: Form,
+    database: Data<Database>,
+    renderer: Data<Renderer>,
+    config: Data<AuthConfig>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    let user = access.into_inner().user;
+
+    if !data.is_valid() {
+        return Either::A(
+            render(
+                user,
+                form,
+                database,
+                renderer,
+                config,
+                Some(REJECTED_MESSAGE),
+                None,
+            )
+            .map(forbidden),
+        );
+    }
+
+    Either::B(
+        crate::action::disable_two_factor::disable_two_factor(
+            &database,
+            user.id,
+            data.into_inner().code,
+        )
+        .map_err(ErrorInternalServerError)
+        .and_then(move |disabled| {
+            if disabled {
+                Either::A(ok(HttpRedirect::to("/account/two-factor").into_response()))
+            } else {
+                Either::B(render(
+                    user,
+                    form,
+                    database,
+                    renderer,
+                    config,
+                    Some("Invalid authentication code"),
+                    None,
+                ))
+            }
+        }),
+    )
+}
+
+pub(crate) fn render(
+    user: User,
+    mut form: Form,
+    database: Data<Database>,
+    renderer: Data<Renderer>,
+    config: Data<AuthConfig>,
+    message: Option<&'static str>,
+    codes: Option<Vec<String>>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let enroll_database = database.clone();
+
+    Box::new(
+        crate::action::two_factor_status::two_factor_status(&database, user.id)
+            .and_then(move |status| {
+                if status.enabled {
+                    Either::A(ok((status, None)))
+                } else {
+                    Either::B(
+                        crate::action::enroll_two_factor::enroll_two_factor(
+                            &enroll_database,
+                            user.id,
+                        )
+                        .map(|secret| (status, secret)),
+                    )
+                }
+            })
+            .map_err(ErrorInternalServerError)
+            .and_then(move |(status, secret)| {
+                let setup = match &secret {
+                    Some(secret) => {
+                        let uri = totp::uri(&config.issuer, &user.email, secret);
+
+                        match totp::qr_code(&uri) {
+                            Ok(qr_code) => Some(json!({
+                                "secret": secret,
+                                "uri": uri,
+                                "qr_code": qr_code,
+                            })),
+                            Err(e) => return Either::B(err(ErrorInternalServerError(e))),
+                        }
+                    }
+                    None => None,
+                };
+
+                form.builder(if status.enabled {
+                    TwoFactorCodeForm {
+                        url: "/account/two-factor/disable",
+                        label: "Disable two-factor authentication",
+                    }
+                } else {
+                    TwoFactorCodeForm {
+                        url: "/account/two-factor",
+                        label: "Enable two-factor authentication",
+                    }
+                });
+
+                Either::A(
+                    form.build()
+                        .map_err(ErrorInternalServerError)
+                        .and_then(move |form| {
+                            let template = Template::new(
+                                "user-two-factor",
+                                json!({
+                                    "title": "Two-factor authentication",
+                                    "message": message,
+                                    "status": status,
+                                    "setup": setup,
+                                    "codes": codes,
+                                    "form": form,
+                                }),
+                            );
+
+                            renderer
+                                .send(template)
+                                .map_err(ErrorInternalServerError)
+                                .and_then(|res| match res {
+                                    Ok(body) => {
+                                        Ok(HttpResponse::Ok().content_type("text/html").body(body))
+                                    }
+                                    Err(err) => Err(ErrorInternalServerError(err)),
+                                })
+                        }),
+                )
+            }),
+    )
+}
@@ -0,0 +1,93 @@
+use actix_web::error::{Error, ErrorInternalServerError};
+use actix_web::web::{Data, Path};
+use actix_web::HttpResponse;
+use brace_db::Database;
+use brace_web::redirect::HttpRedirect;
+use brace_web::render::{Renderer, Template};
+use brace_web_form::csrf::{forbidden, REJECTED_MESSAGE};
+use brace_web_form::{Csrf, CsrfToken};
+use futures::future::{Either, Future};
+use log::info;
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::guard::{ManageUsers, WebGuard};
+use crate::model::User;
+
+pub fn get(
+    _: WebGuard<ManageUsers>,
+    info: Path<Info>,
+    csrf: CsrfToken,
+    renderer: Data<Renderer>,
+    database: Data<Database>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    crate::action::retrieve::retrieve(&database, info.user)
+        .map_err(ErrorInternalServerError)
+        .and_then(move |user| render(user, csrf, &renderer, None))
+}
+
+pub fn post(
+    access: WebGuard<ManageUsers>,
+    info: Path<Info>,
+    data: Csrf,
+    renderer: Data<Renderer>,
+    database: Data<Database>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    if !data.is_valid() {
+        return Either::A(
+            crate::action::retrieve::retrieve(&database, info.user)
+                .map_err(ErrorInternalServerError)
+                .and_then(move |user| render(user, data.token(), &renderer, Some(REJECTED_MESSAGE)))
+                .map(forbidden),
+        );
+    }
+
+    let unlock_database = database.clone();
+
+    Either::B(
+        crate::action::retrieve::retrieve(&database, info.user)
+            .and_then(move |user| {
+                crate::action::unlock::unlock(&unlock_database, user.email.clone()).map(|_| user)
+            })
+            .map_err(ErrorInternalServerError)
+            .map(move |user| {
+                info!(
+                    "Unlocked logins for {} by {}",
+                    user.email, access.user.email
+                );
+
+                HttpRedirect::to(format!("/users/{}", user.id)).into_response()
+            }),
+    )
+}
+
+fn render(
+    user: User,
+    csrf: CsrfToken,
+    renderer: &Renderer,
+    error: Option<&'static str>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    let template = Template::new(
+        "form-confirm",
+        json!({
+            "title": format!("Unlock user <em>{}</em>?", user.email),
+            "message": format!("Are you sure that you want to clear the failed login attempts of the user <em>{}</em>?", user.email),
+            "error": error,
+            "csrf_token": csrf,
+        }),
+    );
+
+    renderer
+        .send(template)
+        .map_err(ErrorInternalServerError)
+        .and_then(|res| match res {
+            Ok(body) => Ok(HttpResponse::Ok().content_type("text/html").body(body)),
+            Err(err) => Err(ErrorInternalServerError(err)),
+        })
+}
+
+#[derive(Deserialize)]
+pub struct Info {
+    user: Uuid,
+}
@@ -1,55 +1,97 @@
-use actix_web::error::{Error, ErrorForbidden, ErrorInternalServerError};
-use actix_web::web::{Data, Form as FormExtractor, Path};
-use actix_web::HttpResponse;
+use actix_web::error::{Error, ErrorInternalServerError};
+use actix_web::web::{Data, Path};
+use actix_web::{HttpRequest, HttpResponse};
 use brace_db::Database;
 use brace_web::redirect::HttpRedirect;
 use brace_web::render::{Renderer, Template};
-use brace_web_form::{Form, FormData};
-use futures::future::{err, Either, Future};
+use brace_web_form::csrf::{forbidden, REJECTED_MESSAGE};
+use brace_web_form::{CsrfForm, Form, FormData};
+use futures::future::{err, ok, Either, Future};
 use serde::Deserialize;
 use serde_json::json;
 use uuid::Uuid;
 
+use crate::action::email::EmailError;
 use crate::form::user::UserForm;
-use crate::model::{CurrentUser, User};
+use crate::guard::{ManageUsers, WebGuard};
+use crate::model::UserUpdate;
+use crate::session::SessionId;
 
 pub fn get(
-    user: CurrentUser,
+    _: WebGuard<ManageUsers>,
     info: Path<Info>,
+    form: Form,
     renderer: Data<Renderer>,
     database: Data<Database>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
-    match user {
-        CurrentUser::Anonymous => Either::A(err(ErrorForbidden("Forbidden"))),
-        CurrentUser::Authenticated(_) => Either::B(
-            crate::action::retrieve::retrieve(&database, info.user)
-                .map_err(ErrorInternalServerError)
-                .and_then(move |user| render(user, renderer)),
-        ),
-    }
+    crate::action::retrieve::retrieve(&database, info.user)
+        .map_err(ErrorInternalServerError)
+        .and_then(move |user| render(UserUpdate::from(user), form, renderer, None))
 }
 
 pub fn post(
-    user: CurrentUser,
-    data: FormExtractor<User>,
+    req: HttpRequest,
+    _: WebGuard<ManageUsers>,
+    info: Path<Info>,
+    data: CsrfForm<UserUpdate>,
+    form: Form,
     database: Data<Database>,
-) -> impl Future<Item = HttpRedirect, Error = Error> {
-    match user {
-        CurrentUser::Anonymous => Either::A(err(ErrorForbidden("Forbidden"))),
-        CurrentUser::Authenticated(_) => Either::B(
-            crate::action::update::update(&database, data.into_inner())
-                .map_err(ErrorInternalServerError)
-                .and_then(|user| HttpRedirect::to(format!("/users/{}", user.id))),
-        ),
+    renderer: Data<Renderer>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    if !data.is_valid() {
+        return Either::A(
+            render(
+                data.into_inner(),
+                form,
+                renderer,
+                Some(REJECTED_MESSAGE.to_string()),
+            )
+            .map(forbidden),
+        );
     }
+
+    let data = data.into_inner();
+    let revoke = data.password().is_some();
+    let keep = SessionId::of(&req);
+
+    Either::B(
+        crate::action::update::update(&database, info.user, data.clone())
+            .and_then(move |user| {
+                if revoke {
+                    Either::A(
+                        crate::action::revoke_sessions::revoke_sessions(&database, user.id, keep)
+                            .map(move |_| user),
+                    )
+                } else {
+                    Either::B(ok(user))
+                }
+            })
+            .then(move |res| match res {
+                Ok(user) => Either::A(ok(
+                    HttpRedirect::to(format!("/users/{}", user.id)).into_response()
+                )),
+                Err(e) => match e.downcast::<EmailError>() {
+                    Ok(e) => Either::B(render(data, form, renderer, Some(e.to_string()))),
+                    Err(e) => Either::A(err(ErrorInternalServerError(e))),
+                },
+            }),
+    )
 }
 
-fn render(user: User, renderer: Data<Renderer>) -> impl Future<Item = HttpResponse, Error = Error> {
+fn render(
+    user: UserUpdate,
+    form: Form,
+    renderer: Data<Renderer>,
+    message: Option<String>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
     let title = format!("Update user <em>{}</em>", user.email);
 
-    match FormData::with(user) {
+    match FormData::with(UserUpdate {
+        password: None,
+        ..user
+    }) {
         Ok(data) => {
-            let mut form = Form::new(()).with(data);
+            let mut form = form.with(data);
 
             form.builder(UserForm);
 
@@ -61,6 +103,7 @@ fn render(user: User, renderer: Data<Renderer>) -> impl Future<Item = HttpRespon
                             "form-layout",
                             json!({
                                 "title": title,
+                                "message": message,
                                 "form": form,
                             }),
                         );
@@ -0,0 +1,19 @@
+use actix_web::error::{Error, ErrorInternalServerError, ErrorNotFound};
+use actix_web::web::{Data, Path};
+use brace_db::Database;
+use brace_web::redirect::HttpRedirect;
+use futures::future::Future;
+
+pub fn get(
+    info: Path<String>,
+    database: Data<Database>,
+) -> impl Future<Item = HttpRedirect, Error = Error> {
+    crate::action::verify_email::verify_email(&database, info.into_inner())
+        .map_err(ErrorInternalServerError)
+        .and_then(|user| match user {
+            Some(_) => Ok(HttpRedirect::to("/login")),
+            None => Err(ErrorNotFound(
+                "This verification link is invalid or has expired",
+            )),
+        })
+}
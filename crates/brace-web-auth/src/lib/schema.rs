@@ -0,0 +1,333 @@
+use brace_db::Migration;
+
+static CREATE_USERS_UP: &str = r#"
+    CREATE TABLE IF NOT EXISTS users (
+        id uuid PRIMARY KEY,
+        email text NOT NULL CHECK (email <> ''),
+        password text NOT NULL CHECK (password <> ''),
+        created timestamp with time zone NOT NULL DEFAULT now(),
+        updated timestamp with time zone NOT NULL DEFAULT now() CHECK (updated >= created)
+    )
+"#;
+
+static CREATE_USERS_DOWN: &str = r#"
+    DROP TABLE users
+"#;
+
+static CREATE_REFRESH_TOKENS_UP: &str = r#"
+    CREATE TABLE IF NOT EXISTS refresh_tokens (
+        id uuid PRIMARY KEY,
+        "user" uuid NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+        hash text NOT NULL,
+        created timestamp with time zone NOT NULL DEFAULT now(),
+        expires timestamp with time zone NOT NULL,
+        revoked timestamp with time zone
+    )
+"#;
+
+static CREATE_REFRESH_TOKENS_DOWN: &str = r#"
+    DROP TABLE refresh_tokens
+"#;
+
+static CREATE_REVOKED_TOKENS_UP: &str = r#"
+    CREATE TABLE IF NOT EXISTS revoked_tokens (
+        jti uuid PRIMARY KEY,
+        expires timestamp with time zone NOT NULL
+    )
+"#;
+
+static CREATE_REVOKED_TOKENS_DOWN: &str = r#"
+    DROP TABLE revoked_tokens
+"#;
+
+static CREATE_ROLES_UP: &str = r#"
+    CREATE TABLE IF NOT EXISTS roles (
+        id uuid PRIMARY KEY,
+        name text NOT NULL UNIQUE CHECK (name <> ''),
+        label text NOT NULL DEFAULT ''
+    );
+
+    CREATE TABLE IF NOT EXISTS role_permissions (
+        role uuid NOT NULL REFERENCES roles(id) ON DELETE CASCADE,
+        permission text NOT NULL CHECK (permission <> ''),
+        PRIMARY KEY (role, permission)
+    );
+
+    CREATE TABLE IF NOT EXISTS user_roles (
+        "user" uuid NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+        role uuid NOT NULL REFERENCES roles(id) ON DELETE CASCADE,
+        PRIMARY KEY ("user", role)
+    );
+
+    INSERT INTO roles (id, name, label)
+    VALUES
+        (md5('administrator')::uuid, 'administrator', 'Administrator'),
+        (md5('editor')::uuid, 'editor', 'Editor'),
+        (md5('author')::uuid, 'author', 'Author');
+
+    INSERT INTO role_permissions (role, permission)
+    VALUES
+        (md5('administrator')::uuid, 'create page'),
+        (md5('administrator')::uuid, 'edit any page'),
+        (md5('administrator')::uuid, 'delete any page'),
+        (md5('administrator')::uuid, 'manage users'),
+        (md5('editor')::uuid, 'create page'),
+        (md5('editor')::uuid, 'edit any page'),
+        (md5('editor')::uuid, 'delete any page'),
+        (md5('author')::uuid, 'create page'),
+        (md5('author')::uuid, 'edit own page'),
+        (md5('author')::uuid, 'delete own page');
+
+    INSERT INTO user_roles ("user", role)
+    SELECT id, md5('administrator')::uuid
+    FROM users;
+"#;
+
+static CREATE_ROLES_DOWN: &str = r#"
+    DROP TABLE user_roles;
+    DROP TABLE role_permissions;
+    DROP TABLE roles;
+"#;
+
+static CREATE_PASSWORD_RESETS_UP: &str = r#"
+    CREATE TABLE IF NOT EXISTS password_resets (
+        id uuid PRIMARY KEY,
+        "user" uuid NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+        hash text NOT NULL,
+        created timestamp with time zone NOT NULL DEFAULT now(),
+        expires timestamp with time zone NOT NULL,
+        used timestamp with time zone
+    );
+
+    CREATE TABLE IF NOT EXISTS password_reset_requests (
+        id uuid PRIMARY KEY,
+        email text NOT NULL,
+        address text,
+        created timestamp with time zone NOT NULL DEFAULT now()
+    );
+"#;
+
+static CREATE_PASSWORD_RESETS_DOWN: &str = r#"
+    DROP TABLE password_reset_requests;
+    DROP TABLE password_resets;
+"#;
+
+static ADD_USER_VERIFICATION_UP: &str = r#"
+    ALTER TABLE users
+    ADD COLUMN verified boolean NOT NULL DEFAULT true,
+    ADD COLUMN approved boolean NOT NULL DEFAULT true;
+
+    ALTER TABLE users
+    ALTER COLUMN verified DROP DEFAULT,
+    ALTER COLUMN approved DROP DEFAULT;
+
+    CREATE TABLE IF NOT EXISTS email_verifications (
+        id uuid PRIMARY KEY,
+        "user" uuid NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+        hash text NOT NULL,
+        created timestamp with time zone NOT NULL DEFAULT now(),
+        expires timestamp with time zone NOT NULL,
+        used timestamp with time zone
+    );
+"#;
+
+static ADD_USER_VERIFICATION_DOWN: &str = r#"
+    DROP TABLE email_verifications;
+
+    ALTER TABLE users
+    DROP COLUMN verified,
+    DROP COLUMN approved;
+"#;
+
+static CREATE_LOGIN_FAILURES_UP: &str = r#"
+    CREATE TABLE IF NOT EXISTS login_failures (
+        id uuid PRIMARY KEY,
+        email text NOT NULL,
+        address text,
+        created timestamp with time zone NOT NULL DEFAULT now()
+    );
+
+    CREATE INDEX IF NOT EXISTS login_failures_email_idx
+    ON login_failures (lower(email), created);
+
+    CREATE INDEX IF NOT EXISTS login_failures_address_idx
+    ON login_failures (address, created);
+"#;
+
+static CREATE_LOGIN_FAILURES_DOWN: &str = r#"
+    DROP TABLE login_failures
+"#;
+
+static ADD_TWO_FACTOR_UP: &str = r#"
+    CREATE TABLE IF NOT EXISTS user_totp (
+        "user" uuid PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+        secret text NOT NULL,
+        last_step bigint,
+        created timestamp with time zone NOT NULL DEFAULT now(),
+        enabled timestamp with time zone
+    );
+
+    CREATE TABLE IF NOT EXISTS recovery_codes (
+        id uuid PRIMARY KEY,
+        "user" uuid NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+        hash text NOT NULL,
+        used timestamp with time zone
+    );
+
+    CREATE TABLE IF NOT EXISTS login_challenges (
+        id uuid PRIMARY KEY,
+        "user" uuid NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+        hash text NOT NULL,
+        expires timestamp with time zone NOT NULL,
+        attempts integer NOT NULL DEFAULT 0,
+        used timestamp with time zone
+    );
+
+    ALTER TABLE roles
+    ADD COLUMN two_factor boolean NOT NULL DEFAULT false;
+"#;
+
+static ADD_TWO_FACTOR_DOWN: &str = r#"
+    ALTER TABLE roles
+    DROP COLUMN two_factor;
+
+    DROP TABLE login_challenges;
+    DROP TABLE recovery_codes;
+    DROP TABLE user_totp;
+"#;
+
+static CREATE_API_KEYS_UP: &str = r#"
+    CREATE TABLE IF NOT EXISTS api_keys (
+        id uuid PRIMARY KEY,
+        "user" uuid NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+        name text NOT NULL,
+        hash text NOT NULL,
+        scopes text[],
+        created timestamp with time zone NOT NULL DEFAULT now(),
+        expires timestamp with time zone,
+        last_used timestamp with time zone
+    );
+"#;
+
+static CREATE_API_KEYS_DOWN: &str = r#"
+    DROP TABLE api_keys
+"#;
+
+static ADD_USER_PROFILES_UP: &str = r#"
+    DO $$
+    BEGIN
+        IF EXISTS (
+            SELECT 1
+            FROM users
+            GROUP BY lower(email)
+            HAVING count(*) > 1
+        ) THEN
+            RAISE EXCEPTION 'Several users share an email address that only differs in case';
+        END IF;
+    END
+    $$;
+
+    CREATE UNIQUE INDEX users_email_key ON users (lower(email));
+
+    ALTER TABLE users
+    ADD COLUMN display_name text,
+    ADD COLUMN avatar text,
+    ADD COLUMN bio text;
+"#;
+
+static ADD_USER_PROFILES_DOWN: &str = r#"
+    ALTER TABLE users
+    DROP COLUMN bio,
+    DROP COLUMN avatar,
+    DROP COLUMN display_name;
+
+    DROP INDEX users_email_key;
+"#;
+
+static CREATE_SESSIONS_UP: &str = r#"
+    CREATE TABLE IF NOT EXISTS sessions (
+        id uuid PRIMARY KEY,
+        "user" uuid NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+        user_agent text,
+        address text,
+        created timestamp with time zone NOT NULL DEFAULT now(),
+        last_seen timestamp with time zone NOT NULL DEFAULT now()
+    );
+
+    CREATE INDEX sessions_user_idx ON sessions ("user");
+"#;
+
+static CREATE_SESSIONS_DOWN: &str = r#"
+    DROP TABLE sessions
+"#;
+
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        Migration::new(
+            "auth",
+            "0001_create_users",
+            CREATE_USERS_UP,
+            CREATE_USERS_DOWN,
+        ),
+        Migration::new(
+            "auth",
+            "0002_create_refresh_tokens",
+            CREATE_REFRESH_TOKENS_UP,
+            CREATE_REFRESH_TOKENS_DOWN,
+        ),
+        Migration::new(
+            "auth",
+            "0003_create_revoked_tokens",
+            CREATE_REVOKED_TOKENS_UP,
+            CREATE_REVOKED_TOKENS_DOWN,
+        ),
+        Migration::new(
+            "auth",
+            "0004_create_roles",
+            CREATE_ROLES_UP,
+            CREATE_ROLES_DOWN,
+        ),
+        Migration::new(
+            "auth",
+            "0005_create_password_resets",
+            CREATE_PASSWORD_RESETS_UP,
+            CREATE_PASSWORD_RESETS_DOWN,
+        ),
+        Migration::new(
+            "auth",
+            "0006_add_user_verification",
+            ADD_USER_VERIFICATION_UP,
+            ADD_USER_VERIFICATION_DOWN,
+        ),
+        Migration::new(
+            "auth",
+            "0007_create_login_failures",
+            CREATE_LOGIN_FAILURES_UP,
+            CREATE_LOGIN_FAILURES_DOWN,
+        ),
+        Migration::new(
+            "auth",
+            "0008_add_two_factor",
+            ADD_TWO_FACTOR_UP,
+            ADD_TWO_FACTOR_DOWN,
+        ),
+        Migration::new(
+            "auth",
+            "0009_create_api_keys",
+            CREATE_API_KEYS_UP,
+            CREATE_API_KEYS_DOWN,
+        ),
+        Migration::new(
+            "auth",
+            "0010_add_user_profiles",
+            ADD_USER_PROFILES_UP,
+            ADD_USER_PROFILES_DOWN,
+        ),
+        Migration::new(
+            "auth",
+            "0011_create_sessions",
+            CREATE_SESSIONS_UP,
+            CREATE_SESSIONS_DOWN,
+        ),
+    ]
+}
@@ -0,0 +1,133 @@
+use actix_identity::{CookieIdentityPolicy, IdentityPolicy};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::error::{Error, ErrorInternalServerError};
+use actix_web::http::header::USER_AGENT;
+use actix_web::{HttpMessage, HttpRequest};
+use brace_db::Database;
+use futures::future::{err, ok, result, Either, Future};
+use uuid::Uuid;
+
+use crate::util::client_address;
+
+type BoxedFuture<I, E> = Box<dyn Future<Item = I, Error = E>>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SessionId(pub Uuid);
+
+impl SessionId {
+    pub fn of(req: &HttpRequest) -> Option<Uuid> {
+        req.extensions().get::<SessionId>().map(|session| session.0)
+    }
+}
+
+pub struct SessionIdentityPolicy {
+    cookie: CookieIdentityPolicy,
+    database: Database,
+    forwarded: bool,
+}
+
+impl SessionIdentityPolicy {
+    pub fn new(cookie: CookieIdentityPolicy, database: Database) -> Self {
+        Self {
+            cookie,
+            database,
+            forwarded: false,
+        }
+    }
+
+    pub fn forwarded(mut self, forwarded: bool) -> Self {
+        self.forwarded = forwarded;
+        self
+    }
+}
+
+impl IdentityPolicy for SessionIdentityPolicy {
+    type Future = BoxedFuture<Option<String>, Error>;
+    type ResponseFuture = BoxedFuture<(), Error>;
+
+    fn from_request(&self, req: &mut ServiceRequest) -> Self::Future {
+        let session = match self.cookie.from_request(req) {
+            Ok(session) => session.and_then(|session| session.parse::<Uuid>().ok()),
+            Err(e) => return Box::new(err(e)),
+        };
+
+        match session {
+            Some(session) => {
+                req.extensions_mut().insert(SessionId(session));
+
+                Box::new(
+                    crate::action::touch_session::touch_session(&self.database, session)
+                        .map_err(ErrorInternalServerError)
+                        .map(|user| user.map(|user| user.to_string())),
+                )
+            }
+            None => Box::new(ok(None)),
+        }
+    }
+
+    fn to_response<B>(
+        &self,
+        identity: Option<String>,
+        changed: bool,
+        res: &mut ServiceResponse<B>,
+    ) -> Self::ResponseFuture {
+        let current = SessionId::of(res.request());
+
+        if !changed {
+            // Refreshes the cookie of a live session and drops that of a revoked one.
+            return Box::new(result(match (identity, current) {
+                (Some(_), Some(session)) => {
+                    self.cookie
+                        .to_response(Some(session.to_string()), false, res)
+                }
+                (None, Some(_)) => self.cookie.to_response(None, true, res),
+                _ => Ok(()),
+            }));
+        }
+
+        let database = self.database.clone();
+        let end = match current {
+            Some(session) => Either::A(crate::action::delete_session::delete_session(
+                &database, session,
+            )),
+            None => Either::B(ok(())),
+        };
+
+        match identity {
+            Some(user) => {
+                let user = match user.parse::<Uuid>() {
+                    Ok(user) => user,
+                    Err(e) => return Box::new(err(ErrorInternalServerError(e))),
+                };
+                let session = Uuid::new_v4();
+                let user_agent = res
+                    .request()
+                    .headers()
+                    .get(USER_AGENT)
+                    .and_then(|header| header.to_str().ok())
+                    .map(str::to_owned);
+                let address = client_address(res.request(), self.forwarded);
+
+                if let Err(e) = self
+                    .cookie
+                    .to_response(Some(session.to_string()), true, res)
+                {
+                    return Box::new(err(e));
+                }
+
+                Box::new(
+                    end.and_then(move |_| {
+                        crate::action::create_session::create_session(
+                            &database, session, user, user_agent, address,
+                        )
+                    })
+                    .map_err(ErrorInternalServerError),
+                )
+            }
+            None => match self.cookie.to_response(None, true, res) {
+                Ok(_) => Box::new(end.map_err(ErrorInternalServerError)),
+                Err(e) => Box::new(err(e)),
+            },
+        }
+    }
+}
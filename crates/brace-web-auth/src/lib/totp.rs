@@ -0,0 +1,130 @@
+//! Time-based one-time passwords as described in RFC 6238.
+
+use base32::Alphabet;
+use failure::Error;
+use hmac::{Hmac, Mac};
+use qrcode::render::svg;
+use qrcode::QrCode;
+use rand::{thread_rng, Rng};
+use sha1::Sha1;
+
+pub const DIGITS: u32 = 6;
+
+pub const PERIOD: i64 = 30;
+
+pub const SKEW: i64 = 1;
+
+const ALPHABET: Alphabet = Alphabet::RFC4648 { padding: false };
+
+pub fn secret() -> String {
+    let bytes: [u8; 20] = thread_rng().gen();
+
+    base32::encode(ALPHABET, &bytes)
+}
+
+pub fn step(timestamp: i64) -> i64 {
+    timestamp / PERIOD
+}
+
+pub fn code(secret: &str, step: i64) -> Option<String> {
+    let key = base32::decode(ALPHABET, secret)?;
+    let mut mac = Hmac::<Sha1>::new_varkey(&key).ok()?;
+
+    mac.input(&(step as u64).to_be_bytes());
+
+    let hash = mac.result().code();
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let binary = (u32::from(hash[offset]) & 0x7f) << 24
+        | u32::from(hash[offset + 1]) << 16
+        | u32::from(hash[offset + 2]) << 8
+        | u32::from(hash[offset + 3]);
+
+    Some(format!(
+        "{:0width$}",
+        binary % 10u32.pow(DIGITS),
+        width = DIGITS as usize
+    ))
+}
+
+pub fn verify(secret: &str, code: &str, timestamp: i64, last: Option<i64>) -> Option<i64> {
+    let code = code.replace(' ', "");
+    let current = step(timestamp);
+
+    (current - SKEW..=current + SKEW)
+        .filter(|step| last.map_or(true, |last| *step > last))
+        .find(|step| self::code(secret, *step).map_or(false, |expected| eq(&expected, &code)))
+}
+
+pub fn uri(issuer: &str, account: &str, secret: &str) -> String {
+    format!(
+        "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        encode(issuer),
+        encode(account),
+        secret,
+        encode(issuer),
+        DIGITS,
+        PERIOD
+    )
+}
+
+pub fn qr_code(uri: &str) -> Result<String, Error> {
+    Ok(QrCode::new(uri.as_bytes())?
+        .render::<svg::Color>()
+        .min_dimensions(200, 200)
+        .build())
+}
+
+fn eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{code, secret, uri, verify};
+
+    // The SHA1 secret of the RFC 6238 test vectors, "12345678901234567890" in base32.
+    static SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    #[test]
+    fn test_totp_code() {
+        assert_eq!(code(SECRET, 59 / 30).unwrap(), "287082");
+        assert_eq!(code(SECRET, 1_111_111_109 / 30).unwrap(), "081804");
+        assert_eq!(code(SECRET, 1_234_567_890 / 30).unwrap(), "005924");
+        assert_eq!(code(SECRET, 20_000_000_000 / 30).unwrap(), "353130");
+        assert!(code("not base32!", 1).is_none());
+        assert_eq!(secret().len(), 32);
+    }
+
+    #[test]
+    fn test_totp_verify() {
+        assert_eq!(verify(SECRET, "287082", 59, None), Some(1));
+        assert_eq!(verify(SECRET, "287 082", 80, None), Some(1));
+        assert_eq!(verify(SECRET, "287082", 59, Some(1)), None);
+        assert_eq!(verify(SECRET, "287082", 120, None), None);
+        assert_eq!(verify(SECRET, "000000", 59, None), None);
+    }
+
+    #[test]
+    fn test_totp_uri() {
+        assert_eq!(
+            uri("brace site", "user@domain.test", SECRET),
+            "otpauth://totp/brace%20site:user%40domain.test?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ&issuer=brace%20site&algorithm=SHA1&digits=6&period=30"
+        );
+    }
+}
@@ -1,13 +1,20 @@
 use std::iter::repeat;
 use std::string::FromUtf8Error;
 
+use actix_web::HttpRequest;
 use argon2rs::verifier::{DecodeError, Encoded};
-use failure::Error;
-use jsonwebtoken::{decode, encode, Header, Validation};
+use brace_mail::{Email, Mailer};
+use brace_web::config::WebConfig;
+use brace_web::render::{Renderer, Template};
+use failure::{format_err, Error};
+use futures::future::Future;
 use rand::distributions::Alphanumeric;
 use rand::{thread_rng, Rng};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
 
-use crate::model::{Claims, SlimUser};
+use crate::config::Jwt;
+use crate::model::{Claims, User};
 
 pub fn salt() -> String {
     repeat(())
@@ -27,19 +34,79 @@ pub fn verify(password: &str, hash: &str) -> Result<bool, DecodeError> {
     }
 }
 
-pub fn create_token(data: SlimUser) -> Result<String, Error> {
-    let claims = Claims::with_email(data.email.as_str());
-    let res = encode(&Header::default(), &claims, get_secret().as_ref())?;
+pub fn digest(secret: &str) -> String {
+    Sha256::digest(secret.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+pub fn constant_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+pub fn create_token(jwt: &Jwt, user: &User) -> Result<String, Error> {
+    jwt.encode(user)
+}
+
+pub fn decode_token(jwt: &Jwt, token: &str) -> Result<Claims, Error> {
+    jwt.decode(token)
+}
 
-    Ok(res)
+pub fn bearer_token(req: &HttpRequest) -> Option<&str> {
+    let header = req.headers().get("Authorization")?.to_str().ok()?;
+    let parts = header.split(' ').collect::<Vec<&str>>();
+
+    if parts.len() == 2 && parts[0] == "Bearer" {
+        Some(parts[1])
+    } else {
+        None
+    }
 }
 
-pub fn decode_token(token: &str) -> Result<SlimUser, Error> {
-    let res = decode::<Claims>(token, get_secret().as_ref(), &Validation::default())?;
+pub fn client_address(req: &HttpRequest, forwarded: bool) -> Option<String> {
+    if forwarded {
+        let header = req
+            .headers()
+            .get("X-Forwarded-For")
+            .and_then(|header| header.to_str().ok())
+            .and_then(|header| header.split(',').next())
+            .map(str::trim);
+
+        if let Some(address) = header {
+            if !address.is_empty() {
+                return Some(address.to_string());
+            }
+        }
+    }
+
+    req.peer_addr().map(|addr| addr.ip().to_string())
+}
 
-    Ok(res.claims.into())
+pub fn base_url(web: &WebConfig) -> String {
+    match &web.url {
+        Some(url) => url.trim_end_matches('/').to_string(),
+        None => format!("http://{}:{}", web.host, web.port),
+    }
 }
 
-fn get_secret() -> String {
-    "secret".to_string()
+pub fn send_email(
+    renderer: &Renderer,
+    mailer: &Mailer,
+    to: String,
+    subject: &'static str,
+    template: &'static str,
+    value: Value,
+) -> impl Future<Item = (), Error = Error> {
+    let mailer = mailer.clone();
+
+    renderer
+        .send(Template::new(template, value))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res.map_err(|err| format_err!("{}", err)))
+        .and_then(move |html| mailer.send(Email::new(to, subject, html)))
 }
@@ -1,15 +1,56 @@
 use actix::System;
 use brace_db::{Database, DatabaseConfig};
+use brace_web::pager::{Order, Pager};
+use brace_web_auth::action::assign_roles::assign_roles;
+use brace_web_auth::action::authenticate::authenticate;
+use brace_web_auth::action::authenticate_key::authenticate_key;
+use brace_web_auth::action::complete_challenge::complete_challenge;
+use brace_web_auth::action::complete_enrollment::complete_enrollment;
 use brace_web_auth::action::create::create;
+use brace_web_auth::action::create_api_key::create_api_key;
+use brace_web_auth::action::create_session::create_session;
 use brace_web_auth::action::delete::delete;
+use brace_web_auth::action::delete_session::delete_session;
+use brace_web_auth::action::disable_two_factor::disable_two_factor;
+use brace_web_auth::action::email::EmailError;
+use brace_web_auth::action::enable_two_factor::enable_two_factor;
+use brace_web_auth::action::enroll_two_factor::enroll_two_factor;
 use brace_web_auth::action::install::install;
+use brace_web_auth::action::issue_challenge::issue_challenge;
+use brace_web_auth::action::issue_token::issue_token;
 use brace_web_auth::action::list::list;
+use brace_web_auth::action::list_api_keys::list_api_keys;
+use brace_web_auth::action::list_roles::list_roles;
+use brace_web_auth::action::list_sessions::list_sessions;
+use brace_web_auth::action::list_user_roles::list_user_roles;
 use brace_web_auth::action::locate::locate;
+use brace_web_auth::action::lockout::lockout;
+use brace_web_auth::action::login::login;
+use brace_web_auth::action::permissions::permissions;
+use brace_web_auth::action::refresh_token::refresh_token;
+use brace_web_auth::action::register::register;
+use brace_web_auth::action::request_reset::request_reset;
+use brace_web_auth::action::reset_password::reset_password;
 use brace_web_auth::action::retrieve::retrieve;
+use brace_web_auth::action::revoke_api_key::revoke_api_key;
+use brace_web_auth::action::revoke_session::revoke_session;
+use brace_web_auth::action::revoke_sessions::revoke_sessions;
+use brace_web_auth::action::throttle_reset::throttle_reset;
+use brace_web_auth::action::touch_session::touch_session;
+use brace_web_auth::action::two_factor_status::two_factor_status;
 use brace_web_auth::action::uninstall::uninstall;
+use brace_web_auth::action::unlock::unlock;
 use brace_web_auth::action::update::update;
-use brace_web_auth::model::User;
-use chrono::Utc;
+use brace_web_auth::action::update_role::update_role;
+use brace_web_auth::action::verify_email::verify_email;
+use brace_web_auth::config::{LockoutConfig, ResetThrottleConfig};
+use brace_web_auth::model::{
+    ChallengeAttempt, Claims, LoginAttempt, Permission, RoleUpdate, User, UserAuth, UserFilter,
+    UserSort, UserUpdate,
+};
+use brace_web_auth::totp;
+use brace_web_auth::util::verify;
+use chrono::{Duration, Utc};
 use uuid::Uuid;
 
 #[test]
@@ -23,6 +64,11 @@ fn test_user_lifecycle() {
         password: "password1".to_string(),
         created: Utc::now(),
         updated: Utc::now(),
+        verified: true,
+        approved: true,
+        display_name: None,
+        avatar: None,
+        bio: None,
     };
 
     let uuid2 = Uuid::new_v4();
@@ -32,6 +78,11 @@ fn test_user_lifecycle() {
         password: "password2".to_string(),
         created: Utc::now(),
         updated: Utc::now(),
+        verified: true,
+        approved: true,
+        display_name: None,
+        avatar: None,
+        bio: None,
     };
 
     system.block_on(install(&database)).unwrap();
@@ -39,8 +90,14 @@ fn test_user_lifecycle() {
     assert!(system.block_on(create(&database, user.clone())).is_ok());
     assert!(system.block_on(create(&database, user.clone())).is_err());
     assert!(system.block_on(create(&database, user2.clone())).is_ok());
-    assert!(system.block_on(update(&database, user.clone())).is_ok());
-    assert!(system.block_on(retrieve(&database, uuid)).is_ok());
+    assert!(system
+        .block_on(update(&database, uuid, UserUpdate::from(user.clone())))
+        .is_ok());
+    assert!(verify(
+        "password1",
+        &system.block_on(retrieve(&database, uuid)).unwrap().password
+    )
+    .unwrap());
     assert!(system
         .block_on(retrieve(&database, Uuid::new_v4()))
         .is_err());
@@ -71,21 +128,28 @@ fn test_user_lifecycle() {
         "user1@domain.test"
     );
 
-    let user = User {
-        id: uuid,
+    let user = UserUpdate {
         email: "user3@domain.test".to_string(),
-        password: "password3".to_string(),
-        created: Utc::now(),
-        updated: Utc::now(),
+        password: Some("password3".to_string()),
+        verified: None,
+        approved: Some(true),
+        display_name: None,
+        avatar: None,
+        bio: None,
     };
 
     assert_eq!(
         system
-            .block_on(update(&database, user.clone()))
+            .block_on(update(&database, uuid, user.clone()))
             .unwrap()
             .email,
         "user3@domain.test"
     );
+    assert!(verify(
+        "password3",
+        &system.block_on(retrieve(&database, uuid)).unwrap().password
+    )
+    .unwrap());
     assert_eq!(
         system.block_on(retrieve(&database, uuid)).unwrap().email,
         "user3@domain.test"
@@ -98,9 +162,1051 @@ fn test_user_lifecycle() {
         password: "password4".to_string(),
         created: Utc::now(),
         updated: Utc::now(),
+        verified: true,
+        approved: true,
+        display_name: None,
+        avatar: None,
+        bio: None,
     };
 
     assert!(system.block_on(create(&database, user.clone())).is_ok());
-    assert_eq!(system.block_on(list(&database)).unwrap().len(), 2);
+    let (users, total) = system
+        .block_on(list(&database, UserFilter::default(), Pager::default()))
+        .unwrap();
+
+    assert_eq!(users.len(), 2);
+    assert_eq!(total, 2);
+
+    let filter = UserFilter {
+        email: Some("USER4".to_string()),
+        ..UserFilter::default()
+    };
+    let (users, total) = system
+        .block_on(list(&database, filter, Pager::default()))
+        .unwrap();
+
+    assert_eq!(total, 1);
+    assert_eq!(users[0].email, "user4@domain.test");
+
+    let filter = UserFilter {
+        sort: UserSort::Email,
+        order: Order::Desc,
+        ..UserFilter::default()
+    };
+    let pager = Pager {
+        limit: Some(1),
+        offset: 0,
+    };
+    let (users, total) = system.block_on(list(&database, filter, pager)).unwrap();
+
+    assert_eq!(total, 2);
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0].email, "user4@domain.test");
     assert!(system.block_on(uninstall(&database)).is_ok());
 }
+
+#[test]
+fn test_user_roles() {
+    let mut system = System::new("test");
+    let database = Database::from_config(DatabaseConfig::default()).unwrap();
+    let user = User {
+        id: Uuid::new_v4(),
+        email: "user1@domain.test".to_string(),
+        password: "password1".to_string(),
+        created: Utc::now(),
+        updated: Utc::now(),
+        verified: true,
+        approved: true,
+        display_name: None,
+        avatar: None,
+        bio: None,
+    };
+
+    system.block_on(install(&database)).unwrap();
+    system.block_on(create(&database, user.clone())).unwrap();
+
+    let roles = system.block_on(list_roles(&database)).unwrap();
+
+    assert_eq!(
+        roles
+            .iter()
+            .map(|role| role.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["administrator", "author", "editor"]
+    );
+    assert!(system
+        .block_on(permissions(&database, user.id))
+        .unwrap()
+        .is_empty());
+
+    let roles = system
+        .block_on(assign_roles(&database, user.id, vec!["author".to_string()]))
+        .unwrap();
+
+    assert_eq!(roles.len(), 1);
+    assert_eq!(roles[0].name, "author");
+
+    let granted = system.block_on(permissions(&database, user.id)).unwrap();
+
+    assert!(granted.contains(&Permission::CreatePage));
+    assert!(granted.contains(&Permission::EditOwnPage));
+    assert!(!granted.contains(&Permission::EditAnyPage));
+    assert!(!granted.contains(&Permission::ManageUsers));
+
+    assert!(system
+        .block_on(assign_roles(
+            &database,
+            user.id,
+            vec!["editor".to_string(), "unknown".to_string()],
+        ))
+        .is_err());
+    assert_eq!(
+        system
+            .block_on(list_user_roles(&database, user.id))
+            .unwrap()
+            .len(),
+        1
+    );
+
+    system.block_on(uninstall(&database)).unwrap();
+}
+
+#[test]
+fn test_password_reset() {
+    let mut system = System::new("test");
+    let database = Database::from_config(DatabaseConfig::default()).unwrap();
+    let user = User {
+        id: Uuid::new_v4(),
+        email: "user1@domain.test".to_string(),
+        password: "password1".to_string(),
+        created: Utc::now(),
+        updated: Utc::now(),
+        verified: true,
+        approved: true,
+        display_name: None,
+        avatar: None,
+        bio: None,
+    };
+    let expires = Utc::now() + Duration::hours(1);
+
+    system.block_on(install(&database)).unwrap();
+    system.block_on(create(&database, user.clone())).unwrap();
+
+    assert!(system
+        .block_on(request_reset(
+            &database,
+            "unknown@domain.test".to_string(),
+            expires
+        ))
+        .unwrap()
+        .is_none());
+
+    let (requested, token) = system
+        .block_on(request_reset(&database, user.email.clone(), expires))
+        .unwrap()
+        .unwrap();
+    let (_, other) = system
+        .block_on(request_reset(&database, user.email.clone(), expires))
+        .unwrap()
+        .unwrap();
+
+    let session = Uuid::new_v4();
+
+    system
+        .block_on(create_session(&database, session, user.id, None, None))
+        .unwrap();
+
+    assert_eq!(requested.id, user.id);
+    assert!(system
+        .block_on(reset_password(
+            &database,
+            format!("{}x", token),
+            "password2".to_string()
+        ))
+        .unwrap()
+        .is_none());
+
+    let changed = system
+        .block_on(reset_password(
+            &database,
+            token.clone(),
+            "password2".to_string(),
+        ))
+        .unwrap()
+        .unwrap();
+
+    assert!(verify("password2", &changed.password).unwrap());
+    assert_eq!(
+        system.block_on(touch_session(&database, session)).unwrap(),
+        None
+    );
+    assert!(system
+        .block_on(reset_password(&database, token, "password3".to_string()))
+        .unwrap()
+        .is_none());
+    assert!(system
+        .block_on(reset_password(&database, other, "password3".to_string()))
+        .unwrap()
+        .is_none());
+
+    let (_, expired) = system
+        .block_on(request_reset(
+            &database,
+            user.email.clone(),
+            Utc::now() - Duration::hours(1),
+        ))
+        .unwrap()
+        .unwrap();
+
+    assert!(system
+        .block_on(reset_password(&database, expired, "password3".to_string()))
+        .unwrap()
+        .is_none());
+
+    let config = ResetThrottleConfig {
+        attempts: 2,
+        address_attempts: 3,
+        ..ResetThrottleConfig::default()
+    };
+    let address = Some("192.0.2.1".to_string());
+
+    for email in &["user1@domain.test", "USER1@domain.test"] {
+        assert!(system
+            .block_on(throttle_reset(
+                &database,
+                email.to_string(),
+                address.clone(),
+                config
+            ))
+            .unwrap()
+            .is_none());
+    }
+
+    for (email, throttled) in &[
+        ("user1@domain.test", true),
+        ("unknown@domain.test", false),
+        ("other@domain.test", true),
+    ] {
+        assert_eq!(
+            system
+                .block_on(throttle_reset(
+                    &database,
+                    email.to_string(),
+                    address.clone(),
+                    config
+                ))
+                .unwrap()
+                .is_some(),
+            *throttled
+        );
+    }
+
+    system.block_on(uninstall(&database)).unwrap();
+}
+
+#[test]
+fn test_user_registration() {
+    let mut system = System::new("test");
+    let database = Database::from_config(DatabaseConfig::default()).unwrap();
+    let user = User {
+        email: "user1@domain.test".to_string(),
+        password: "password1".to_string(),
+        verified: true,
+        approved: false,
+        ..User::default()
+    };
+    let expires = Utc::now() + Duration::hours(1);
+
+    system.block_on(install(&database)).unwrap();
+
+    let (registered, token) = system
+        .block_on(register(&database, user.clone(), expires))
+        .unwrap()
+        .unwrap();
+
+    assert!(!registered.verified);
+    assert!(!registered.approved);
+    assert!(registered.inactive_reason().is_some());
+    assert!(system
+        .block_on(register(
+            &database,
+            User {
+                id: Uuid::new_v4(),
+                ..user.clone()
+            },
+            expires
+        ))
+        .unwrap()
+        .is_none());
+    assert!(system
+        .block_on(verify_email(&database, format!("{}x", token)))
+        .unwrap()
+        .is_none());
+
+    let verified = system
+        .block_on(verify_email(&database, token.clone()))
+        .unwrap()
+        .unwrap();
+
+    assert!(verified.verified);
+    assert!(!verified.approved);
+    assert!(system
+        .block_on(verify_email(&database, token))
+        .unwrap()
+        .is_none());
+
+    let approved = system
+        .block_on(update(
+            &database,
+            verified.id,
+            UserUpdate {
+                approved: Some(true),
+                ..UserUpdate::from(verified)
+            },
+        ))
+        .unwrap();
+
+    assert!(approved.inactive_reason().is_none());
+
+    let token = system
+        .block_on(issue_token(
+            &database,
+            approved.id,
+            Utc::now() + Duration::hours(1),
+        ))
+        .unwrap();
+
+    system
+        .block_on(update(
+            &database,
+            approved.id,
+            UserUpdate {
+                approved: Some(false),
+                ..UserUpdate::from(approved.clone())
+            },
+        ))
+        .unwrap();
+
+    assert!(system
+        .block_on(refresh_token(
+            &database,
+            token,
+            Utc::now() + Duration::hours(1)
+        ))
+        .unwrap()
+        .is_none());
+    assert!(system
+        .block_on(authenticate(
+            &database,
+            Claims {
+                email: approved.email.clone(),
+                iss: "localhost".to_string(),
+                aud: None,
+                sub: approved.id.to_string(),
+                jti: Uuid::new_v4(),
+                iat: Utc::now().timestamp(),
+                exp: (Utc::now() + Duration::hours(1)).timestamp(),
+            }
+        ))
+        .is_err());
+
+    let kept = system
+        .block_on(update(
+            &database,
+            approved.id,
+            UserUpdate {
+                verified: None,
+                approved: None,
+                ..UserUpdate::from(approved)
+            },
+        ))
+        .unwrap();
+
+    assert!(kept.verified);
+    assert!(!kept.approved);
+
+    system.block_on(uninstall(&database)).unwrap();
+}
+
+#[test]
+fn test_login_lockout() {
+    let mut system = System::new("test");
+    let database = Database::from_config(DatabaseConfig::default()).unwrap();
+    let config = LockoutConfig {
+        attempts: 3,
+        address_attempts: 5,
+        ..LockoutConfig::default()
+    };
+    let user = User {
+        email: "user1@domain.test".to_string(),
+        password: "password1".to_string(),
+        ..User::default()
+    };
+    let auth = |email: &str, password: &str| UserAuth {
+        email: email.to_string(),
+        password: password.to_string(),
+        code: None,
+    };
+    let address = Some("192.0.2.1".to_string());
+
+    system.block_on(install(&database)).unwrap();
+    system.block_on(create(&database, user.clone())).unwrap();
+
+    for _ in 0..2 {
+        match system
+            .block_on(login(
+                &database,
+                auth("user1@domain.test", "wrong"),
+                address.clone(),
+                config,
+            ))
+            .unwrap()
+        {
+            LoginAttempt::Failure(None) => {}
+            attempt => panic!("Unexpected login attempt: {:?}", attempt),
+        }
+    }
+
+    match system
+        .block_on(login(
+            &database,
+            auth("user1@domain.test", "wrong"),
+            address.clone(),
+            config,
+        ))
+        .unwrap()
+    {
+        LoginAttempt::Failure(Some(seconds)) => assert!(seconds > 0 && seconds <= 60),
+        attempt => panic!("Unexpected login attempt: {:?}", attempt),
+    }
+
+    match system
+        .block_on(login(
+            &database,
+            auth("user1@domain.test", "password1"),
+            None,
+            config,
+        ))
+        .unwrap()
+    {
+        LoginAttempt::Locked(_) => {}
+        attempt => panic!("Unexpected login attempt: {:?}", attempt),
+    }
+
+    assert!(system
+        .block_on(lockout(&database, user.email.clone(), config))
+        .unwrap()
+        .is_some());
+
+    system
+        .block_on(unlock(&database, user.email.clone()))
+        .unwrap();
+
+    assert!(system
+        .block_on(lockout(&database, user.email.clone(), config))
+        .unwrap()
+        .is_none());
+
+    match system
+        .block_on(login(
+            &database,
+            auth("user1@domain.test", "password1"),
+            address.clone(),
+            config,
+        ))
+        .unwrap()
+    {
+        LoginAttempt::Success(found) => assert_eq!(found.id, user.id),
+        attempt => panic!("Unexpected login attempt: {:?}", attempt),
+    }
+
+    for i in 0..5 {
+        system
+            .block_on(login(
+                &database,
+                auth(&format!("unknown{}@domain.test", i), "wrong"),
+                address.clone(),
+                config,
+            ))
+            .unwrap();
+    }
+
+    match system
+        .block_on(login(
+            &database,
+            auth("user1@domain.test", "password1"),
+            address.clone(),
+            config,
+        ))
+        .unwrap()
+    {
+        LoginAttempt::Locked(_) => {}
+        attempt => panic!("Unexpected login attempt: {:?}", attempt),
+    }
+
+    system.block_on(uninstall(&database)).unwrap();
+}
+
+#[test]
+fn test_challenge_lockout() {
+    let mut system = System::new("test");
+    let database = Database::from_config(DatabaseConfig::default()).unwrap();
+    let config = LockoutConfig {
+        attempts: 3,
+        ..LockoutConfig::default()
+    };
+    let user = User {
+        email: "user1@domain.test".to_string(),
+        password: "password1".to_string(),
+        ..User::default()
+    };
+    let address = Some("192.0.2.1".to_string());
+
+    system.block_on(install(&database)).unwrap();
+    system.block_on(create(&database, user.clone())).unwrap();
+
+    for expected in &[false, false, true] {
+        let challenge = system
+            .block_on(issue_challenge(
+                &database,
+                user.id,
+                Utc::now() + Duration::minutes(5),
+            ))
+            .unwrap();
+
+        match system
+            .block_on(complete_challenge(
+                &database,
+                challenge,
+                "000000".to_string(),
+                address.clone(),
+                config,
+            ))
+            .unwrap()
+        {
+            ChallengeAttempt::Failure(found, locked) => {
+                assert_eq!(found.id, user.id);
+                assert_eq!(locked.is_some(), *expected);
+            }
+            attempt => panic!("Unexpected challenge attempt: {:?}", attempt),
+        }
+    }
+
+    let challenge = system
+        .block_on(issue_challenge(
+            &database,
+            user.id,
+            Utc::now() + Duration::minutes(5),
+        ))
+        .unwrap();
+
+    match system
+        .block_on(complete_enrollment(
+            &database,
+            challenge,
+            "000000".to_string(),
+            address.clone(),
+            config,
+        ))
+        .unwrap()
+    {
+        ChallengeAttempt::Locked(_) => {}
+        attempt => panic!("Unexpected challenge attempt: {:?}", attempt),
+    }
+
+    system.block_on(uninstall(&database)).unwrap();
+}
+
+#[test]
+fn test_two_factor() {
+    let mut system = System::new("test");
+    let database = Database::from_config(DatabaseConfig::default()).unwrap();
+    let config = LockoutConfig::default();
+    let user = User {
+        email: "user1@domain.test".to_string(),
+        password: "password1".to_string(),
+        ..User::default()
+    };
+    let auth = |code: Option<&str>| UserAuth {
+        email: "user1@domain.test".to_string(),
+        password: "password1".to_string(),
+        code: code.map(str::to_string),
+    };
+
+    system.block_on(install(&database)).unwrap();
+    system.block_on(create(&database, user.clone())).unwrap();
+
+    let pending = system
+        .block_on(enroll_two_factor(&database, user.id))
+        .unwrap()
+        .unwrap();
+    let secret = system
+        .block_on(enroll_two_factor(&database, user.id))
+        .unwrap()
+        .unwrap();
+
+    assert_ne!(pending, secret);
+
+    let code = totp::code(&secret, totp::step(Utc::now().timestamp())).unwrap();
+
+    assert!(system
+        .block_on(enable_two_factor(&database, user.id, "000000".to_string()))
+        .unwrap()
+        .is_none());
+
+    let codes = system
+        .block_on(enable_two_factor(&database, user.id, code.clone()))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(codes.len(), 10);
+    assert!(system
+        .block_on(enroll_two_factor(&database, user.id))
+        .unwrap()
+        .is_none());
+
+    match system
+        .block_on(login(&database, auth(None), None, config))
+        .unwrap()
+    {
+        LoginAttempt::TwoFactor(found) => assert_eq!(found.id, user.id),
+        attempt => panic!("Unexpected login attempt: {:?}", attempt),
+    }
+
+    match system
+        .block_on(login(&database, auth(Some(&code)), None, config))
+        .unwrap()
+    {
+        LoginAttempt::Failure(_) => {}
+        attempt => panic!("Unexpected login attempt: {:?}", attempt),
+    }
+
+    for expected in &[true, false] {
+        match system
+            .block_on(login(&database, auth(Some(&codes[0])), None, config))
+            .unwrap()
+        {
+            LoginAttempt::Success(found) if *expected => assert_eq!(found.id, user.id),
+            LoginAttempt::Failure(_) if !*expected => {}
+            attempt => panic!("Unexpected login attempt: {:?}", attempt),
+        }
+    }
+
+    let challenge = system
+        .block_on(issue_challenge(
+            &database,
+            user.id,
+            Utc::now() + Duration::minutes(5),
+        ))
+        .unwrap();
+
+    match system
+        .block_on(complete_challenge(
+            &database,
+            challenge.clone(),
+            "000000".to_string(),
+            None,
+            config,
+        ))
+        .unwrap()
+    {
+        ChallengeAttempt::Failure(found, None) => assert_eq!(found.id, user.id),
+        attempt => panic!("Unexpected challenge attempt: {:?}", attempt),
+    }
+
+    match system
+        .block_on(complete_challenge(
+            &database,
+            challenge.clone(),
+            codes[1].clone(),
+            None,
+            config,
+        ))
+        .unwrap()
+    {
+        ChallengeAttempt::Success(found) => assert_eq!(found.id, user.id),
+        attempt => panic!("Unexpected challenge attempt: {:?}", attempt),
+    }
+
+    match system
+        .block_on(complete_challenge(
+            &database,
+            challenge,
+            codes[2].clone(),
+            None,
+            config,
+        ))
+        .unwrap()
+    {
+        ChallengeAttempt::Expired => {}
+        attempt => panic!("Unexpected challenge attempt: {:?}", attempt),
+    }
+
+    let status = system
+        .block_on(two_factor_status(&database, user.id))
+        .unwrap();
+
+    assert!(status.enabled);
+    assert!(!status.required);
+    assert_eq!(status.recovery_codes, 8);
+
+    let role = system
+        .block_on(update_role(
+            &database,
+            "editor".to_string(),
+            RoleUpdate { two_factor: true },
+        ))
+        .unwrap()
+        .unwrap();
+
+    assert!(role.two_factor);
+    assert!(system
+        .block_on(update_role(
+            &database,
+            "unknown".to_string(),
+            RoleUpdate { two_factor: true },
+        ))
+        .unwrap()
+        .is_none());
+
+    system
+        .block_on(assign_roles(&database, user.id, vec!["editor".to_string()]))
+        .unwrap();
+
+    assert!(system
+        .block_on(permissions(&database, user.id))
+        .unwrap()
+        .contains(&Permission::EditAnyPage));
+
+    assert!(system
+        .block_on(disable_two_factor(&database, user.id, codes[2].clone()))
+        .unwrap());
+    assert!(system
+        .block_on(permissions(&database, user.id))
+        .unwrap()
+        .is_empty());
+    assert!(
+        system
+            .block_on(two_factor_status(&database, user.id))
+            .unwrap()
+            .required
+    );
+
+    match system
+        .block_on(login(&database, auth(None), None, config))
+        .unwrap()
+    {
+        LoginAttempt::Enroll(found) => assert_eq!(found.id, user.id),
+        attempt => panic!("Unexpected login attempt: {:?}", attempt),
+    }
+
+    let challenge = system
+        .block_on(issue_challenge(
+            &database,
+            user.id,
+            Utc::now() + Duration::minutes(5),
+        ))
+        .unwrap();
+    let secret = system
+        .block_on(enroll_two_factor(&database, user.id))
+        .unwrap()
+        .unwrap();
+
+    match system
+        .block_on(complete_enrollment(
+            &database,
+            challenge.clone(),
+            "000000".to_string(),
+            None,
+            config,
+        ))
+        .unwrap()
+    {
+        ChallengeAttempt::Failure(found, None) => assert_eq!(found.id, user.id),
+        attempt => panic!("Unexpected enrollment: {:?}", attempt),
+    }
+
+    let code = totp::code(&secret, totp::step(Utc::now().timestamp())).unwrap();
+
+    match system
+        .block_on(complete_enrollment(
+            &database,
+            challenge.clone(),
+            code.clone(),
+            None,
+            config,
+        ))
+        .unwrap()
+    {
+        ChallengeAttempt::Enrolled(found, codes) => {
+            assert_eq!(found.id, user.id);
+            assert_eq!(codes.len(), 10);
+        }
+        attempt => panic!("Unexpected enrollment: {:?}", attempt),
+    }
+
+    match system
+        .block_on(complete_enrollment(
+            &database, challenge, code, None, config,
+        ))
+        .unwrap()
+    {
+        ChallengeAttempt::Expired => {}
+        attempt => panic!("Unexpected enrollment: {:?}", attempt),
+    }
+
+    assert!(system
+        .block_on(permissions(&database, user.id))
+        .unwrap()
+        .contains(&Permission::EditAnyPage));
+
+    system.block_on(uninstall(&database)).unwrap();
+}
+
+#[test]
+fn test_api_keys() {
+    let mut system = System::new("test");
+    let database = Database::from_config(DatabaseConfig::default()).unwrap();
+    let user = User {
+        email: "user1@domain.test".to_string(),
+        password: "password1".to_string(),
+        ..User::default()
+    };
+
+    system.block_on(install(&database)).unwrap();
+    system.block_on(create(&database, user.clone())).unwrap();
+
+    let key = system
+        .block_on(create_api_key(
+            &database,
+            user.id,
+            "ci".to_string(),
+            Some(vec![Permission::CreatePage]),
+            Some(Utc::now() + Duration::days(30)),
+        ))
+        .unwrap();
+    let expired = system
+        .block_on(create_api_key(
+            &database,
+            user.id,
+            "old".to_string(),
+            None,
+            Some(Utc::now() - Duration::days(1)),
+        ))
+        .unwrap();
+
+    let (found, scopes) = system
+        .block_on(authenticate_key(&database, key.clone()))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(found.id, user.id);
+    assert_eq!(scopes, Some(vec![Permission::CreatePage]));
+    assert!(system
+        .block_on(authenticate_key(&database, expired))
+        .unwrap()
+        .is_none());
+    assert!(system
+        .block_on(authenticate_key(
+            &database,
+            format!("{}.wrong", key.split('.').next().unwrap())
+        ))
+        .unwrap()
+        .is_none());
+    assert!(system
+        .block_on(authenticate_key(&database, "invalid".to_string()))
+        .unwrap()
+        .is_none());
+
+    let keys = system.block_on(list_api_keys(&database, user.id)).unwrap();
+
+    assert_eq!(keys.len(), 2);
+
+    let listed = keys.iter().find(|listed| listed.name == "ci").unwrap();
+
+    assert!(listed.last_used.is_some());
+    assert!(key.starts_with(&listed.id.to_string()));
+    assert!(keys
+        .iter()
+        .find(|listed| listed.name == "old")
+        .unwrap()
+        .last_used
+        .is_none());
+
+    assert!(!system
+        .block_on(revoke_api_key(&database, Uuid::new_v4(), listed.id))
+        .unwrap());
+    assert!(system
+        .block_on(revoke_api_key(&database, user.id, listed.id))
+        .unwrap());
+    assert!(system
+        .block_on(authenticate_key(&database, key))
+        .unwrap()
+        .is_none());
+
+    system.block_on(uninstall(&database)).unwrap();
+}
+
+#[test]
+fn test_user_email_unique() {
+    let mut system = System::new("test");
+    let database = Database::from_config(DatabaseConfig::default()).unwrap();
+    let user = User {
+        email: "user1@domain.test".to_string(),
+        password: "password1".to_string(),
+        ..User::default()
+    };
+    let user2 = User {
+        email: "user2@domain.test".to_string(),
+        password: "password2".to_string(),
+        ..User::default()
+    };
+
+    system.block_on(install(&database)).unwrap();
+    system.block_on(create(&database, user.clone())).unwrap();
+    system.block_on(create(&database, user2.clone())).unwrap();
+
+    let err = system
+        .block_on(create(
+            &database,
+            User {
+                email: "USER1@domain.test".to_string(),
+                password: "password3".to_string(),
+                ..User::default()
+            },
+        ))
+        .unwrap_err();
+
+    assert_eq!(
+        err.downcast::<EmailError>().unwrap(),
+        EmailError::Duplicate("USER1@domain.test".to_string())
+    );
+
+    let err = system
+        .block_on(update(
+            &database,
+            user2.id,
+            UserUpdate {
+                email: "User1@Domain.test".to_string(),
+                ..UserUpdate::from(user2.clone())
+            },
+        ))
+        .unwrap_err();
+
+    assert_eq!(err.downcast::<EmailError>().unwrap().field(), "email");
+    assert_eq!(
+        system
+            .block_on(locate(&database, "USER1@DOMAIN.TEST"))
+            .unwrap()
+            .id,
+        user.id
+    );
+
+    let updated = system
+        .block_on(update(
+            &database,
+            user.id,
+            UserUpdate {
+                email: "User1@domain.test".to_string(),
+                display_name: Some("User One".to_string()),
+                bio: Some("Writes pages.".to_string()),
+                ..UserUpdate::from(user.clone())
+            },
+        ))
+        .unwrap();
+
+    assert_eq!(updated.email, "User1@domain.test");
+
+    let retrieved = system.block_on(retrieve(&database, user.id)).unwrap();
+
+    assert_eq!(retrieved.display_name, Some("User One".to_string()));
+    assert_eq!(retrieved.avatar, None);
+    assert_eq!(retrieved.bio, Some("Writes pages.".to_string()));
+
+    system.block_on(uninstall(&database)).unwrap();
+}
+
+#[test]
+fn test_sessions() {
+    let mut system = System::new("test");
+    let database = Database::from_config(DatabaseConfig::default()).unwrap();
+    let user = User {
+        email: "user1@domain.test".to_string(),
+        password: "password1".to_string(),
+        ..User::default()
+    };
+    let other = User {
+        email: "user2@domain.test".to_string(),
+        password: "password2".to_string(),
+        ..User::default()
+    };
+    let first = Uuid::new_v4();
+    let second = Uuid::new_v4();
+    let third = Uuid::new_v4();
+
+    system.block_on(install(&database)).unwrap();
+    system.block_on(create(&database, user.clone())).unwrap();
+    system.block_on(create(&database, other.clone())).unwrap();
+
+    for session in &[first, second, third] {
+        system
+            .block_on(create_session(
+                &database,
+                *session,
+                user.id,
+                Some("Mozilla/5.0".to_string()),
+                Some("127.0.0.1".to_string()),
+            ))
+            .unwrap();
+    }
+
+    assert_eq!(
+        system.block_on(touch_session(&database, first)).unwrap(),
+        Some(user.id)
+    );
+    assert_eq!(
+        system
+            .block_on(touch_session(&database, Uuid::new_v4()))
+            .unwrap(),
+        None
+    );
+
+    let sessions = system.block_on(list_sessions(&database, user.id)).unwrap();
+
+    assert_eq!(sessions.len(), 3);
+    assert_eq!(sessions[0].user_agent, Some("Mozilla/5.0".to_string()));
+    assert_eq!(sessions[0].address, Some("127.0.0.1".to_string()));
+    assert!(!system
+        .block_on(revoke_session(&database, other.id, first))
+        .unwrap());
+    assert!(system
+        .block_on(revoke_session(&database, user.id, first))
+        .unwrap());
+    assert_eq!(
+        system.block_on(touch_session(&database, first)).unwrap(),
+        None
+    );
+    assert_eq!(
+        system
+            .block_on(revoke_sessions(&database, user.id, Some(second)))
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        system.block_on(touch_session(&database, third)).unwrap(),
+        None
+    );
+    assert_eq!(
+        system.block_on(touch_session(&database, second)).unwrap(),
+        Some(user.id)
+    );
+
+    system.block_on(delete_session(&database, second)).unwrap();
+
+    assert!(system
+        .block_on(list_sessions(&database, user.id))
+        .unwrap()
+        .is_empty());
+
+    system.block_on(uninstall(&database)).unwrap();
+}
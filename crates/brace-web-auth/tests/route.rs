@@ -5,27 +5,50 @@ use actix_web::http::header::{self, HeaderValue};
 use actix_web::http::{Method, StatusCode};
 use actix_web::App;
 use brace_db::{Database, DatabaseConfig};
+use brace_web_auth::action::assign_roles::assign_roles;
 use brace_web_auth::action::create::create;
+use brace_web_auth::action::create_api_key::create_api_key;
 use brace_web_auth::action::install::install;
 use brace_web_auth::action::uninstall::uninstall;
-use brace_web_auth::model::User;
+use brace_web_auth::action::unlock::unlock;
+use brace_web_auth::config::{AuthConfig, Jwt};
+use brace_web_auth::model::{NewUser, Permission, User, UserUpdate};
 use chrono::Utc;
 use futures::future::Future;
 use serde_json::{json, Value};
 use uuid::Uuid;
 
+fn assert_public(body: &[u8]) {
+    let body = String::from_utf8_lossy(body);
+
+    assert!(!body.contains("password"));
+    assert!(!body.contains("$argon2"));
+}
+
 #[test]
 fn test_user_route_lifecycle() {
     let mut system = System::new("test");
     let database = Database::from_config(DatabaseConfig::default()).unwrap();
     let uuid = Uuid::new_v4();
     let path = format!("/api/users/{}", uuid);
-    let user = User {
+    let user = NewUser {
         id: uuid,
         email: "user1@domain.test".to_string(),
         password: "password1".to_string(),
-        created: Utc::now(),
-        updated: Utc::now(),
+        verified: true,
+        approved: true,
+        display_name: None,
+        avatar: None,
+        bio: None,
+    };
+    let update = UserUpdate {
+        email: "user2@domain.test".to_string(),
+        password: Some("password2".to_string()),
+        verified: Some(true),
+        approved: Some(true),
+        display_name: None,
+        avatar: None,
+        bio: None,
     };
 
     system.block_on(install(&database)).unwrap();
@@ -34,6 +57,8 @@ fn test_user_route_lifecycle() {
         HttpService::new(
             App::new()
                 .data(Database::from_config(DatabaseConfig::default()).unwrap())
+                .data(Jwt::from_config(&AuthConfig::generate()).unwrap())
+                .data(AuthConfig::default())
                 .configure(brace_web_auth::route::api::config),
         )
     });
@@ -72,7 +97,7 @@ fn test_user_route_lifecycle() {
 
     assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
 
-    let req = srv.request(Method::PUT, srv.url(&path)).send_json(&user);
+    let req = srv.request(Method::PUT, srv.url(&path)).send_json(&update);
     let res = srv.block_on(req).unwrap();
 
     assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
@@ -88,9 +113,15 @@ fn test_user_route_lifecycle() {
         password: "password".to_string(),
         created: Utc::now(),
         updated: Utc::now(),
+        verified: true,
+        approved: true,
+        display_name: None,
+        avatar: None,
+        bio: None,
     };
+    let admin_path = format!("/api/users/{}/roles", admin.id);
 
-    system.block_on(create(&database, admin)).unwrap();
+    system.block_on(create(&database, admin.clone())).unwrap();
 
     let auth = json!({
         "email": "admin@domain.test",
@@ -108,51 +139,124 @@ fn test_user_route_lifecycle() {
     let token = json.get("token").unwrap();
     let header = format!("Bearer {}", token.as_str().unwrap());
 
+    let req = srv
+        .request(Method::GET, srv.url("/api/users/"))
+        .header("Authorization", header.clone())
+        .send();
+    let res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+    system
+        .block_on(assign_roles(
+            &database,
+            admin.id,
+            vec!["administrator".to_string()],
+        ))
+        .unwrap();
+
+    let req = srv
+        .request(Method::GET, srv.url("/api/roles/"))
+        .header("Authorization", header.clone())
+        .send();
+    let mut res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let json = res.json::<Value>().wait().unwrap();
+
+    assert_eq!(json["value"].as_array().unwrap().len(), 3);
+
+    let req = srv
+        .request(Method::PUT, srv.url(&admin_path))
+        .header("Authorization", header.clone())
+        .send_json(&json!(["administrator", "unknown"]));
+    let res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let req = srv
+        .request(Method::GET, srv.url(&admin_path))
+        .header("Authorization", header.clone())
+        .send();
+    let mut res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let json = res.json::<Value>().wait().unwrap();
+
+    assert_eq!(json["value"][0]["name"], "administrator");
+
     let uuid = Uuid::new_v4();
     let path = format!("/api/users/{}", uuid);
-    let user = User {
+    let user = NewUser {
         id: uuid,
         email: "user1@domain.test".to_string(),
         password: "password1".to_string(),
-        created: Utc::now(),
-        updated: Utc::now(),
+        verified: true,
+        approved: true,
+        display_name: None,
+        avatar: None,
+        bio: None,
     };
 
     let req = srv
         .request(Method::GET, srv.url("/api/users/"))
         .header("Authorization", header.clone())
         .send();
-    let res = srv.block_on(req).unwrap();
+    let mut res = srv.block_on(req).unwrap();
 
     assert_eq!(res.status(), StatusCode::OK);
+    assert_public(&res.body().wait().unwrap());
 
     let req = srv
         .request(Method::POST, srv.url("/api/users/"))
         .header("Authorization", header.clone())
         .send_json(&user);
-    let res = srv.block_on(req).unwrap();
+    let mut res = srv.block_on(req).unwrap();
 
     assert_eq!(res.status(), StatusCode::CREATED);
     assert_eq!(
         res.headers().get(header::LOCATION),
         Some(&HeaderValue::from_str(&path).unwrap())
     );
+    assert_public(&res.body().wait().unwrap());
+
+    let duplicate = NewUser {
+        email: "USER1@domain.test".to_string(),
+        password: "password1".to_string(),
+        ..NewUser::default()
+    };
+
+    let req = srv
+        .request(Method::POST, srv.url("/api/users/"))
+        .header("Authorization", header.clone())
+        .send_json(&duplicate);
+    let mut res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let json = res.json::<Value>().wait().unwrap();
+
+    assert_eq!(json["error"]["field"], "email");
 
     let req = srv
         .request(Method::GET, srv.url(&path))
         .header("Authorization", header.clone())
         .send();
-    let res = srv.block_on(req).unwrap();
+    let mut res = srv.block_on(req).unwrap();
 
     assert_eq!(res.status(), StatusCode::OK);
+    assert_public(&res.body().wait().unwrap());
 
     let req = srv
         .request(Method::PUT, srv.url(&path))
         .header("Authorization", header.clone())
-        .send_json(&user);
-    let res = srv.block_on(req).unwrap();
+        .send_json(&update);
+    let mut res = srv.block_on(req).unwrap();
 
     assert_eq!(res.status(), StatusCode::OK);
+    assert_public(&res.body().wait().unwrap());
 
     let req = srv
         .request(Method::DELETE, srv.url(&path))
@@ -164,3 +268,261 @@ fn test_user_route_lifecycle() {
 
     system.block_on(uninstall(&database)).unwrap();
 }
+
+#[test]
+fn test_auth_route_tokens() {
+    let mut system = System::new("test");
+    let database = Database::from_config(DatabaseConfig::default()).unwrap();
+
+    system.block_on(install(&database)).unwrap();
+
+    let mut srv = TestServer::new(|| {
+        HttpService::new(
+            App::new()
+                .data(Database::from_config(DatabaseConfig::default()).unwrap())
+                .data(Jwt::from_config(&AuthConfig::generate()).unwrap())
+                .data(AuthConfig::default())
+                .configure(brace_web_auth::route::api::config),
+        )
+    });
+
+    let admin = User {
+        id: Uuid::new_v4(),
+        email: "admin@domain.test".to_string(),
+        password: "password".to_string(),
+        created: Utc::now(),
+        updated: Utc::now(),
+        verified: true,
+        approved: true,
+        display_name: None,
+        avatar: None,
+        bio: None,
+    };
+
+    system.block_on(create(&database, admin.clone())).unwrap();
+    system
+        .block_on(assign_roles(
+            &database,
+            admin.id,
+            vec!["administrator".to_string()],
+        ))
+        .unwrap();
+
+    let auth = json!({
+        "email": "admin@domain.test",
+        "password": "password",
+    });
+
+    let req = srv
+        .request(Method::POST, srv.url("/api/auth"))
+        .send_json(&auth);
+    let mut res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let json = res.json::<Value>().wait().unwrap();
+    let refresh = json!({
+        "refresh_token": json.get("refresh_token").unwrap(),
+    });
+
+    assert_eq!(json.get("token_type").unwrap(), "Bearer");
+
+    let req = srv
+        .request(Method::POST, srv.url("/api/auth/refresh"))
+        .send_json(&refresh);
+    let mut res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let json = res.json::<Value>().wait().unwrap();
+    let token = json.get("token").unwrap();
+    let header = format!("Bearer {}", token.as_str().unwrap());
+
+    let req = srv
+        .request(Method::GET, srv.url("/api/users/"))
+        .header("Authorization", header.clone())
+        .send();
+    let res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let req = srv
+        .request(Method::POST, srv.url("/api/auth/refresh"))
+        .send_json(&refresh);
+    let res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let refresh = json!({
+        "refresh_token": json.get("refresh_token").unwrap(),
+    });
+
+    let req = srv
+        .request(Method::POST, srv.url("/api/auth/refresh"))
+        .send_json(&refresh);
+    let res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let req = srv
+        .request(Method::POST, srv.url("/api/auth/revoke"))
+        .header("Authorization", header.clone())
+        .send_json(&json!({}));
+    let res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+    let req = srv
+        .request(Method::GET, srv.url("/api/users/"))
+        .header("Authorization", header.clone())
+        .send();
+    let res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    system.block_on(uninstall(&database)).unwrap();
+}
+
+#[test]
+fn test_auth_route_lockout() {
+    let mut system = System::new("test");
+    let database = Database::from_config(DatabaseConfig::default()).unwrap();
+
+    system.block_on(install(&database)).unwrap();
+
+    let mut srv = TestServer::new(|| {
+        HttpService::new(
+            App::new()
+                .data(Database::from_config(DatabaseConfig::default()).unwrap())
+                .data(Jwt::from_config(&AuthConfig::generate()).unwrap())
+                .data(AuthConfig::default())
+                .configure(brace_web_auth::route::api::config),
+        )
+    });
+
+    let user = User {
+        email: "user1@domain.test".to_string(),
+        password: "password1".to_string(),
+        ..User::default()
+    };
+
+    system.block_on(create(&database, user)).unwrap();
+
+    let auth = json!({
+        "email": "user1@domain.test",
+        "password": "wrong",
+    });
+
+    for _ in 0..5 {
+        let req = srv
+            .request(Method::POST, srv.url("/api/auth"))
+            .send_json(&auth);
+        let res = srv.block_on(req).unwrap();
+
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    let auth = json!({
+        "email": "user1@domain.test",
+        "password": "password1",
+    });
+
+    let req = srv
+        .request(Method::POST, srv.url("/api/auth"))
+        .send_json(&auth);
+    let res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(res.headers().get(header::RETRY_AFTER).is_some());
+
+    system
+        .block_on(unlock(&database, "user1@domain.test".to_string()))
+        .unwrap();
+
+    let req = srv
+        .request(Method::POST, srv.url("/api/auth"))
+        .send_json(&auth);
+    let res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    system.block_on(uninstall(&database)).unwrap();
+}
+
+#[test]
+fn test_auth_route_api_keys() {
+    let mut system = System::new("test");
+    let database = Database::from_config(DatabaseConfig::default()).unwrap();
+
+    system.block_on(install(&database)).unwrap();
+
+    let mut srv = TestServer::new(|| {
+        HttpService::new(
+            App::new()
+                .data(Database::from_config(DatabaseConfig::default()).unwrap())
+                .data(Jwt::from_config(&AuthConfig::generate()).unwrap())
+                .data(AuthConfig::default())
+                .configure(brace_web_auth::route::api::config),
+        )
+    });
+
+    let admin = User {
+        email: "admin@domain.test".to_string(),
+        password: "password".to_string(),
+        ..User::default()
+    };
+
+    system.block_on(create(&database, admin.clone())).unwrap();
+    system
+        .block_on(assign_roles(
+            &database,
+            admin.id,
+            vec!["administrator".to_string()],
+        ))
+        .unwrap();
+
+    let key = system
+        .block_on(create_api_key(
+            &database,
+            admin.id,
+            "admin".to_string(),
+            None,
+            None,
+        ))
+        .unwrap();
+    let scoped = system
+        .block_on(create_api_key(
+            &database,
+            admin.id,
+            "publish".to_string(),
+            Some(vec![Permission::CreatePage]),
+            None,
+        ))
+        .unwrap();
+
+    let req = srv
+        .request(Method::GET, srv.url("/api/users/"))
+        .header("Authorization", format!("Bearer {}", key))
+        .send();
+    let res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let req = srv
+        .request(Method::GET, srv.url("/api/users/"))
+        .header("Authorization", format!("Bearer {}", scoped))
+        .send();
+    let res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+    let req = srv
+        .request(Method::GET, srv.url("/api/users/"))
+        .header("Authorization", format!("Bearer {}x", key))
+        .send();
+    let res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    system.block_on(uninstall(&database)).unwrap();
+}
@@ -0,0 +1,373 @@
+use std::iter::repeat;
+use std::ops::Deref;
+
+use actix_identity::RequestIdentity;
+use actix_service::{Service, Transform};
+use actix_web::cookie::{Cookie, SameSite};
+use actix_web::dev::{Payload, ServiceRequest, ServiceResponse};
+use actix_web::error::{Error, ErrorBadRequest, ErrorInternalServerError};
+use actix_web::http::header::AUTHORIZATION;
+use actix_web::http::StatusCode;
+use actix_web::web::Bytes;
+use actix_web::{FromRequest, HttpMessage, HttpRequest, HttpResponse};
+use futures::future::{err, ok, Future, FutureResult, IntoFuture};
+use futures::Poll;
+use hmac::{Hmac, Mac};
+use rand::distributions::Alphanumeric;
+use rand::{thread_rng, Rng};
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+pub const FIELD: &str = "csrf_token";
+
+pub const REJECTED_MESSAGE: &str = "The form could not be verified, please submit it again";
+
+#[derive(Clone)]
+pub struct CsrfProtection {
+    key: Vec<u8>,
+    name: String,
+    path: String,
+    secure: bool,
+}
+
+impl CsrfProtection {
+    pub fn new(key: &[u8]) -> Self {
+        Self {
+            key: derive(key),
+            name: "csrf".to_owned(),
+            path: "/".to_owned(),
+            secure: false,
+        }
+    }
+
+    pub fn name<T>(mut self, name: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.name = name.into();
+        self
+    }
+
+    pub fn path<T>(mut self, path: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.path = path.into();
+        self
+    }
+
+    pub fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    fn cookie(&self, secret: String) -> Cookie<'static> {
+        Cookie::build(self.name.clone(), secret)
+            .path(self.path.clone())
+            .http_only(true)
+            .same_site(SameSite::Lax)
+            .secure(self.secure)
+            .finish()
+    }
+}
+
+impl<S, B> Transform<S> for CsrfProtection
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = CsrfMiddleware<S>;
+    type Future = FutureResult<Self::Transform, Self::InitError>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(CsrfMiddleware {
+            service,
+            config: self.clone(),
+        })
+    }
+}
+
+pub struct CsrfMiddleware<S> {
+    service: S,
+    config: CsrfProtection,
+}
+
+impl<S, B> Service for CsrfMiddleware<S>
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = Box<dyn Future<Item = Self::Response, Error = Self::Error>>;
+
+    fn poll_ready(&mut self) -> Poll<(), Self::Error> {
+        self.service.poll_ready()
+    }
+
+    fn call(&mut self, req: ServiceRequest) -> Self::Future {
+        if req.headers().contains_key(AUTHORIZATION) {
+            return Box::new(self.service.call(req));
+        }
+
+        let identity = req.get_identity();
+        let (current, issued) = match req.cookie(&self.config.name) {
+            Some(ref cookie) if is_secret(cookie.value()) => (cookie.value().to_owned(), false),
+            _ => (secret(), true),
+        };
+
+        req.extensions_mut().insert(CsrfToken(sign(
+            &self.config.key,
+            &current,
+            identity.as_ref().map(String::as_str),
+        )));
+
+        let config = self.config.clone();
+
+        Box::new(self.service.call(req).map(move |mut res| {
+            // Logging in or out starts over with a new secret.
+            let cookie = if res.request().get_identity() != identity {
+                Some(config.cookie(secret()))
+            } else if issued {
+                Some(config.cookie(current))
+            } else {
+                None
+            };
+
+            if let Some(cookie) = cookie {
+                let _ = res.response_mut().add_cookie(&cookie);
+            }
+
+            res
+        }))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(transparent)]
+pub struct CsrfToken(pub(crate) String);
+
+impl CsrfToken {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn verify(&self, token: &str) -> bool {
+        self.0.len() == token.len()
+            && self
+                .0
+                .bytes()
+                .zip(token.bytes())
+                .fold(0, |acc, (a, b)| acc | (a ^ b))
+                == 0
+    }
+}
+
+impl Into<String> for CsrfToken {
+    fn into(self) -> String {
+        self.0
+    }
+}
+
+impl FromRequest for CsrfToken {
+    type Error = Error;
+    type Future = Result<Self, Self::Error>;
+    type Config = ();
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        req.extensions()
+            .get::<CsrfToken>()
+            .cloned()
+            .ok_or_else(|| ErrorInternalServerError("CSRF protection is not enabled"))
+    }
+}
+
+pub struct CsrfForm<T> {
+    data: T,
+    token: CsrfToken,
+    valid: bool,
+}
+
+pub type Csrf = CsrfForm<IgnoredAny>;
+
+impl<T> CsrfForm<T> {
+    pub fn is_valid(&self) -> bool {
+        self.valid
+    }
+
+    pub fn token(&self) -> CsrfToken {
+        self.token.clone()
+    }
+
+    pub fn into_inner(self) -> T {
+        self.data
+    }
+}
+
+impl<T> Deref for CsrfForm<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data
+    }
+}
+
+impl<T> FromRequest for CsrfForm<T>
+where
+    T: DeserializeOwned + 'static,
+{
+    type Error = Error;
+    type Future = Box<dyn Future<Item = Self, Error = Self::Error>>;
+    type Config = ();
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let token = match CsrfToken::from_request(req, payload) {
+            Ok(token) => token,
+            Err(e) => return Box::new(err(e)),
+        };
+
+        if req.content_type().to_lowercase() != "application/x-www-form-urlencoded" {
+            return Box::new(err(ErrorBadRequest("Content type error")));
+        }
+
+        Box::new(Bytes::from_request(req, payload).into_future().and_then(
+            move |body| -> Result<Self, Error> {
+                let data = serde_urlencoded::from_bytes::<T>(&body).map_err(ErrorBadRequest)?;
+                let submitted =
+                    serde_urlencoded::from_bytes::<Submitted>(&body).map_err(ErrorBadRequest)?;
+                let valid = token.verify(&submitted.csrf_token);
+
+                Ok(CsrfForm { data, token, valid })
+            },
+        ))
+    }
+}
+
+#[derive(Deserialize)]
+struct Submitted {
+    #[serde(default)]
+    csrf_token: String,
+}
+
+pub fn forbidden(mut res: HttpResponse) -> HttpResponse {
+    *res.status_mut() = StatusCode::FORBIDDEN;
+    res
+}
+
+fn secret() -> String {
+    repeat(())
+        .map(|()| thread_rng().sample(Alphanumeric))
+        .take(32)
+        .collect()
+}
+
+fn is_secret(value: &str) -> bool {
+    value.len() == 32 && value.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+fn derive(key: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_varkey(key).expect("HMAC can take a key of any size");
+
+    mac.input(b"csrf");
+    mac.result().code().to_vec()
+}
+
+fn sign(key: &[u8], secret: &str, identity: Option<&str>) -> String {
+    let mut mac = Hmac::<Sha256>::new_varkey(key).expect("HMAC can take a key of any size");
+
+    mac.input(secret.as_bytes());
+
+    if let Some(identity) = identity {
+        mac.input(identity.as_bytes());
+    }
+
+    mac.result()
+        .code()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_identity::{CookieIdentityPolicy, Identity, IdentityService};
+    use actix_web::cookie::Cookie;
+    use actix_web::test::{call_service, init_service, TestRequest};
+    use actix_web::{web, App, HttpResponse};
+
+    use super::{derive, is_secret, secret, sign, CsrfProtection, CsrfToken};
+
+    #[test]
+    fn test_csrf_token_verify() {
+        let secret = secret();
+        let token = CsrfToken(sign(b"key", &secret, None));
+
+        assert!(is_secret(&secret));
+        assert!(token.verify(&sign(b"key", &secret, None)));
+        assert!(!token.verify(&sign(b"other", &secret, None)));
+        assert!(!token.verify(&sign(b"key", "forged", None)));
+        assert!(!token.verify(""));
+        assert_ne!(derive(b"key"), b"key".to_vec());
+    }
+
+    #[test]
+    fn test_csrf_token_identity() {
+        let secret = secret();
+        let token = CsrfToken(sign(b"key", &secret, Some("user")));
+
+        assert!(token.verify(&sign(b"key", &secret, Some("user"))));
+        assert!(!token.verify(&sign(b"key", &secret, Some("other"))));
+        assert!(!token.verify(&sign(b"key", &secret, None)));
+    }
+
+    #[test]
+    fn test_csrf_secret_rotation() {
+        let mut app = init_service(
+            App::new()
+                .wrap(CsrfProtection::new(b"key"))
+                .wrap(IdentityService::new(CookieIdentityPolicy::new(&[0; 32])))
+                .route("/", web::get().to(HttpResponse::Ok))
+                .route(
+                    "/login",
+                    web::post().to(|id: Identity| {
+                        id.remember("user".to_owned());
+                        HttpResponse::Ok()
+                    }),
+                ),
+        );
+        let secret = secret();
+
+        let req = TestRequest::get()
+            .uri("/")
+            .cookie(Cookie::new("csrf", secret.clone()))
+            .to_request();
+        let res = call_service(&mut app, req);
+
+        assert!(res
+            .response()
+            .cookies()
+            .all(|cookie| cookie.name() != "csrf"));
+
+        let req = TestRequest::post()
+            .uri("/login")
+            .cookie(Cookie::new("csrf", secret.clone()))
+            .to_request();
+        let res = call_service(&mut app, req);
+        let cookie = res
+            .response()
+            .cookies()
+            .find(|cookie| cookie.name() == "csrf")
+            .unwrap();
+
+        assert!(is_secret(cookie.value()));
+        assert_ne!(cookie.value(), secret);
+    }
+}
@@ -36,6 +36,20 @@ impl FormData {
             _ => Err(format_err!("form data does not contain key {}", key)),
         }
     }
+
+    pub fn value(&self, key: &str) -> Option<&Value> {
+        match &self.0 {
+            Value::Object(map) => map.get(key),
+            _ => None,
+        }
+    }
+
+    pub fn parse<T>(&self) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        from_value(self.0.clone()).map_err(Error::from)
+    }
 }
 
 impl Default for FormData {
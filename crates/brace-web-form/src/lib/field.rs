@@ -64,6 +64,32 @@ pub enum Field {
     Password(Password),
 }
 
+impl Field {
+    pub fn name(&self) -> &str {
+        match self {
+            Field::Text(field) => &field.name,
+            Field::Textarea(field) => &field.name,
+            Field::Hidden(field) => &field.name,
+            Field::Select(field) => &field.name,
+            Field::Datetime(field) => &field.name,
+            Field::Email(field) => &field.name,
+            Field::Password(field) => &field.name,
+        }
+    }
+
+    pub fn errors_mut(&mut self) -> Option<&mut Vec<String>> {
+        match self {
+            Field::Text(field) => Some(&mut field.errors),
+            Field::Textarea(field) => Some(&mut field.errors),
+            Field::Hidden(_) => None,
+            Field::Select(field) => Some(&mut field.errors),
+            Field::Datetime(field) => Some(&mut field.errors),
+            Field::Email(field) => Some(&mut field.errors),
+            Field::Password(field) => Some(&mut field.errors),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Text {
     pub name: String,
@@ -72,6 +98,8 @@ pub struct Text {
     pub description: Option<String>,
     pub placeholder: Option<String>,
     pub weight: i32,
+    #[serde(default)]
+    pub errors: Vec<String>,
 }
 
 impl Text {
@@ -86,6 +114,7 @@ impl Text {
             description: None,
             placeholder: None,
             weight: 0,
+            errors: Vec::new(),
         }
     }
 
@@ -141,6 +170,8 @@ pub struct Textarea {
     pub description: Option<String>,
     pub placeholder: Option<String>,
     pub weight: i32,
+    #[serde(default)]
+    pub errors: Vec<String>,
 }
 
 impl Textarea {
@@ -155,6 +186,7 @@ impl Textarea {
             description: None,
             placeholder: None,
             weight: 0,
+            errors: Vec::new(),
         }
     }
 
@@ -248,7 +280,10 @@ pub struct Select {
     pub label: Option<String>,
     pub description: Option<String>,
     pub options: HashMap<String, String>,
+    pub required: bool,
     pub weight: i32,
+    #[serde(default)]
+    pub errors: Vec<String>,
 }
 
 impl Select {
@@ -262,7 +297,9 @@ impl Select {
             label: None,
             description: None,
             options: HashMap::new(),
+            required: false,
             weight: 0,
+            errors: Vec::new(),
         }
     }
 
@@ -295,6 +332,11 @@ impl Select {
         self
     }
 
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
     pub fn weight(mut self, weight: i32) -> Self {
         self.weight = weight;
         self
@@ -309,11 +351,13 @@ impl From<Select> for Field {
 #[derive(Serialize, Deserialize)]
 pub struct Datetime {
     pub name: String,
-    #[serde(with = "serde_datetime_utc")]
-    pub value: DateTime<Utc>,
+    #[serde(with = "serde_option_datetime_utc")]
+    pub value: Option<DateTime<Utc>>,
     pub label: Option<String>,
     pub description: Option<String>,
     pub weight: i32,
+    #[serde(default)]
+    pub errors: Vec<String>,
 }
 
 impl Datetime {
@@ -323,15 +367,19 @@ impl Datetime {
     {
         Self {
             name: name.into(),
-            value: Utc::now(),
+            value: Some(Utc::now()),
             label: None,
             description: None,
             weight: 0,
+            errors: Vec::new(),
         }
     }
 
-    pub fn value(mut self, value: DateTime<Utc>) -> Self {
-        self.value = value;
+    pub fn value<T>(mut self, value: T) -> Self
+    where
+        T: Into<Option<DateTime<Utc>>>,
+    {
+        self.value = value.into();
         self
     }
 
@@ -371,6 +419,8 @@ pub struct Email {
     pub description: Option<String>,
     pub placeholder: Option<String>,
     pub weight: i32,
+    #[serde(default)]
+    pub errors: Vec<String>,
 }
 
 impl Email {
@@ -385,6 +435,7 @@ impl Email {
             description: None,
             placeholder: None,
             weight: 0,
+            errors: Vec::new(),
         }
     }
 
@@ -440,6 +491,8 @@ pub struct Password {
     pub description: Option<String>,
     pub placeholder: Option<String>,
     pub weight: i32,
+    #[serde(default)]
+    pub errors: Vec<String>,
 }
 
 impl Password {
@@ -454,6 +507,7 @@ impl Password {
             description: None,
             placeholder: None,
             weight: 0,
+            errors: Vec::new(),
         }
     }
 
@@ -501,28 +555,35 @@ impl From<Password> for Field {
     }
 }
 
-mod serde_datetime_utc {
+mod serde_option_datetime_utc {
     use chrono::{DateTime, NaiveDateTime, Utc};
     use serde::de::Error;
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
     pub fn serialize<S: Serializer>(
-        datetime: &DateTime<Utc>,
+        datetime: &Option<DateTime<Utc>>,
         serializer: S,
     ) -> Result<S::Ok, S::Error> {
-        datetime
-            .format("%Y-%m-%dT%H:%M")
-            .to_string()
-            .serialize(serializer)
+        match datetime {
+            Some(datetime) => datetime
+                .format("%Y-%m-%dT%H:%M")
+                .to_string()
+                .serialize(serializer),
+            None => "".serialize(serializer),
+        }
     }
 
     pub fn deserialize<'de, D: Deserializer<'de>>(
         deserializer: D,
-    ) -> Result<DateTime<Utc>, D::Error> {
+    ) -> Result<Option<DateTime<Utc>>, D::Error> {
         let datetime: String = Deserialize::deserialize(deserializer)?;
 
+        if datetime.is_empty() {
+            return Ok(None);
+        }
+
         NaiveDateTime::parse_from_str(&datetime, "%Y-%m-%dT%H:%M")
             .map_err(Error::custom)
-            .map(|datetime| DateTime::from_utc(datetime, Utc))
+            .map(|datetime| Some(DateTime::from_utc(datetime, Utc)))
     }
 }
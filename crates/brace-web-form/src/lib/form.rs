@@ -1,13 +1,18 @@
 use std::collections::VecDeque;
 
+use actix_web::dev::Payload;
+use actix_web::error::Error as ActixError;
+use actix_web::{FromRequest, HttpRequest};
 use failure::Error;
 use futures::future::{loop_fn, ok, Future, FutureResult, IntoFuture, Loop};
 use serde::{Deserialize, Serialize};
 
 use super::action::Action;
 use super::builder::BoxedFormBuilder;
+use super::csrf::{CsrfToken, FIELD};
 use super::data::FormData;
-use super::field::Field;
+use super::field::{self, Field};
+use super::validate::FieldErrors;
 
 #[derive(Serialize, Deserialize)]
 pub struct Form<S = ()> {
@@ -17,6 +22,10 @@ pub struct Form<S = ()> {
     pub(crate) actions: Vec<Action>,
     #[serde(skip, default = "VecDeque::new")]
     pub(crate) builders: VecDeque<Box<dyn BoxedFormBuilder<S>>>,
+    #[serde(skip)]
+    pub(crate) csrf: Option<CsrfToken>,
+    #[serde(default)]
+    pub(crate) errors: FieldErrors,
 }
 
 impl<S> Form<S>
@@ -30,6 +39,8 @@ where
             fields: Vec::new(),
             actions: Vec::new(),
             builders: VecDeque::new(),
+            csrf: None,
+            errors: FieldErrors::new(),
         }
     }
 
@@ -38,6 +49,11 @@ where
         self
     }
 
+    pub fn errors(mut self, errors: FieldErrors) -> Self {
+        self.errors = errors;
+        self
+    }
+
     pub fn build(self) -> impl Future<Item = Self, Error = Error> {
         let form = Box::new(self.into_future());
 
@@ -51,6 +67,21 @@ where
                     })
             },
         )
+        .map(|mut form| {
+            if let Some(token) = form.csrf.take() {
+                form.insert(field::hidden(FIELD).value(token));
+            }
+
+            for field in form.fields.iter_mut() {
+                let messages = form.errors.get(field.name()).to_vec();
+
+                if let Some(errors) = field.errors_mut() {
+                    errors.extend(messages);
+                }
+            }
+
+            form
+        })
     }
 }
 
@@ -59,6 +90,10 @@ impl<S> Form<S> {
         &self.data
     }
 
+    pub fn token(&self) -> Option<&CsrfToken> {
+        self.csrf.as_ref()
+    }
+
     pub fn state(&self) -> &S {
         &self.state
     }
@@ -88,6 +123,20 @@ impl<S> Form<S> {
     }
 }
 
+impl FromRequest for Form {
+    type Error = ActixError;
+    type Future = Result<Self, Self::Error>;
+    type Config = ();
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let mut form = Form::new(());
+
+        form.csrf = Some(CsrfToken::from_request(req, payload)?);
+
+        Ok(form)
+    }
+}
+
 impl<S> IntoFuture for Form<S> {
     type Item = Self;
     type Error = Error;
@@ -100,9 +149,14 @@ impl<S> IntoFuture for Form<S> {
 
 #[cfg(test)]
 mod tests {
+    use actix_web::dev::Payload;
+    use actix_web::test::TestRequest;
+    use actix_web::FromRequest;
     use futures::future::Future;
 
-    use crate::{action, field, Form};
+    use crate::csrf::FIELD;
+    use crate::field::Field;
+    use crate::{action, field, CsrfToken, FieldErrors, Form};
 
     struct FormState {
         value: String,
@@ -146,4 +200,52 @@ mod tests {
         assert_eq!(form.fields.len(), 1);
         assert_eq!(form.actions.len(), 1);
     }
+
+    #[test]
+    fn test_form_build_with_csrf() {
+        let req = TestRequest::default().to_http_request();
+
+        req.extensions_mut().insert(CsrfToken("token".to_owned()));
+
+        let mut form = Form::from_request(&req, &mut Payload::None).unwrap();
+
+        form.builder(build_form_without_state);
+
+        let form = form.build().wait().unwrap();
+
+        assert_eq!(form.fields.len(), 2);
+
+        match &form.fields[1] {
+            Field::Hidden(field) => {
+                assert_eq!(field.name, FIELD);
+                assert_eq!(field.value, "token");
+            }
+            _ => panic!("expected a hidden field"),
+        }
+    }
+
+    #[test]
+    fn test_form_build_with_errors() {
+        let mut errors = FieldErrors::new();
+
+        errors.insert("title", "This field is required");
+
+        let mut form = Form::new(()).errors(errors);
+
+        form.builder(|mut form: Form| {
+            form.insert(field::text("title"));
+            form.insert(field::text("slug"));
+            form
+        });
+
+        let form = form.build().wait().unwrap();
+
+        match (&form.fields[0], &form.fields[1]) {
+            (Field::Text(title), Field::Text(slug)) => {
+                assert_eq!(title.errors, vec!["This field is required".to_owned()]);
+                assert!(slug.errors.is_empty());
+            }
+            _ => panic!("expected text fields"),
+        }
+    }
 }
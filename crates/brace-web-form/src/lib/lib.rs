@@ -1,9 +1,13 @@
 pub use self::builder::FormBuilder;
+pub use self::csrf::{Csrf, CsrfForm, CsrfProtection, CsrfToken};
 pub use self::data::FormData;
 pub use self::form::Form;
+pub use self::validate::{FieldErrors, Validator};
 
 pub mod action;
 pub mod builder;
+pub mod csrf;
 pub mod data;
 pub mod field;
 pub mod form;
+pub mod validate;
@@ -0,0 +1,316 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use actix_web::{HttpResponse, ResponseError};
+use failure::Error;
+use futures::future::{join_all, ok, Future, IntoFuture};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::data::FormData;
+
+pub use regex::Regex;
+
+type BoxedCheck = Box<dyn Fn(&Value) -> Box<dyn Future<Item = Result<(), String>, Error = Error>>>;
+
+pub fn required() -> Rule {
+    Rule::new(|value| {
+        if is_empty(value) {
+            Err("This field is required".to_owned())
+        } else {
+            Ok(())
+        }
+    })
+}
+
+pub fn min_length(min: usize) -> Rule {
+    Rule::new(move |value| match value.as_str() {
+        Some(text) if !text.is_empty() && text.chars().count() < min => {
+            Err(format!("Must be at least {} characters long", min))
+        }
+        _ => Ok(()),
+    })
+}
+
+pub fn max_length(max: usize) -> Rule {
+    Rule::new(move |value| match value.as_str() {
+        Some(text) if text.chars().count() > max => {
+            Err(format!("Must be at most {} characters long", max))
+        }
+        _ => Ok(()),
+    })
+}
+
+pub fn pattern<S>(regex: Regex, message: S) -> Rule
+where
+    S: Into<String>,
+{
+    let message = message.into();
+
+    Rule::new(move |value| match value.as_str() {
+        Some(text) if !text.is_empty() && !regex.is_match(text) => Err(message.clone()),
+        _ => Ok(()),
+    })
+}
+
+pub fn email() -> Rule {
+    Rule::new(|value| match value.as_str() {
+        Some(text) if !text.is_empty() && !is_email(text) => {
+            Err("Must be a valid email address".to_owned())
+        }
+        _ => Ok(()),
+    })
+}
+
+pub fn range(min: f64, max: f64) -> Rule {
+    Rule::new(move |value| {
+        let number = match value {
+            Value::Number(number) => number.as_f64(),
+            Value::String(text) if !text.is_empty() => text.trim().parse::<f64>().ok(),
+            _ => return Ok(()),
+        };
+
+        match number {
+            Some(number) if number >= min && number <= max => Ok(()),
+            Some(_) => Err(format!("Must be between {} and {}", min, max)),
+            None => Err("Must be a number".to_owned()),
+        }
+    })
+}
+
+pub fn custom<F>(check: F) -> Rule
+where
+    F: Fn(&Value) -> Result<(), String> + 'static,
+{
+    Rule::new(check)
+}
+
+pub fn custom_future<F, R>(check: F) -> Rule
+where
+    F: Fn(&Value) -> R + 'static,
+    R: IntoFuture<Item = Result<(), String>, Error = Error>,
+    R::Future: 'static,
+{
+    Rule(Box::new(move |value| Box::new(check(value).into_future())))
+}
+
+pub struct Rule(BoxedCheck);
+
+impl Rule {
+    fn new<F>(check: F) -> Self
+    where
+        F: Fn(&Value) -> Result<(), String> + 'static,
+    {
+        Rule(Box::new(move |value| Box::new(ok(check(value)))))
+    }
+
+    fn check(&self, value: &Value) -> Box<dyn Future<Item = Result<(), String>, Error = Error>> {
+        (self.0)(value)
+    }
+}
+
+#[derive(Default)]
+pub struct Validator {
+    fields: Vec<(String, Rule)>,
+}
+
+impl Validator {
+    pub fn new() -> Self {
+        Self { fields: Vec::new() }
+    }
+
+    pub fn field<S>(mut self, name: S, rule: Rule) -> Self
+    where
+        S: Into<String>,
+    {
+        self.fields.push((name.into(), rule));
+        self
+    }
+
+    pub fn validate(&self, data: &FormData) -> impl Future<Item = FieldErrors, Error = Error> {
+        let checks = self
+            .fields
+            .iter()
+            .map(|(name, rule)| {
+                let name = name.clone();
+                let value = data.value(&name).cloned().unwrap_or(Value::Null);
+
+                rule.check(&value).map(move |res| (name, res))
+            })
+            .collect::<Vec<_>>();
+
+        join_all(checks).map(|results| {
+            let mut errors = FieldErrors::new();
+
+            for (name, res) in results {
+                if let Err(message) = res {
+                    errors.insert(name, message);
+                }
+            }
+
+            errors
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(transparent)]
+pub struct FieldErrors(BTreeMap<String, Vec<String>>);
+
+impl FieldErrors {
+    pub fn new() -> Self {
+        Self(BTreeMap::new())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn get(&self, name: &str) -> &[String] {
+        self.0.get(name).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn insert<S, M>(&mut self, name: S, message: M) -> &mut Self
+    where
+        S: Into<String>,
+        M: Into<String>,
+    {
+        self.0
+            .entry(name.into())
+            .or_insert_with(Vec::new)
+            .push(message.into());
+        self
+    }
+
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl fmt::Display for FieldErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "The submitted data is invalid")
+    }
+}
+
+impl ResponseError for FieldErrors {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::UnprocessableEntity().json(json!({
+            "error": {
+                "message": self.to_string(),
+                "fields": self,
+            },
+        }))
+    }
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(text) => text.trim().is_empty(),
+        Value::Array(items) => items.is_empty(),
+        Value::Object(map) => map.is_empty(),
+        _ => false,
+    }
+}
+
+fn is_email(text: &str) -> bool {
+    let mut parts = text.splitn(2, '@');
+
+    match (parts.next(), parts.next()) {
+        (Some(local), Some(domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && !text.chars().any(char::is_whitespace)
+                && domain.split('.').count() > 1
+                && domain.split('.').all(|label| !label.is_empty())
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::future::Future;
+    use serde_json::json;
+
+    use super::{
+        custom, custom_future, email, max_length, min_length, pattern, range, required, Regex,
+        Validator,
+    };
+    use crate::FormData;
+
+    #[test]
+    fn test_validator_collects_field_errors() {
+        let validator = Validator::new()
+            .field("title", required())
+            .field("title", max_length(5))
+            .field(
+                "slug",
+                pattern(Regex::new(r"^[a-z]+$").unwrap(), "Lowercase only"),
+            )
+            .field("slug", min_length(3))
+            .field("email", email())
+            .field("count", range(1.0, 10.0))
+            .field("missing", required())
+            .field("description", max_length(5));
+
+        let data = FormData::with(json!({
+            "title": "Too long",
+            "slug": "A",
+            "email": "user@domain",
+            "count": "11",
+            "description": "",
+        }))
+        .unwrap();
+
+        let errors = validator.validate(&data).wait().unwrap();
+
+        assert_eq!(errors.get("title"), ["Must be at most 5 characters long"]);
+        assert_eq!(
+            errors.get("slug"),
+            ["Lowercase only", "Must be at least 3 characters long"]
+        );
+        assert_eq!(errors.get("email"), ["Must be a valid email address"]);
+        assert_eq!(errors.get("count"), ["Must be between 1 and 10"]);
+        assert_eq!(errors.get("missing"), ["This field is required"]);
+        assert!(errors.get("description").is_empty());
+    }
+
+    #[test]
+    fn test_validator_passes_valid_data() {
+        let validator = Validator::new()
+            .field("title", required())
+            .field("email", email())
+            .field("count", range(1.0, 10.0))
+            .field(
+                "code",
+                custom(|value| match value.as_str() {
+                    Some("secret") => Ok(()),
+                    _ => Err("Wrong code".to_owned()),
+                }),
+            )
+            .field(
+                "name",
+                custom_future(|value| Ok(value.as_str().map(|_| ()).ok_or_else(String::new))),
+            );
+
+        let data = FormData::with(json!({
+            "title": "Title",
+            "email": "user@domain.test",
+            "count": 5,
+            "code": "secret",
+            "name": "name",
+        }))
+        .unwrap();
+
+        let errors = validator.validate(&data).wait().unwrap();
+
+        assert!(errors.is_empty());
+        assert!(errors.into_result().is_ok());
+    }
+}
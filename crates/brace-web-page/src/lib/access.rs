@@ -0,0 +1,50 @@
+use actix_web::error::{Error, ErrorForbidden, ErrorInternalServerError};
+use brace_db::Database;
+use brace_web_auth::guard::Access;
+use brace_web_auth::model::Permission;
+use futures::future::{err, ok, Either, Future};
+use uuid::Uuid;
+
+pub fn authorize(
+    access: &Access,
+    database: &Database,
+    page: Uuid,
+    any: Permission,
+    own: Permission,
+) -> impl Future<Item = (), Error = Error> {
+    if access.has(any) {
+        return Either::A(ok(()));
+    }
+
+    if !access.has(own) {
+        return Either::A(err(ErrorForbidden("Forbidden")));
+    }
+
+    let user = access.user.id;
+
+    Either::B(
+        crate::action::retrieve_owner::retrieve_owner(database, page)
+            .map_err(ErrorInternalServerError)
+            .and_then(move |owner| {
+                if owner == Some(user) {
+                    Ok(())
+                } else {
+                    Err(ErrorForbidden("Forbidden"))
+                }
+            }),
+    )
+}
+
+pub fn owns(access: &Access, owner: Option<Uuid>, any: Permission, own: Permission) -> bool {
+    access.has(any) || (access.has(own) && owner == Some(access.user.id))
+}
+
+pub fn restrict(access: &Access, any: Permission, own: Permission) -> Result<Option<Uuid>, Error> {
+    if access.has(any) {
+        Ok(None)
+    } else if access.has(own) {
+        Ok(Some(access.user.id))
+    } else {
+        Err(ErrorForbidden("Forbidden"))
+    }
+}
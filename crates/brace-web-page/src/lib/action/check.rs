@@ -0,0 +1,85 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+
+use crate::model::PageIssue;
+
+static ORPHAN_QUERY: &str = r#"
+    SELECT p.id, p.parent
+    FROM pages p
+    LEFT JOIN pages q ON q.id = p.parent
+    WHERE p.parent IS NOT NULL AND q.id IS NULL
+    ORDER BY p.id
+"#;
+
+static CYCLE_QUERY: &str = r#"
+    WITH RECURSIVE reachable AS (
+        SELECT p.id
+        FROM pages p
+        LEFT JOIN pages q ON q.id = p.parent
+        WHERE p.parent IS NULL OR q.id IS NULL
+        UNION
+        SELECT t.id
+        FROM pages t
+        JOIN reachable r ON t.parent = r.id
+    )
+    SELECT id, parent
+    FROM pages
+    WHERE id NOT IN (SELECT id FROM reachable)
+    ORDER BY id
+"#;
+
+static DUPLICATE_QUERY: &str = r#"
+    SELECT parent, slug, array_agg(id ORDER BY id)
+    FROM pages
+    GROUP BY parent, slug
+    HAVING count(*) > 1
+    ORDER BY parent, slug
+"#;
+
+pub fn check(database: &Database) -> impl Future<Item = Vec<PageIssue>, Error = Error> {
+    database
+        .send(Check)
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct Check;
+
+impl Message for Check {
+    type Result = Result<Vec<PageIssue>, Error>;
+}
+
+impl Handler<Check> for DatabaseInner {
+    type Result = Result<Vec<PageIssue>, Error>;
+
+    fn handle(&mut self, _: Check, _: &mut Self::Context) -> Self::Result {
+        let conn = self.0.get()?;
+        let mut issues = Vec::new();
+
+        for row in conn.query(ORPHAN_QUERY, &[])?.iter() {
+            issues.push(PageIssue::Orphan {
+                page: row.get(0),
+                parent: row.get(1),
+            });
+        }
+
+        for row in conn.query(CYCLE_QUERY, &[])?.iter() {
+            issues.push(PageIssue::Cycle {
+                page: row.get(0),
+                parent: row.get(1),
+            });
+        }
+
+        for row in conn.query(DUPLICATE_QUERY, &[])?.iter() {
+            issues.push(PageIssue::Duplicate {
+                parent: row.get(0),
+                slug: row.get(1),
+                pages: row.get(2),
+            });
+        }
+
+        Ok(issues)
+    }
+}
@@ -9,7 +9,7 @@ use crate::model::Page;
 static QUERY: &str = r#"
     DELETE FROM pages *
     WHERE id = $1
-    RETURNING id, parent, slug, title, description, document, created, updated
+    RETURNING id, parent, slug, title, description, document, created, updated, status, publish_at, unpublish_at, created_by, updated_by
 "#;
 
 pub fn delete(database: &Database, page: Uuid) -> impl Future<Item = Page, Error = Error> {
@@ -47,6 +47,11 @@ impl Handler<Delete> for DatabaseInner {
             document: row.get(5),
             created: row.get(6),
             updated: row.get(7),
+            status: row.get::<_, String>(8).parse()?,
+            publish_at: row.get(9),
+            unpublish_at: row.get(10),
+            created_by: row.get(11),
+            updated_by: row.get(12),
         })
     }
 }
@@ -0,0 +1,45 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+use uuid::Uuid;
+
+static QUERY: &str = r#"
+    DELETE FROM page_redirects
+    WHERE id = $1
+    RETURNING path
+"#;
+
+pub fn delete_redirect(
+    database: &Database,
+    redirect: Uuid,
+) -> impl Future<Item = String, Error = Error> {
+    database
+        .send(DeleteRedirect(redirect))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct DeleteRedirect(pub Uuid);
+
+impl Message for DeleteRedirect {
+    type Result = Result<String, Error>;
+}
+
+impl Handler<DeleteRedirect> for DatabaseInner {
+    type Result = Result<String, Error>;
+
+    fn handle(&mut self, msg: DeleteRedirect, _: &mut Self::Context) -> Self::Result {
+        let conn = self.0.get()?;
+        let rows = conn.query(QUERY, &[&msg.0])?;
+
+        if rows.is_empty() {
+            return Err(format_err!("Row not returned"));
+        }
+
+        let row = rows.get(0);
+        let path: String = row.get(0);
+
+        Ok(path)
+    }
+}
@@ -1,58 +1,107 @@
 use actix::{Handler, Message};
 use brace_db::{Database, DatabaseInner};
+use brace_web::pager::Pager;
 use failure::{format_err, Error};
 use futures::future::Future;
 
-use crate::model::PageWithPath;
+use crate::model::{PageFilter, PageWithPath};
 
 static QUERY: &str = r#"
     WITH RECURSIVE cte AS (
-        SELECT id, parent, slug, title, description, document, created, updated, '/' || slug AS path
+        SELECT id, parent, slug, title, description, document, created, updated, status, publish_at, unpublish_at, created_by, updated_by, '/' || slug AS path
         FROM pages
         WHERE parent is null
         UNION ALL
-        SELECT t.id, t.parent, t.slug, t.title, t.description, t.document, t.created, t.updated, concat_ws('/', r.path, t.slug) AS path
+        SELECT t.id, t.parent, t.slug, t.title, t.description, t.document, t.created, t.updated, t.status, t.publish_at, t.unpublish_at, t.created_by, t.updated_by, concat_ws('/', r.path, t.slug) AS path
         FROM pages t
         JOIN cte r ON t.parent = r.id
     )
-    SELECT id, parent, slug, title, description, document, created, updated, path
+    SELECT id, parent, slug, title, description, document, created, updated, status, publish_at, unpublish_at, created_by, updated_by, path
     FROM cte
-    ORDER BY path
+    WHERE ($1::text IS NULL OR status = $1)
+    AND ($2::uuid IS NULL OR parent = $2)
+    AND ($3::text IS NULL OR strpos(lower(title), lower($3)) > 0)
+    AND ($4::timestamptz IS NULL OR created >= $4)
+    AND ($5::uuid IS NULL OR created_by = $5)
 "#;
 
-pub fn list(database: &Database) -> impl Future<Item = Vec<PageWithPath>, Error = Error> {
+pub fn list(
+    database: &Database,
+    filter: PageFilter,
+    pager: Pager,
+) -> impl Future<Item = (Vec<PageWithPath>, i64), Error = Error> {
     database
-        .send(List)
+        .send(List(filter, pager))
         .map_err(|err| format_err!("{}", err))
         .and_then(|res| res)
 }
 
-pub struct List;
+pub struct List(pub PageFilter, pub Pager);
 
 impl Message for List {
-    type Result = Result<Vec<PageWithPath>, Error>;
+    type Result = Result<(Vec<PageWithPath>, i64), Error>;
 }
 
 impl Handler<List> for DatabaseInner {
-    type Result = Result<Vec<PageWithPath>, Error>;
+    type Result = Result<(Vec<PageWithPath>, i64), Error>;
 
-    fn handle(&mut self, _: List, _: &mut Self::Context) -> Self::Result {
+    fn handle(&mut self, msg: List, _: &mut Self::Context) -> Self::Result {
         let conn = self.0.get()?;
-        let rows = conn.query(QUERY, &[])?;
+        let filter = msg.0;
+        let status = filter.status.map(|status| status.as_str());
 
-        Ok(rows
+        let count = conn.query(
+            &format!("SELECT count(*) FROM ({}) AS pages", QUERY),
+            &[
+                &status,
+                &filter.parent,
+                &filter.title,
+                &filter.created_after,
+                &filter.created_by,
+            ],
+        )?;
+        let total: i64 = count.get(0).get(0);
+
+        let rows = conn.query(
+            &format!(
+                "{} ORDER BY {} {}, id LIMIT $6 OFFSET $7",
+                QUERY,
+                filter.sort.as_str(),
+                filter.order.as_str()
+            ),
+            &[
+                &status,
+                &filter.parent,
+                &filter.title,
+                &filter.created_after,
+                &filter.created_by,
+                &msg.1.limit(),
+                &msg.1.offset(),
+            ],
+        )?;
+
+        let pages = rows
             .iter()
-            .map(|row| PageWithPath {
-                id: row.get(0),
-                parent: row.get(1),
-                slug: row.get(2),
-                title: row.get(3),
-                description: row.get(4),
-                document: row.get(5),
-                created: row.get(6),
-                updated: row.get(7),
-                path: row.get(8),
+            .map(|row| {
+                Ok(PageWithPath {
+                    id: row.get(0),
+                    parent: row.get(1),
+                    slug: row.get(2),
+                    title: row.get(3),
+                    description: row.get(4),
+                    document: row.get(5),
+                    created: row.get(6),
+                    updated: row.get(7),
+                    status: row.get::<_, String>(8).parse()?,
+                    publish_at: row.get(9),
+                    unpublish_at: row.get(10),
+                    created_by: row.get(11),
+                    updated_by: row.get(12),
+                    path: row.get(13),
+                })
             })
-            .collect())
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok((pages, total))
     }
 }
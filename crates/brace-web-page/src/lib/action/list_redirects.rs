@@ -0,0 +1,55 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+
+use crate::model::PageRedirect;
+
+static QUERY: &str = r#"
+    WITH RECURSIVE cte AS (
+        SELECT id, parent, '/' || slug AS path
+        FROM pages
+        WHERE parent is null
+        UNION ALL
+        SELECT t.id, t.parent, concat_ws('/', r.path, t.slug) AS path
+        FROM pages t
+        JOIN cte r ON t.parent = r.id
+    )
+    SELECT r.id, r.path, r.page, cte.path, r.created
+    FROM page_redirects r
+    JOIN cte ON cte.id = r.page
+    ORDER BY r.path
+"#;
+
+pub fn list_redirects(database: &Database) -> impl Future<Item = Vec<PageRedirect>, Error = Error> {
+    database
+        .send(ListRedirects)
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct ListRedirects;
+
+impl Message for ListRedirects {
+    type Result = Result<Vec<PageRedirect>, Error>;
+}
+
+impl Handler<ListRedirects> for DatabaseInner {
+    type Result = Result<Vec<PageRedirect>, Error>;
+
+    fn handle(&mut self, _: ListRedirects, _: &mut Self::Context) -> Self::Result {
+        let conn = self.0.get()?;
+        let rows = conn.query(QUERY, &[])?;
+
+        Ok(rows
+            .iter()
+            .map(|row| PageRedirect {
+                id: row.get(0),
+                path: row.get(1),
+                page: row.get(2),
+                location: row.get(3),
+                created: row.get(4),
+            })
+            .collect())
+    }
+}
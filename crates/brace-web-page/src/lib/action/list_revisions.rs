@@ -0,0 +1,42 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+use uuid::Uuid;
+
+use crate::action::snapshot::revision;
+use crate::model::PageRevision;
+
+static QUERY: &str = r#"
+    SELECT id, page, revision, parent, slug, title, description, document, author, created
+    FROM page_revisions
+    WHERE page = $1
+    ORDER BY revision DESC
+"#;
+
+pub fn list_revisions(
+    database: &Database,
+    page: Uuid,
+) -> impl Future<Item = Vec<PageRevision>, Error = Error> {
+    database
+        .send(ListRevisions(page))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct ListRevisions(pub Uuid);
+
+impl Message for ListRevisions {
+    type Result = Result<Vec<PageRevision>, Error>;
+}
+
+impl Handler<ListRevisions> for DatabaseInner {
+    type Result = Result<Vec<PageRevision>, Error>;
+
+    fn handle(&mut self, msg: ListRevisions, _: &mut Self::Context) -> Self::Result {
+        let conn = self.0.get()?;
+        let rows = conn.query(QUERY, &[&msg.0])?;
+
+        Ok(rows.iter().map(|row| revision(&row)).collect())
+    }
+}
@@ -7,15 +7,16 @@ use crate::model::Page;
 
 static QUERY: &str = r#"
     WITH RECURSIVE cte AS (
-        SELECT id, parent, slug, title, description, document, created, updated, '/' || slug AS path
+        SELECT id, parent, slug, title, description, document, created, updated, status, publish_at, unpublish_at, created_by, updated_by, '/' || slug AS path, true AS visible
         FROM pages
         WHERE parent is null
         UNION ALL
-        SELECT t.id, t.parent, t.slug, t.title, t.description, t.document, t.created, t.updated, concat_ws('/', r.path, t.slug) AS path
+        SELECT t.id, t.parent, t.slug, t.title, t.description, t.document, t.created, t.updated, t.status, t.publish_at, t.unpublish_at, t.created_by, t.updated_by, concat_ws('/', r.path, t.slug) AS path,
+            r.visible AND r.status = 'published' AND (r.publish_at IS NULL OR r.publish_at <= now()) AND (r.unpublish_at IS NULL OR r.unpublish_at > now()) AS visible
         FROM pages t
         JOIN cte r ON t.parent = r.id
     )
-    SELECT id, parent, slug, title, description, document, created, updated
+    SELECT id, parent, slug, title, description, document, created, updated, status, publish_at, unpublish_at, created_by, updated_by, visible
     FROM cte
     WHERE path = $1
 "#;
@@ -23,7 +24,7 @@ static QUERY: &str = r#"
 pub fn locate<S: Into<String>>(
     database: &Database,
     page: S,
-) -> impl Future<Item = Page, Error = Error> {
+) -> impl Future<Item = (Page, bool), Error = Error> {
     database
         .send(Locate(page.into()))
         .map_err(|err| format_err!("{}", err))
@@ -33,11 +34,11 @@ pub fn locate<S: Into<String>>(
 pub struct Locate(pub String);
 
 impl Message for Locate {
-    type Result = Result<Page, Error>;
+    type Result = Result<(Page, bool), Error>;
 }
 
 impl Handler<Locate> for DatabaseInner {
-    type Result = Result<Page, Error>;
+    type Result = Result<(Page, bool), Error>;
 
     fn handle(&mut self, msg: Locate, _: &mut Self::Context) -> Self::Result {
         let conn = self.0.get()?;
@@ -49,7 +50,7 @@ impl Handler<Locate> for DatabaseInner {
 
         let row = rows.get(0);
 
-        Ok(Page {
+        let page = Page {
             id: row.get(0),
             parent: row.get(1),
             slug: row.get(2),
@@ -58,6 +59,13 @@ impl Handler<Locate> for DatabaseInner {
             document: row.get(5),
             created: row.get(6),
             updated: row.get(7),
-        })
+            status: row.get::<_, String>(8).parse()?,
+            publish_at: row.get(9),
+            unpublish_at: row.get(10),
+            created_by: row.get(11),
+            updated_by: row.get(12),
+        };
+
+        Ok((page, row.get(13)))
     }
 }
@@ -0,0 +1,61 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+
+use crate::model::PageRedirect;
+
+static QUERY: &str = r#"
+    WITH RECURSIVE cte AS (
+        SELECT id, parent, '/' || slug AS path
+        FROM pages
+        WHERE parent is null
+        UNION ALL
+        SELECT t.id, t.parent, concat_ws('/', r.path, t.slug) AS path
+        FROM pages t
+        JOIN cte r ON t.parent = r.id
+    )
+    SELECT r.id, r.path, r.page, cte.path, r.created
+    FROM page_redirects r
+    JOIN cte ON cte.id = r.page
+    WHERE r.path = $1
+"#;
+
+pub fn locate_redirect<S: Into<String>>(
+    database: &Database,
+    path: S,
+) -> impl Future<Item = PageRedirect, Error = Error> {
+    database
+        .send(LocateRedirect(path.into()))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct LocateRedirect(pub String);
+
+impl Message for LocateRedirect {
+    type Result = Result<PageRedirect, Error>;
+}
+
+impl Handler<LocateRedirect> for DatabaseInner {
+    type Result = Result<PageRedirect, Error>;
+
+    fn handle(&mut self, msg: LocateRedirect, _: &mut Self::Context) -> Self::Result {
+        let conn = self.0.get()?;
+        let rows = conn.query(QUERY, &[&msg.0])?;
+
+        if rows.is_empty() {
+            return Err(format_err!("Row not found"));
+        }
+
+        let row = rows.get(0);
+
+        Ok(PageRedirect {
+            id: row.get(0),
+            path: row.get(1),
+            page: row.get(2),
+            location: row.get(3),
+            created: row.get(4),
+        })
+    }
+}
@@ -1,9 +1,20 @@
+pub mod check;
 pub mod create;
 pub mod delete;
+pub mod delete_redirect;
 pub mod install;
 pub mod list;
+pub mod list_redirects;
+pub mod list_revisions;
 pub mod locate;
+pub mod locate_redirect;
+pub mod redirect;
 pub mod retrieve;
+pub mod retrieve_owner;
 pub mod retrieve_path;
+pub mod retrieve_revision;
+pub mod revert_revision;
+pub mod snapshot;
+pub mod tree;
 pub mod uninstall;
 pub mod update;
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+use failure::Error;
+use postgres::transaction::Transaction;
+use uuid::Uuid;
+
+static PATHS_QUERY: &str = r#"
+    WITH RECURSIVE cte AS (
+        SELECT id, parent, '/' || slug AS path
+        FROM pages
+        WHERE parent is null
+        UNION ALL
+        SELECT t.id, t.parent, concat_ws('/', r.path, t.slug) AS path
+        FROM pages t
+        JOIN cte r ON t.parent = r.id
+    ), subtree AS (
+        SELECT id
+        FROM pages
+        WHERE id = $1
+        UNION ALL
+        SELECT t.id
+        FROM pages t
+        JOIN subtree s ON t.parent = s.id
+    )
+    SELECT cte.id, cte.path
+    FROM cte
+    JOIN subtree ON subtree.id = cte.id
+"#;
+
+static INSERT_QUERY: &str = r#"
+    INSERT INTO page_redirects (id, path, page)
+    VALUES ($1, $2, $3)
+    ON CONFLICT (path) DO UPDATE SET page = excluded.page, created = now()
+"#;
+
+static DELETE_QUERY: &str = r#"
+    DELETE FROM page_redirects
+    WHERE path = $1
+"#;
+
+pub fn paths(trans: &Transaction, page: Uuid) -> Result<HashMap<Uuid, String>, Error> {
+    let rows = trans.query(PATHS_QUERY, &[&page])?;
+
+    Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+}
+
+pub fn record(
+    trans: &Transaction,
+    before: &HashMap<Uuid, String>,
+    after: &HashMap<Uuid, String>,
+) -> Result<(), Error> {
+    let moved = after
+        .iter()
+        .filter_map(|(page, path)| match before.get(page) {
+            Some(old) if old != path => Some((page, old, path)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    for (page, old, _) in moved.iter() {
+        trans.execute(INSERT_QUERY, &[&Uuid::new_v4(), *old, *page])?;
+    }
+
+    for (_, _, path) in moved.iter() {
+        trans.execute(DELETE_QUERY, &[*path])?;
+    }
+
+    Ok(())
+}
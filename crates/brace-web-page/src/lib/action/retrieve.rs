@@ -7,7 +7,7 @@ use uuid::Uuid;
 use crate::model::Page;
 
 static QUERY: &str = r#"
-    SELECT id, parent, slug, title, description, document, created, updated
+    SELECT id, parent, slug, title, description, document, created, updated, status, publish_at, unpublish_at, created_by, updated_by
     FROM pages
     WHERE id = $1
 "#;
@@ -47,6 +47,11 @@ impl Handler<Retrieve> for DatabaseInner {
             document: row.get(5),
             created: row.get(6),
             updated: row.get(7),
+            status: row.get::<_, String>(8).parse()?,
+            publish_at: row.get(9),
+            unpublish_at: row.get(10),
+            created_by: row.get(11),
+            updated_by: row.get(12),
         })
     }
 }
@@ -0,0 +1,42 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+use uuid::Uuid;
+
+static QUERY: &str = r#"
+    SELECT created_by
+    FROM pages
+    WHERE id = $1
+"#;
+
+pub fn retrieve_owner(
+    database: &Database,
+    page: Uuid,
+) -> impl Future<Item = Option<Uuid>, Error = Error> {
+    database
+        .send(RetrieveOwner(page))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct RetrieveOwner(pub Uuid);
+
+impl Message for RetrieveOwner {
+    type Result = Result<Option<Uuid>, Error>;
+}
+
+impl Handler<RetrieveOwner> for DatabaseInner {
+    type Result = Result<Option<Uuid>, Error>;
+
+    fn handle(&mut self, msg: RetrieveOwner, _: &mut Self::Context) -> Self::Result {
+        let conn = self.0.get()?;
+        let rows = conn.query(QUERY, &[&msg.0])?;
+
+        if rows.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(rows.get(0).get(0))
+    }
+}
@@ -0,0 +1,46 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+use uuid::Uuid;
+
+use crate::action::snapshot::revision;
+use crate::model::PageRevision;
+
+static QUERY: &str = r#"
+    SELECT id, page, revision, parent, slug, title, description, document, author, created
+    FROM page_revisions
+    WHERE page = $1 AND id = $2
+"#;
+
+pub fn retrieve_revision(
+    database: &Database,
+    page: Uuid,
+    revision: Uuid,
+) -> impl Future<Item = PageRevision, Error = Error> {
+    database
+        .send(RetrieveRevision(page, revision))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct RetrieveRevision(pub Uuid, pub Uuid);
+
+impl Message for RetrieveRevision {
+    type Result = Result<PageRevision, Error>;
+}
+
+impl Handler<RetrieveRevision> for DatabaseInner {
+    type Result = Result<PageRevision, Error>;
+
+    fn handle(&mut self, msg: RetrieveRevision, _: &mut Self::Context) -> Self::Result {
+        let conn = self.0.get()?;
+        let rows = conn.query(QUERY, &[&msg.0, &msg.1])?;
+
+        if rows.is_empty() {
+            return Err(format_err!("Row not returned"));
+        }
+
+        Ok(revision(&rows.get(0)))
+    }
+}
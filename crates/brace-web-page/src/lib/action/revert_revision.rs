@@ -0,0 +1,98 @@
+use actix::{Handler, Message};
+use brace_db::{Database, DatabaseInner};
+use failure::{format_err, Error};
+use futures::future::Future;
+use uuid::Uuid;
+
+use crate::action::redirect::{paths, record};
+use crate::action::snapshot::snapshot;
+use crate::action::tree::{unique, validate, TreeError};
+use crate::model::Page;
+
+static QUERY: &str = r#"
+    UPDATE pages p
+    SET parent = r.parent, slug = r.slug, title = r.title, description = r.description, document = r.document, updated = greatest(now(), p.created), updated_by = $3
+    FROM page_revisions r
+    WHERE p.id = $1 AND r.page = p.id AND r.id = $2
+    RETURNING p.id, p.parent, p.slug, p.title, p.description, p.document, p.created, p.updated, p.status, p.publish_at, p.unpublish_at, p.created_by, p.updated_by
+"#;
+
+static REVISION_QUERY: &str = r#"
+    SELECT r.slug, r.parent IS NULL OR EXISTS (
+        SELECT 1
+        FROM pages
+        WHERE id = r.parent
+    )
+    FROM page_revisions r
+    WHERE r.id = $2 AND r.page = $1
+"#;
+
+pub fn revert_revision(
+    database: &Database,
+    page: Uuid,
+    revision: Uuid,
+    author: Option<Uuid>,
+) -> impl Future<Item = Page, Error = Error> {
+    database
+        .send(RevertRevision(page, revision, author))
+        .map_err(|err| format_err!("{}", err))
+        .and_then(|res| res)
+}
+
+pub struct RevertRevision(pub Uuid, pub Uuid, pub Option<Uuid>);
+
+impl Message for RevertRevision {
+    type Result = Result<Page, Error>;
+}
+
+impl Handler<RevertRevision> for DatabaseInner {
+    type Result = Result<Page, Error>;
+
+    fn handle(&mut self, msg: RevertRevision, _: &mut Self::Context) -> Self::Result {
+        self.transaction(|trans| {
+            let revisions = trans.query(REVISION_QUERY, &[&msg.0, &msg.1])?;
+
+            if revisions.is_empty() {
+                return Err(format_err!("Row not returned"));
+            }
+
+            let slug: String = revisions.get(0).get(0);
+
+            if !revisions.get(0).get::<_, bool>(1) {
+                return Err(TreeError::MissingParent.into());
+            }
+
+            let before = paths(trans, msg.0)?;
+            let rows = trans
+                .query(QUERY, &[&msg.0, &msg.1, &msg.2])
+                .map_err(|err| unique(err, &slug))?;
+
+            if rows.is_empty() {
+                return Err(format_err!("Row not returned"));
+            }
+
+            let row = rows.get(0);
+            let page = Page {
+                id: row.get(0),
+                parent: row.get(1),
+                slug: row.get(2),
+                title: row.get(3),
+                description: row.get(4),
+                document: row.get(5),
+                created: row.get(6),
+                updated: row.get(7),
+                status: row.get::<_, String>(8).parse()?,
+                publish_at: row.get(9),
+                unpublish_at: row.get(10),
+                created_by: row.get(11),
+                updated_by: row.get(12),
+            };
+
+            validate(trans, &page)?;
+            snapshot(trans, &page, msg.2)?;
+            record(trans, &before, &paths(trans, page.id)?)?;
+
+            Ok(page)
+        })
+    }
+}
@@ -0,0 +1,64 @@
+use failure::{format_err, Error};
+use postgres::rows::Row;
+use postgres::transaction::Transaction;
+use uuid::Uuid;
+
+use crate::model::{Page, PageRevision};
+
+static LOCK_QUERY: &str = r#"
+    SELECT id
+    FROM pages
+    WHERE id = $1
+    FOR UPDATE
+"#;
+
+static QUERY: &str = r#"
+    INSERT INTO page_revisions (id, page, revision, parent, slug, title, description, document, author)
+    SELECT $1, $2, COALESCE(MAX(revision), 0) + 1, $3, $4, $5, $6, $7, $8
+    FROM page_revisions
+    WHERE page = $2
+    RETURNING id, page, revision, parent, slug, title, description, document, author, created
+"#;
+
+pub fn snapshot(
+    trans: &Transaction,
+    page: &Page,
+    author: Option<Uuid>,
+) -> Result<PageRevision, Error> {
+    trans.execute(LOCK_QUERY, &[&page.id])?;
+
+    let rows = trans.query(
+        QUERY,
+        &[
+            &Uuid::new_v4(),
+            &page.id,
+            &page.parent,
+            &page.slug,
+            &page.title,
+            &page.description,
+            &page.document,
+            &author,
+        ],
+    )?;
+
+    if rows.is_empty() {
+        return Err(format_err!("Row not returned"));
+    }
+
+    Ok(revision(&rows.get(0)))
+}
+
+pub(crate) fn revision(row: &Row) -> PageRevision {
+    PageRevision {
+        id: row.get(0),
+        page: row.get(1),
+        revision: row.get(2),
+        parent: row.get(3),
+        slug: row.get(4),
+        title: row.get(5),
+        description: row.get(6),
+        document: row.get(7),
+        author: row.get(8),
+        created: row.get(9),
+    }
+}
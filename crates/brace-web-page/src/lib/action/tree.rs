@@ -0,0 +1,92 @@
+use failure::{Error, Fail};
+use postgres::error::UNIQUE_VIOLATION;
+use postgres::transaction::Transaction;
+
+use crate::model::Page;
+
+static CYCLE_QUERY: &str = r#"
+    WITH RECURSIVE ancestors AS (
+        SELECT id, parent
+        FROM pages
+        WHERE id = $2
+        UNION
+        SELECT t.id, t.parent
+        FROM pages t
+        JOIN ancestors a ON t.id = a.parent
+    )
+    SELECT EXISTS (
+        SELECT 1
+        FROM ancestors
+        WHERE id = $1
+    )
+"#;
+
+static DUPLICATE_QUERY: &str = r#"
+    SELECT EXISTS (
+        SELECT 1
+        FROM pages
+        WHERE id <> $1 AND parent IS NOT DISTINCT FROM $2 AND slug = $3
+    )
+"#;
+
+#[derive(Debug, Fail, PartialEq)]
+pub enum TreeError {
+    #[fail(display = "A page can not be its own parent or be placed below one of its children")]
+    Cycle,
+    #[fail(
+        display = "A page with the slug \"{}\" already exists at this level",
+        _0
+    )]
+    Duplicate(String),
+    #[fail(display = "The parent page of this revision no longer exists")]
+    MissingParent,
+}
+
+impl TreeError {
+    pub fn field(&self) -> &'static str {
+        match self {
+            TreeError::Cycle => "parent",
+            TreeError::Duplicate(_) => "slug",
+            TreeError::MissingParent => "parent",
+        }
+    }
+}
+
+static SLUG_INDEXES: &[&str] = &["pages_parent_slug_key", "pages_root_slug_key"];
+
+pub fn validate(trans: &Transaction, page: &Page) -> Result<(), Error> {
+    if let Some(parent) = page.parent {
+        let rows = trans.query(CYCLE_QUERY, &[&page.id, &parent])?;
+
+        if rows.get(0).get::<_, bool>(0) {
+            return Err(TreeError::Cycle.into());
+        }
+    }
+
+    let rows = trans.query(DUPLICATE_QUERY, &[&page.id, &page.parent, &page.slug])?;
+
+    if rows.get(0).get::<_, bool>(0) {
+        return Err(TreeError::Duplicate(page.slug.clone()).into());
+    }
+
+    Ok(())
+}
+
+pub fn unique(err: postgres::Error, slug: &str) -> Error {
+    let duplicate = match err.as_db() {
+        Some(db) => {
+            db.code == UNIQUE_VIOLATION
+                && db
+                    .constraint
+                    .as_ref()
+                    .map_or(false, |name| SLUG_INDEXES.contains(&name.as_str()))
+        }
+        None => false,
+    };
+
+    if duplicate {
+        TreeError::Duplicate(slug.to_owned()).into()
+    } else {
+        err.into()
+    }
+}
@@ -2,24 +2,32 @@ use actix::{Handler, Message};
 use brace_db::{Database, DatabaseInner};
 use failure::{format_err, Error};
 use futures::future::Future;
+use uuid::Uuid;
 
+use crate::action::redirect::{paths, record};
+use crate::action::snapshot::snapshot;
+use crate::action::tree::{unique, validate};
 use crate::model::Page;
 
 static QUERY: &str = r#"
     UPDATE pages
-    SET parent = $2, slug = $3, title = $4, description = $5, document = $6, created = $7, updated = $8
+    SET parent = $2, slug = $3, title = $4, description = $5, document = $6, created = $7, updated = $8, status = $9, publish_at = $10, unpublish_at = $11, updated_by = $12
     WHERE id = $1
-    RETURNING id, parent, slug, title, description, document, created, updated
+    RETURNING id, parent, slug, title, description, document, created, updated, status, publish_at, unpublish_at, created_by, updated_by
 "#;
 
-pub fn update(database: &Database, page: Page) -> impl Future<Item = Page, Error = Error> {
+pub fn update(
+    database: &Database,
+    page: Page,
+    author: Option<Uuid>,
+) -> impl Future<Item = Page, Error = Error> {
     database
-        .send(Update(page))
+        .send(Update(page, author))
         .map_err(|err| format_err!("{}", err))
         .and_then(|res| res)
 }
 
-pub struct Update(pub Page);
+pub struct Update(pub Page, pub Option<Uuid>);
 
 impl Message for Update {
     type Result = Result<Page, Error>;
@@ -29,36 +37,55 @@ impl Handler<Update> for DatabaseInner {
     type Result = Result<Page, Error>;
 
     fn handle(&mut self, msg: Update, _: &mut Self::Context) -> Self::Result {
-        let conn = self.0.get()?;
-        let rows = conn.query(
-            QUERY,
-            &[
-                &msg.0.id,
-                &msg.0.parent,
-                &msg.0.slug,
-                &msg.0.title,
-                &msg.0.description,
-                &msg.0.document,
-                &msg.0.created,
-                &msg.0.updated,
-            ],
-        )?;
+        self.transaction(|trans| {
+            validate(trans, &msg.0)?;
 
-        if rows.is_empty() {
-            return Err(format_err!("Row not returned"));
-        }
+            let before = paths(trans, msg.0.id)?;
+            let rows = trans
+                .query(
+                    QUERY,
+                    &[
+                        &msg.0.id,
+                        &msg.0.parent,
+                        &msg.0.slug,
+                        &msg.0.title,
+                        &msg.0.description,
+                        &msg.0.document,
+                        &msg.0.created,
+                        &msg.0.updated,
+                        &msg.0.status.as_str(),
+                        &msg.0.publish_at,
+                        &msg.0.unpublish_at,
+                        &msg.1,
+                    ],
+                )
+                .map_err(|err| unique(err, &msg.0.slug))?;
 
-        let row = rows.get(0);
+            if rows.is_empty() {
+                return Err(format_err!("Row not returned"));
+            }
 
-        Ok(Page {
-            id: row.get(0),
-            parent: row.get(1),
-            slug: row.get(2),
-            title: row.get(3),
-            description: row.get(4),
-            document: row.get(5),
-            created: row.get(6),
-            updated: row.get(7),
+            let row = rows.get(0);
+            let page = Page {
+                id: row.get(0),
+                parent: row.get(1),
+                slug: row.get(2),
+                title: row.get(3),
+                description: row.get(4),
+                document: row.get(5),
+                created: row.get(6),
+                updated: row.get(7),
+                status: row.get::<_, String>(8).parse()?,
+                publish_at: row.get(9),
+                unpublish_at: row.get(10),
+                created_by: row.get(11),
+                updated_by: row.get(12),
+            };
+
+            snapshot(trans, &page, msg.1)?;
+            record(trans, &before, &paths(trans, page.id)?)?;
+
+            Ok(page)
         })
     }
 }
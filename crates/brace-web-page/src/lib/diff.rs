@@ -0,0 +1,165 @@
+use difference::{Changeset, Difference};
+use serde::Serialize;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::model::PageRevision;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Change {
+    Equal,
+    Insert,
+    Delete,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct Line {
+    pub change: Change,
+    pub text: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct FieldDiff {
+    pub field: &'static str,
+    pub changed: bool,
+    pub lines: Vec<Line>,
+}
+
+pub fn diff(old: Option<&PageRevision>, new: &PageRevision) -> Vec<FieldDiff> {
+    vec![
+        field(
+            "parent",
+            &old.map(|old| parent(old.parent)).unwrap_or_default(),
+            &parent(new.parent),
+        ),
+        field(
+            "slug",
+            old.map(|old| old.slug.as_str()).unwrap_or_default(),
+            &new.slug,
+        ),
+        field(
+            "title",
+            old.map(|old| old.title.as_str()).unwrap_or_default(),
+            &new.title,
+        ),
+        field(
+            "description",
+            old.map(|old| old.description.as_str()).unwrap_or_default(),
+            &new.description,
+        ),
+        field(
+            "document",
+            &old.map(|old| document(&old.document)).unwrap_or_default(),
+            &document(&new.document),
+        ),
+    ]
+}
+
+pub fn previous<'a>(
+    revisions: &'a [PageRevision],
+    revision: &PageRevision,
+) -> Option<&'a PageRevision> {
+    revisions
+        .iter()
+        .filter(|item| item.revision < revision.revision)
+        .max_by_key(|item| item.revision)
+}
+
+fn field(field: &'static str, old: &str, new: &str) -> FieldDiff {
+    FieldDiff {
+        field,
+        changed: old != new,
+        lines: lines(old, new),
+    }
+}
+
+fn lines(old: &str, new: &str) -> Vec<Line> {
+    if old.is_empty() {
+        return split(Change::Insert, new);
+    }
+
+    if new.is_empty() {
+        return split(Change::Delete, old);
+    }
+
+    Changeset::new(old, new, "\n")
+        .diffs
+        .into_iter()
+        .flat_map(|diff| match diff {
+            Difference::Same(text) => split(Change::Equal, &text),
+            Difference::Add(text) => split(Change::Insert, &text),
+            Difference::Rem(text) => split(Change::Delete, &text),
+        })
+        .collect()
+}
+
+fn split(change: Change, text: &str) -> Vec<Line> {
+    if text.is_empty() {
+        return Vec::new();
+    }
+
+    text.split('\n')
+        .map(|line| Line {
+            change,
+            text: line.to_string(),
+        })
+        .collect()
+}
+
+fn parent(parent: Option<Uuid>) -> String {
+    parent.map(|parent| parent.to_string()).unwrap_or_default()
+}
+
+fn document(document: &Value) -> String {
+    serde_json::to_string_pretty(document).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lines, Change, Line};
+
+    #[test]
+    fn test_diff_lines() {
+        assert_eq!(
+            lines("a\nb\nc", "a\nc\nd"),
+            vec![
+                Line {
+                    change: Change::Equal,
+                    text: "a".to_string(),
+                },
+                Line {
+                    change: Change::Delete,
+                    text: "b".to_string(),
+                },
+                Line {
+                    change: Change::Equal,
+                    text: "c".to_string(),
+                },
+                Line {
+                    change: Change::Insert,
+                    text: "d".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_lines_empty() {
+        assert_eq!(
+            lines("", "a\nb"),
+            vec![
+                Line {
+                    change: Change::Insert,
+                    text: "a".to_string(),
+                },
+                Line {
+                    change: Change::Insert,
+                    text: "b".to_string(),
+                },
+            ]
+        );
+
+        assert!(lines("", "").is_empty());
+    }
+}
@@ -1,12 +1,16 @@
 use std::collections::HashMap;
 
 use brace_db::Database;
-use brace_web_form::{action, field, Form, FormBuilder};
+use brace_web::pager::Pager;
+use brace_web_form::validate::{self, Regex};
+use brace_web_form::{action, field, Form, FormBuilder, Validator};
 use chrono::{DateTime, NaiveDateTime, Utc};
 use failure::Error;
 use futures::future::Future;
 use uuid::Uuid;
 
+use crate::model::{PageFilter, PageStatus};
+
 pub struct PageForm {
     pub database: Database,
 }
@@ -41,6 +45,41 @@ impl FormBuilder for PageForm {
                 .weight(3),
         );
 
+        form.insert(
+            field::select("status")
+                .label("Status")
+                .description("Only published pages are visible to visitors.")
+                .value(form.data().get::<String>("status")?)
+                .options(
+                    [
+                        PageStatus::Draft,
+                        PageStatus::Published,
+                        PageStatus::Archived,
+                    ]
+                    .iter()
+                    .map(|status| (status.as_str().to_owned(), status.label().to_owned()))
+                    .collect(),
+                )
+                .required(true)
+                .weight(5),
+        );
+
+        form.insert(
+            field::datetime("publish_at")
+                .label("Publish at")
+                .description("The date/time from which a published page is visible.")
+                .value(optional_datetime(&form, "publish_at")?)
+                .weight(6),
+        );
+
+        form.insert(
+            field::datetime("unpublish_at")
+                .label("Unpublish at")
+                .description("The date/time after which a published page is hidden.")
+                .value(optional_datetime(&form, "unpublish_at")?)
+                .weight(7),
+        );
+
         let created = DateTime::<Utc>::from_utc(
             NaiveDateTime::parse_from_str(
                 &form.data().get::<String>("created")?,
@@ -54,7 +93,7 @@ impl FormBuilder for PageForm {
                 .label("Created")
                 .description("The date/time of when the page was first created.")
                 .value(created)
-                .weight(5),
+                .weight(8),
         );
 
         form.insert(
@@ -62,7 +101,7 @@ impl FormBuilder for PageForm {
                 .label("Updated")
                 .description("The date/time of when the page was last updated.")
                 .value(Utc::now())
-                .weight(6),
+                .weight(9),
         );
 
         form.action(action::submit(""));
@@ -75,8 +114,31 @@ impl FormBuilder for PageForm {
     }
 }
 
+pub fn rules() -> Validator {
+    Validator::new()
+        .field("title", validate::required())
+        .field("slug", validate::max_length(255))
+        .field(
+            "slug",
+            validate::pattern(
+                Regex::new(r"^[\w-]+$").expect("valid slug pattern"),
+                "Only letters, numbers, hyphens and underscores are allowed",
+            ),
+        )
+}
+
+fn optional_datetime(form: &Form, key: &str) -> Result<Option<DateTime<Utc>>, Error> {
+    match form.data().get::<Option<String>>(key)? {
+        Some(ref datetime) if !datetime.is_empty() => Ok(Some(DateTime::<Utc>::from_utc(
+            NaiveDateTime::parse_from_str(datetime, "%Y-%m-%dT%H:%M")?,
+            Utc,
+        ))),
+        _ => Ok(None),
+    }
+}
+
 fn build_parent(mut form: Form, ctx: Database) -> impl Future<Item = Form, Error = Error> {
-    crate::action::list::list(&ctx).and_then(|pages| {
+    crate::action::list::list(&ctx, PageFilter::default(), Pager::all()).and_then(|(pages, _)| {
         let mut map = HashMap::<String, String>::new();
 
         for page in pages {
@@ -1,5 +1,8 @@
+pub mod access;
 pub mod action;
+pub mod diff;
 pub mod form;
 pub mod model;
 pub mod route;
 pub mod router;
+pub mod schema;
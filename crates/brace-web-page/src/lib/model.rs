@@ -1,4 +1,8 @@
+use std::str::FromStr;
+
+use brace_web::pager::Order;
 use chrono::{DateTime, Utc};
+use failure::{format_err, Error};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use uuid::Uuid;
@@ -13,10 +17,33 @@ pub struct Page {
     pub title: String,
     pub description: String,
     pub document: Value,
+    pub status: PageStatus,
+    #[serde(with = "serde_option_datetime_utc")]
+    pub publish_at: Option<DateTime<Utc>>,
+    #[serde(with = "serde_option_datetime_utc")]
+    pub unpublish_at: Option<DateTime<Utc>>,
     #[serde(with = "serde_datetime_utc")]
     pub created: DateTime<Utc>,
     #[serde(with = "serde_datetime_utc")]
     pub updated: DateTime<Utc>,
+    #[serde(deserialize_with = "serde_option_uuid::deserialize")]
+    pub created_by: Option<Uuid>,
+    #[serde(deserialize_with = "serde_option_uuid::deserialize")]
+    pub updated_by: Option<Uuid>,
+}
+
+impl Page {
+    pub fn is_published(&self) -> bool {
+        self.is_published_at(Utc::now())
+    }
+
+    pub fn is_published_at(&self, now: DateTime<Utc>) -> bool {
+        self.status == PageStatus::Published
+            && self.publish_at.map_or(true, |publish_at| publish_at <= now)
+            && self
+                .unpublish_at
+                .map_or(true, |unpublish_at| unpublish_at > now)
+    }
 }
 
 impl Default for Page {
@@ -28,8 +55,13 @@ impl Default for Page {
             title: "".to_string(),
             description: "".to_string(),
             document: json!({}),
+            status: PageStatus::Published,
+            publish_at: None,
+            unpublish_at: None,
             created: Utc::now(),
             updated: Utc::now(),
+            created_by: None,
+            updated_by: None,
         }
     }
 }
@@ -43,11 +75,154 @@ pub struct PageWithPath {
     pub title: String,
     pub description: String,
     pub document: Value,
+    pub status: PageStatus,
+    #[serde(with = "serde_option_datetime_utc")]
+    pub publish_at: Option<DateTime<Utc>>,
+    #[serde(with = "serde_option_datetime_utc")]
+    pub unpublish_at: Option<DateTime<Utc>>,
     #[serde(with = "serde_datetime_utc")]
     pub created: DateTime<Utc>,
     #[serde(with = "serde_datetime_utc")]
     pub updated: DateTime<Utc>,
+    #[serde(deserialize_with = "serde_option_uuid::deserialize")]
+    pub created_by: Option<Uuid>,
+    #[serde(deserialize_with = "serde_option_uuid::deserialize")]
+    pub updated_by: Option<Uuid>,
+    pub path: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PageRedirect {
+    pub id: Uuid,
     pub path: String,
+    pub page: Uuid,
+    pub location: String,
+    #[serde(with = "serde_datetime_utc")]
+    pub created: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PageStatus {
+    Draft,
+    Published,
+    Archived,
+}
+
+impl PageStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PageStatus::Draft => "draft",
+            PageStatus::Published => "published",
+            PageStatus::Archived => "archived",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PageStatus::Draft => "Draft",
+            PageStatus::Published => "Published",
+            PageStatus::Archived => "Archived",
+        }
+    }
+}
+
+impl Default for PageStatus {
+    fn default() -> Self {
+        PageStatus::Published
+    }
+}
+
+impl FromStr for PageStatus {
+    type Err = Error;
+
+    fn from_str(status: &str) -> Result<Self, Self::Err> {
+        match status {
+            "draft" => Ok(PageStatus::Draft),
+            "published" => Ok(PageStatus::Published),
+            "archived" => Ok(PageStatus::Archived),
+            _ => Err(format_err!("Invalid page status: {}", status)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct PageFilter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<PageStatus>,
+    #[serde(
+        deserialize_with = "serde_option_uuid::deserialize",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub parent: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_after: Option<DateTime<Utc>>,
+    #[serde(skip)]
+    pub created_by: Option<Uuid>,
+    pub sort: PageSort,
+    pub order: Order,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PageSort {
+    Title,
+    Created,
+    Updated,
+    Path,
+}
+
+impl PageSort {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PageSort::Title => "title",
+            PageSort::Created => "created",
+            PageSort::Updated => "updated",
+            PageSort::Path => "path",
+        }
+    }
+}
+
+impl Default for PageSort {
+    fn default() -> Self {
+        PageSort::Path
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PageRevision {
+    pub id: Uuid,
+    pub page: Uuid,
+    pub revision: i32,
+    pub parent: Option<Uuid>,
+    pub slug: String,
+    pub title: String,
+    pub description: String,
+    pub document: Value,
+    pub author: Option<Uuid>,
+    #[serde(with = "serde_datetime_utc")]
+    pub created: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum PageIssue {
+    Orphan {
+        page: Uuid,
+        parent: Uuid,
+    },
+    Cycle {
+        page: Uuid,
+        parent: Option<Uuid>,
+    },
+    Duplicate {
+        parent: Option<Uuid>,
+        slug: String,
+        pages: Vec<Uuid>,
+    },
 }
 
 mod serde_datetime_utc {
@@ -76,6 +251,36 @@ mod serde_datetime_utc {
     }
 }
 
+mod serde_option_datetime_utc {
+    use chrono::{DateTime, NaiveDateTime, Utc};
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        datetime: &Option<DateTime<Utc>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        datetime
+            .map(|datetime| datetime.format("%Y-%m-%dT%H:%M").to_string())
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<DateTime<Utc>>, D::Error> {
+        let datetime: Option<String> = Deserialize::deserialize(deserializer)?;
+
+        match datetime {
+            Some(ref datetime) if !datetime.is_empty() => {
+                NaiveDateTime::parse_from_str(datetime, "%Y-%m-%dT%H:%M")
+                    .map_err(Error::custom)
+                    .map(|datetime| Some(DateTime::from_utc(datetime, Utc)))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
 mod serde_option_uuid {
     use serde::de::{Deserialize, Deserializer, Error};
     use uuid::Uuid;
@@ -99,9 +304,10 @@ mod serde_option_uuid {
 
 #[cfg(test)]
 mod tests {
+    use chrono::{Duration, Utc};
     use serde_json::Value;
 
-    use super::Page;
+    use super::{Page, PageStatus};
 
     #[test]
     fn test_serde_page_default() {
@@ -124,4 +330,52 @@ mod tests {
 
         assert!(out.parent.is_none());
     }
+
+    #[test]
+    fn test_serde_page_schedule() {
+        let mut val = serde_json::to_value(&Page::default()).unwrap();
+
+        assert!(val["publish_at"].is_null());
+
+        val["status"] = Value::String("published".to_string());
+        val["publish_at"] = Value::String("".to_string());
+        val["unpublish_at"] = Value::String("2019-01-01T10:30".to_string());
+
+        let out: Page = serde_json::from_value(val).unwrap();
+
+        assert_eq!(out.status, PageStatus::Published);
+        assert!(out.publish_at.is_none());
+        assert!(out.unpublish_at.is_some());
+    }
+
+    #[test]
+    fn test_page_published() {
+        let now = Utc::now();
+        let mut page = Page::default();
+
+        assert!(page.is_published_at(now));
+
+        page.status = PageStatus::Draft;
+
+        assert!(!page.is_published_at(now));
+
+        page.status = PageStatus::Published;
+
+        page.publish_at = Some(now + Duration::hours(1));
+
+        assert!(!page.is_published_at(now));
+
+        page.publish_at = Some(now - Duration::hours(1));
+        page.unpublish_at = Some(now);
+
+        assert!(!page.is_published_at(now));
+
+        page.unpublish_at = Some(now + Duration::hours(1));
+
+        assert!(page.is_published_at(now));
+
+        page.status = PageStatus::Archived;
+
+        assert!(!page.is_published_at(now));
+    }
 }
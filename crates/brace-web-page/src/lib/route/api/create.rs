@@ -1,36 +1,40 @@
-use actix_web::error::{Error, ErrorInternalServerError};
+use actix_web::error::{Error, ErrorBadRequest, ErrorInternalServerError};
 use actix_web::http::header;
 use actix_web::web::{Data, Json};
 use actix_web::HttpResponse;
 use brace_db::Database;
-use brace_web_auth::model::CurrentAuth;
-use futures::future::{ok, Either, Future};
+use brace_web_auth::guard::{ApiGuard, CreatePage};
+use brace_web_form::FormData;
+use futures::future::Future;
 use serde_json::json;
 
+use crate::form::page::rules;
 use crate::model::Page;
+use crate::route::api::tree_error;
 
 pub fn create(
-    auth: CurrentAuth,
+    access: ApiGuard<CreatePage>,
     database: Data<Database>,
-    page: Json<Page>,
+    data: Json<FormData>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
-    match auth {
-        CurrentAuth::Unauthenticated => Either::A(ok(HttpResponse::Unauthorized()
-            .header(
-                "WWW-Authenticate",
-                r#"Bearer realm="localhost", charset="UTF-8""#,
-            )
-            .finish())),
-        CurrentAuth::Authenticated(_) => Either::B(
-            crate::action::create::create(&database, page.into_inner())
-                .map_err(ErrorInternalServerError)
-                .and_then(|page| {
-                    HttpResponse::Created()
-                        .header(header::LOCATION, format!("/api/pages/{}", page.id))
-                        .json(json!({
-                            "value": page,
-                        }))
-                }),
-        ),
-    }
+    let data = data.into_inner();
+    let user = access.user.id;
+
+    rules()
+        .validate(&data)
+        .map_err(ErrorInternalServerError)
+        .and_then(move |errors| -> Result<Page, Error> {
+            errors.into_result()?;
+            data.parse::<Page>().map_err(ErrorBadRequest)
+        })
+        .and_then(move |page| {
+            crate::action::create::create(&database, page, Some(user)).then(|res| match res {
+                Ok(page) => Ok(HttpResponse::Created()
+                    .header(header::LOCATION, format!("/api/pages/{}", page.id))
+                    .json(json!({
+                        "value": page,
+                    }))),
+                Err(err) => tree_error(err),
+            })
+        })
 }
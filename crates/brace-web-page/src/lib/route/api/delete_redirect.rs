@@ -0,0 +1,28 @@
+use actix_web::error::{Error, ErrorInternalServerError};
+use actix_web::web::{Data, Path};
+use actix_web::HttpResponse;
+use brace_db::Database;
+use brace_web_auth::guard::{ApiGuard, EditAnyPage};
+use futures::future::Future;
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+pub fn delete_redirect(
+    _: ApiGuard<EditAnyPage>,
+    database: Data<Database>,
+    path: Path<Info>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    crate::action::delete_redirect::delete_redirect(&database, path.redirect)
+        .map_err(ErrorInternalServerError)
+        .and_then(|path| {
+            HttpResponse::Ok().json(json!({
+                "value": path,
+            }))
+        })
+}
+
+#[derive(Deserialize)]
+pub struct Info {
+    redirect: Uuid,
+}
@@ -1,30 +1,41 @@
 use actix_web::error::{Error, ErrorInternalServerError};
 use actix_web::web::Data;
-use actix_web::HttpResponse;
+use actix_web::{HttpRequest, HttpResponse};
 use brace_db::Database;
-use brace_web_auth::model::CurrentAuth;
-use futures::future::{ok, Either, Future};
+use brace_web::extract::Query;
+use brace_web::pager::Pager;
+use brace_web_auth::guard::{ApiGuard, Authenticated};
+use brace_web_auth::model::Permission;
+use futures::future::{err, Either, Future};
 use serde_json::json;
 
+use crate::access::restrict;
+use crate::model::PageFilter;
+
 pub fn list(
-    auth: CurrentAuth,
+    access: ApiGuard<Authenticated>,
+    req: HttpRequest,
     database: Data<Database>,
+    filter: Query<PageFilter>,
+    pager: Query<Pager>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
-    match auth {
-        CurrentAuth::Unauthenticated => Either::A(ok(HttpResponse::Unauthorized()
-            .header(
-                "WWW-Authenticate",
-                r#"Bearer realm="localhost", charset="UTF-8""#,
-            )
-            .finish())),
-        CurrentAuth::Authenticated(_) => Either::B(
-            crate::action::list::list(&database)
-                .map_err(ErrorInternalServerError)
-                .and_then(|pages| {
-                    HttpResponse::Ok().json(json!({
-                        "value": pages,
-                    }))
-                }),
-        ),
-    }
+    let mut filter = filter.into_inner();
+    let pager = pager.into_inner();
+    let path = req.path().to_owned();
+
+    filter.created_by = match restrict(&access, Permission::EditAnyPage, Permission::EditOwnPage) {
+        Ok(owner) => owner,
+        Err(e) => return Either::A(err(e)),
+    };
+
+    Either::B(
+        crate::action::list::list(&database, filter.clone(), pager)
+            .map_err(ErrorInternalServerError)
+            .and_then(move |(pages, total)| {
+                HttpResponse::Ok().json(json!({
+                    "value": pages,
+                    "pager": pager.paginate(&path, &filter, total),
+                }))
+            }),
+    )
 }
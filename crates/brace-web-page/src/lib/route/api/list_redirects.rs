@@ -0,0 +1,20 @@
+use actix_web::error::{Error, ErrorInternalServerError};
+use actix_web::web::Data;
+use actix_web::HttpResponse;
+use brace_db::Database;
+use brace_web_auth::guard::{ApiGuard, EditAnyPage};
+use futures::future::Future;
+use serde_json::json;
+
+pub fn list_redirects(
+    _: ApiGuard<EditAnyPage>,
+    database: Data<Database>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    crate::action::list_redirects::list_redirects(&database)
+        .map_err(ErrorInternalServerError)
+        .and_then(|redirects| {
+            HttpResponse::Ok().json(json!({
+                "value": redirects,
+            }))
+        })
+}
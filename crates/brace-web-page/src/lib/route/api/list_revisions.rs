@@ -0,0 +1,42 @@
+use actix_web::error::{Error, ErrorInternalServerError};
+use actix_web::web::{Data, Path};
+use actix_web::HttpResponse;
+use brace_db::Database;
+use brace_web_auth::guard::{ApiGuard, Authenticated};
+use brace_web_auth::model::Permission;
+use futures::future::Future;
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::access::authorize;
+
+pub fn list_revisions(
+    access: ApiGuard<Authenticated>,
+    database: Data<Database>,
+    path: Path<Info>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    let page = path.page;
+
+    authorize(
+        &access,
+        &database,
+        page,
+        Permission::EditAnyPage,
+        Permission::EditOwnPage,
+    )
+    .and_then(move |_| {
+        crate::action::list_revisions::list_revisions(&database, page)
+            .map_err(ErrorInternalServerError)
+    })
+    .and_then(|revisions| {
+        HttpResponse::Ok().json(json!({
+            "value": revisions,
+        }))
+    })
+}
+
+#[derive(Deserialize)]
+pub struct Info {
+    page: Uuid,
+}
@@ -1,9 +1,19 @@
+use actix_web::error::{Error, ErrorInternalServerError};
 use actix_web::web::{self, ServiceConfig};
+use actix_web::HttpResponse;
+use serde_json::json;
+
+use crate::action::tree::TreeError;
 
 pub mod create;
 pub mod delete;
+pub mod delete_redirect;
 pub mod list;
+pub mod list_redirects;
+pub mod list_revisions;
 pub mod retrieve;
+pub mod retrieve_revision;
+pub mod revert_revision;
 pub mod update;
 
 pub fn config(conf: &mut ServiceConfig) {
@@ -14,11 +24,43 @@ pub fn config(conf: &mut ServiceConfig) {
                     .route(web::get().to_async(list::list))
                     .route(web::post().to_async(create::create)),
             )
+            .service(
+                web::resource("/redirects/")
+                    .route(web::get().to_async(list_redirects::list_redirects)),
+            )
+            .service(
+                web::resource("/redirects/{redirect}")
+                    .route(web::delete().to_async(delete_redirect::delete_redirect)),
+            )
             .service(
                 web::resource("/{page}")
                     .route(web::get().to_async(retrieve::retrieve))
                     .route(web::put().to_async(update::update))
                     .route(web::delete().to_async(delete::delete)),
+            )
+            .service(
+                web::resource("/{page}/revisions")
+                    .route(web::get().to_async(list_revisions::list_revisions)),
+            )
+            .service(
+                web::resource("/{page}/revisions/{revision}")
+                    .route(web::get().to_async(retrieve_revision::retrieve_revision)),
+            )
+            .service(
+                web::resource("/{page}/revisions/{revision}/revert")
+                    .route(web::post().to_async(revert_revision::revert_revision)),
             ),
     );
 }
+
+pub(crate) fn tree_error(err: failure::Error) -> Result<HttpResponse, Error> {
+    match err.downcast::<TreeError>() {
+        Ok(err) => Ok(HttpResponse::UnprocessableEntity().json(json!({
+            "error": {
+                "field": err.field(),
+                "message": err.to_string(),
+            },
+        }))),
+        Err(err) => Err(ErrorInternalServerError(err)),
+    }
+}
@@ -2,34 +2,37 @@ use actix_web::error::{Error, ErrorInternalServerError};
 use actix_web::web::{Data, Path};
 use actix_web::HttpResponse;
 use brace_db::Database;
-use brace_web_auth::model::CurrentAuth;
-use futures::future::{ok, Either, Future};
+use brace_web_auth::guard::{ApiGuard, Authenticated};
+use brace_web_auth::model::Permission;
+use futures::future::Future;
 use serde::Deserialize;
 use serde_json::json;
 use uuid::Uuid;
 
+use crate::access::authorize;
+
 pub fn retrieve(
-    auth: CurrentAuth,
+    access: ApiGuard<Authenticated>,
     database: Data<Database>,
     path: Path<Info>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
-    match auth {
-        CurrentAuth::Unauthenticated => Either::A(ok(HttpResponse::Unauthorized()
-            .header(
-                "WWW-Authenticate",
-                r#"Bearer realm="localhost", charset="UTF-8""#,
-            )
-            .finish())),
-        CurrentAuth::Authenticated(_) => Either::B(
-            crate::action::retrieve::retrieve(&database, path.page)
-                .map_err(ErrorInternalServerError)
-                .and_then(|page| {
-                    HttpResponse::Ok().json(json!({
-                        "value": page,
-                    }))
-                }),
-        ),
-    }
+    let page = path.page;
+
+    authorize(
+        &access,
+        &database,
+        page,
+        Permission::EditAnyPage,
+        Permission::EditOwnPage,
+    )
+    .and_then(move |_| {
+        crate::action::retrieve::retrieve(&database, page).map_err(ErrorInternalServerError)
+    })
+    .and_then(|page| {
+        HttpResponse::Ok().json(json!({
+            "value": page,
+        }))
+    })
 }
 
 #[derive(Deserialize)]
@@ -0,0 +1,48 @@
+use actix_web::error::{Error, ErrorInternalServerError, ErrorNotFound};
+use actix_web::web::{Data, Path};
+use actix_web::HttpResponse;
+use brace_db::Database;
+use brace_web_auth::guard::{ApiGuard, Authenticated};
+use brace_web_auth::model::Permission;
+use futures::future::Future;
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::access::authorize;
+use crate::diff::{diff, previous};
+
+pub fn retrieve_revision(
+    access: ApiGuard<Authenticated>,
+    database: Data<Database>,
+    path: Path<Info>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    let (page, revision) = (path.page, path.revision);
+
+    authorize(
+        &access,
+        &database,
+        page,
+        Permission::EditAnyPage,
+        Permission::EditOwnPage,
+    )
+    .and_then(move |_| {
+        crate::action::list_revisions::list_revisions(&database, page)
+            .map_err(ErrorInternalServerError)
+    })
+    .and_then(
+        move |revisions| match revisions.iter().find(|item| item.id == revision) {
+            Some(revision) => Ok(HttpResponse::Ok().json(json!({
+                "value": revision,
+                "diff": diff(previous(&revisions, revision), revision),
+            }))),
+            None => Err(ErrorNotFound("Not Found")),
+        },
+    )
+}
+
+#[derive(Deserialize)]
+pub struct Info {
+    page: Uuid,
+    revision: Uuid,
+}
@@ -0,0 +1,45 @@
+use actix_web::error::Error;
+use actix_web::web::{Data, Path};
+use actix_web::HttpResponse;
+use brace_db::Database;
+use brace_web_auth::guard::{ApiGuard, Authenticated};
+use brace_web_auth::model::Permission;
+use futures::future::Future;
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::access::authorize;
+use crate::route::api::tree_error;
+
+pub fn revert_revision(
+    access: ApiGuard<Authenticated>,
+    database: Data<Database>,
+    path: Path<Info>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    let (page, revision, user) = (path.page, path.revision, access.user.id);
+
+    authorize(
+        &access,
+        &database,
+        page,
+        Permission::EditAnyPage,
+        Permission::EditOwnPage,
+    )
+    .and_then(move |_| {
+        crate::action::revert_revision::revert_revision(&database, page, revision, Some(user)).then(
+            |res| match res {
+                Ok(page) => Ok(HttpResponse::Ok().json(json!({
+                    "value": page,
+                }))),
+                Err(err) => tree_error(err),
+            },
+        )
+    })
+}
+
+#[derive(Deserialize)]
+pub struct Info {
+    page: Uuid,
+    revision: Uuid,
+}
@@ -1,33 +1,56 @@
-use actix_web::error::{Error, ErrorInternalServerError};
+use actix_web::error::{Error, ErrorBadRequest, ErrorInternalServerError};
 use actix_web::web::{Data, Json};
 use actix_web::HttpResponse;
 use brace_db::Database;
-use brace_web_auth::model::CurrentAuth;
-use futures::future::{ok, Either, Future};
+use brace_web_auth::guard::{ApiGuard, Authenticated};
+use brace_web_auth::model::Permission;
+use brace_web_form::FormData;
+use futures::future::{err, Either, Future};
 use serde_json::json;
+use uuid::Uuid;
 
+use crate::access::authorize;
+use crate::form::page::rules;
 use crate::model::Page;
+use crate::route::api::tree_error;
 
 pub fn update(
-    auth: CurrentAuth,
+    access: ApiGuard<Authenticated>,
     database: Data<Database>,
-    page: Json<Page>,
+    data: Json<FormData>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
-    match auth {
-        CurrentAuth::Unauthenticated => Either::A(ok(HttpResponse::Unauthorized()
-            .header(
-                "WWW-Authenticate",
-                r#"Bearer realm="localhost", charset="UTF-8""#,
-            )
-            .finish())),
-        CurrentAuth::Authenticated(_) => Either::B(
-            crate::action::update::update(&database, page.into_inner())
+    let data = data.into_inner();
+    let user = access.user.id;
+
+    let page = match data.get::<Uuid>("id") {
+        Ok(page) => page,
+        Err(e) => return Either::A(err(ErrorBadRequest(e))),
+    };
+
+    Either::B(
+        authorize(
+            &access,
+            &database,
+            page,
+            Permission::EditAnyPage,
+            Permission::EditOwnPage,
+        )
+        .and_then(move |_| {
+            rules()
+                .validate(&data)
                 .map_err(ErrorInternalServerError)
-                .and_then(|page| {
-                    HttpResponse::Ok().json(json!({
-                        "value": page,
-                    }))
-                }),
-        ),
-    }
+                .and_then(move |errors| -> Result<Page, Error> {
+                    errors.into_result()?;
+                    data.parse::<Page>().map_err(ErrorBadRequest)
+                })
+        })
+        .and_then(move |page| {
+            crate::action::update::update(&database, page, Some(user)).then(|res| match res {
+                Ok(page) => Ok(HttpResponse::Ok().json(json!({
+                    "value": page,
+                }))),
+                Err(err) => tree_error(err),
+            })
+        }),
+    )
 }
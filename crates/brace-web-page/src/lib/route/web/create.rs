@@ -1,79 +1,125 @@
-use actix_web::error::{Error, ErrorForbidden, ErrorInternalServerError};
-use actix_web::web::{Data, Form as FormExtractor};
+use actix_web::error::{Error, ErrorBadRequest, ErrorInternalServerError};
+use actix_web::web::Data;
 use actix_web::HttpResponse;
 use brace_db::Database;
 use brace_web::redirect::HttpRedirect;
 use brace_web::render::{Renderer, Template};
-use brace_web_auth::model::CurrentUser;
-use brace_web_form::{Form, FormData};
-use futures::future::{err, Either, Future};
+use brace_web_auth::guard::{CreatePage, WebGuard};
+use brace_web_form::csrf::{forbidden, REJECTED_MESSAGE};
+use brace_web_form::{CsrfForm, FieldErrors, Form, FormData};
+use futures::future::{err, ok, Either, Future, IntoFuture};
 use serde_json::json;
 
-use crate::form::page::PageForm;
+use crate::action::tree::TreeError;
+use crate::form::page::{rules, PageForm};
 use crate::model::Page;
 
 pub fn get(
-    user: CurrentUser,
+    _: WebGuard<CreatePage>,
+    form: Form,
     database: Data<Database>,
     renderer: Data<Renderer>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
-    match user {
-        CurrentUser::Anonymous => Either::A(err(ErrorForbidden("Forbidden"))),
-        CurrentUser::Authenticated(_) => Either::B(render(database, renderer)),
-    }
+    FormData::with(Page::default())
+        .map_err(ErrorInternalServerError)
+        .into_future()
+        .and_then(move |data| render(data, form, database, renderer, FieldErrors::new(), None))
 }
 
 pub fn post(
-    user: CurrentUser,
-    page: FormExtractor<Page>,
+    access: WebGuard<CreatePage>,
+    data: CsrfForm<FormData>,
+    form: Form,
     database: Data<Database>,
-) -> impl Future<Item = HttpRedirect, Error = Error> {
-    match user {
-        CurrentUser::Anonymous => Either::A(err(ErrorForbidden("Forbidden"))),
-        CurrentUser::Authenticated(_) => Either::B(
-            crate::action::create::create(&database, page.into_inner())
-                .map_err(ErrorInternalServerError)
-                .and_then(|page| HttpRedirect::to(format!("/pages/{}", page.id))),
-        ),
+    renderer: Data<Renderer>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    if !data.is_valid() {
+        return Either::A(
+            render(
+                data.into_inner(),
+                form,
+                database,
+                renderer,
+                FieldErrors::new(),
+                Some(REJECTED_MESSAGE.to_string()),
+            )
+            .map(forbidden),
+        );
     }
+
+    let data = data.into_inner();
+
+    Either::B(
+        rules()
+            .validate(&data)
+            .map_err(ErrorInternalServerError)
+            .and_then(move |errors| {
+                if !errors.is_empty() {
+                    return Either::A(render(data, form, database, renderer, errors, None));
+                }
+
+                let page = match data.parse::<Page>() {
+                    Ok(page) => page,
+                    Err(e) => return Either::B(Either::A(err(ErrorBadRequest(e)))),
+                };
+
+                Either::B(Either::B(
+                    crate::action::create::create(&database, page, Some(access.user.id)).then(
+                        move |res| match res {
+                            Ok(page) => {
+                                Either::A(ok(
+                                    HttpRedirect::to(format!("/pages/{}", page.id)).into_response()
+                                ))
+                            }
+                            Err(e) => match e.downcast::<TreeError>() {
+                                Ok(e) => {
+                                    let mut errors = FieldErrors::new();
+
+                                    errors.insert(e.field(), e.to_string());
+
+                                    Either::B(render(data, form, database, renderer, errors, None))
+                                }
+                                Err(e) => Either::A(err(ErrorInternalServerError(e))),
+                            },
+                        },
+                    ),
+                ))
+            }),
+    )
 }
 
 fn render(
+    data: FormData,
+    form: Form,
     database: Data<Database>,
     renderer: Data<Renderer>,
+    errors: FieldErrors,
+    message: Option<String>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
-    match FormData::with(Page::default()) {
-        Ok(data) => {
-            let mut form = Form::new(()).with(data);
+    let mut form = form.with(data).errors(errors);
 
-            form.builder(PageForm {
-                database: (*database).clone(),
-            });
+    form.builder(PageForm {
+        database: (*database).clone(),
+    });
 
-            Either::A(
-                form.build()
-                    .map_err(ErrorInternalServerError)
-                    .and_then(move |form| {
-                        let template = Template::new(
-                            "form-layout",
-                            json!({
-                                "title": "Create page",
-                                "form": form,
-                            }),
-                        );
+    form.build()
+        .map_err(ErrorInternalServerError)
+        .and_then(move |form| {
+            let template = Template::new(
+                "form-layout",
+                json!({
+                    "title": "Create page",
+                    "message": message,
+                    "form": form,
+                }),
+            );
 
-                        renderer
-                            .send(template)
-                            .map_err(ErrorInternalServerError)
-                            .and_then(move |res| match res {
-                                Ok(body) => {
-                                    Ok(HttpResponse::Ok().content_type("text/html").body(body))
-                                }
-                                Err(err) => Err(ErrorInternalServerError(err)),
-                            })
-                    }),
-            )
-        }
-        Err(e) => Either::B(err(ErrorInternalServerError(e))),
-    }
+            renderer
+                .send(template)
+                .map_err(ErrorInternalServerError)
+                .and_then(move |res| match res {
+                    Ok(body) => Ok(HttpResponse::Ok().content_type("text/html").body(body)),
+                    Err(err) => Err(ErrorInternalServerError(err)),
+                })
+        })
 }
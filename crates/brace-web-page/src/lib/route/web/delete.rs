@@ -1,54 +1,92 @@
-use actix_web::error::{Error, ErrorForbidden, ErrorInternalServerError};
+use actix_web::error::{Error, ErrorInternalServerError};
 use actix_web::web::{Data, Path};
 use actix_web::HttpResponse;
 use brace_db::Database;
 use brace_web::redirect::HttpRedirect;
 use brace_web::render::{Renderer, Template};
-use brace_web_auth::model::CurrentUser;
-use futures::future::{err, Either, Future};
+use brace_web_auth::guard::{Authenticated, WebGuard};
+use brace_web_auth::model::Permission;
+use brace_web_form::csrf::{forbidden, REJECTED_MESSAGE};
+use brace_web_form::{Csrf, CsrfToken};
+use futures::future::{Either, Future};
 use serde::Deserialize;
 use serde_json::json;
 use uuid::Uuid;
 
+use crate::access::authorize;
 use crate::model::Page;
 
 pub fn get(
-    user: CurrentUser,
+    access: WebGuard<Authenticated>,
     info: Path<Info>,
+    csrf: CsrfToken,
     renderer: Data<Renderer>,
     database: Data<Database>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
-    match user {
-        CurrentUser::Anonymous => Either::A(err(ErrorForbidden("Forbidden"))),
-        CurrentUser::Authenticated(_) => Either::B(
-            crate::action::retrieve::retrieve(&database, info.page)
-                .map_err(ErrorInternalServerError)
-                .and_then(move |page| render(page, &renderer)),
-        ),
-    }
+    let page = info.page;
+
+    authorize(
+        &access,
+        &database,
+        page,
+        Permission::DeleteAnyPage,
+        Permission::DeleteOwnPage,
+    )
+    .and_then(move |_| {
+        crate::action::retrieve::retrieve(&database, page).map_err(ErrorInternalServerError)
+    })
+    .and_then(move |page| render(page, csrf, &renderer, None))
 }
 
 pub fn post(
-    user: CurrentUser,
+    access: WebGuard<Authenticated>,
     info: Path<Info>,
+    data: Csrf,
+    renderer: Data<Renderer>,
     database: Data<Database>,
-) -> impl Future<Item = HttpRedirect, Error = Error> {
-    match user {
-        CurrentUser::Anonymous => Either::A(err(ErrorForbidden("Forbidden"))),
-        CurrentUser::Authenticated(_) => Either::B(
-            crate::action::delete::delete(&database, info.page)
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    let page = info.page;
+
+    authorize(
+        &access,
+        &database,
+        page,
+        Permission::DeleteAnyPage,
+        Permission::DeleteOwnPage,
+    )
+    .and_then(move |_| {
+        if !data.is_valid() {
+            return Either::A(
+                crate::action::retrieve::retrieve(&database, page)
+                    .map_err(ErrorInternalServerError)
+                    .and_then(move |page| {
+                        render(page, data.token(), &renderer, Some(REJECTED_MESSAGE))
+                    })
+                    .map(forbidden),
+            );
+        }
+
+        Either::B(
+            crate::action::delete::delete(&database, page)
                 .map_err(ErrorInternalServerError)
-                .and_then(|_| HttpRedirect::to("/pages/")),
-        ),
-    }
+                .map(|_| HttpRedirect::to("/pages/").into_response()),
+        )
+    })
 }
 
-fn render(page: Page, renderer: &Renderer) -> impl Future<Item = HttpResponse, Error = Error> {
+fn render(
+    page: Page,
+    csrf: CsrfToken,
+    renderer: &Renderer,
+    error: Option<&'static str>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
     let template = Template::new(
         "form-confirm",
         json!({
             "title": format!("Delete page <em>{}</em>?", page.title),
             "message": format!("Are you sure that you want to delete the page <em>{}</em>?", page.title),
+            "error": error,
+            "csrf_token": csrf,
         }),
     );
 
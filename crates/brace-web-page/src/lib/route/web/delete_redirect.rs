@@ -0,0 +1,68 @@
+use actix_web::error::{Error, ErrorInternalServerError};
+use actix_web::web::{Data, Path};
+use actix_web::HttpResponse;
+use brace_db::Database;
+use brace_web::redirect::HttpRedirect;
+use brace_web::render::{Renderer, Template};
+use brace_web_auth::guard::{EditAnyPage, WebGuard};
+use brace_web_form::csrf::{forbidden, REJECTED_MESSAGE};
+use brace_web_form::{Csrf, CsrfToken};
+use futures::future::{Either, Future};
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+pub fn get(
+    _: WebGuard<EditAnyPage>,
+    csrf: CsrfToken,
+    renderer: Data<Renderer>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    render(csrf, &renderer, None)
+}
+
+pub fn post(
+    _: WebGuard<EditAnyPage>,
+    info: Path<Info>,
+    data: Csrf,
+    renderer: Data<Renderer>,
+    database: Data<Database>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    if !data.is_valid() {
+        return Either::A(render(data.token(), &renderer, Some(REJECTED_MESSAGE)).map(forbidden));
+    }
+
+    Either::B(
+        crate::action::delete_redirect::delete_redirect(&database, info.redirect)
+            .map_err(ErrorInternalServerError)
+            .map(|_| HttpRedirect::to("/pages/redirects/").into_response()),
+    )
+}
+
+fn render(
+    csrf: CsrfToken,
+    renderer: &Renderer,
+    error: Option<&'static str>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    let template = Template::new(
+        "form-confirm",
+        json!({
+            "title": "Delete redirect?",
+            "message": "Are you sure that you want to delete this redirect? Links to the old path will stop working.",
+            "error": error,
+            "csrf_token": csrf,
+        }),
+    );
+
+    renderer
+        .send(template)
+        .map_err(ErrorInternalServerError)
+        .and_then(|res| match res {
+            Ok(body) => Ok(HttpResponse::Ok().content_type("text/html").body(body)),
+            Err(err) => Err(ErrorInternalServerError(err)),
+        })
+}
+
+#[derive(Deserialize)]
+pub struct Info {
+    redirect: Uuid,
+}
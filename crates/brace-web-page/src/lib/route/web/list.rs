@@ -1,38 +1,85 @@
-use actix_web::error::{Error, ErrorForbidden, ErrorInternalServerError};
+use std::collections::HashMap;
+
+use actix_web::error::{Error, ErrorInternalServerError};
 use actix_web::web::Data;
-use actix_web::HttpResponse;
+use actix_web::{HttpRequest, HttpResponse};
 use brace_db::Database;
+use brace_web::extract::Query;
+use brace_web::pager::{Pager, Pagination};
 use brace_web::render::{Renderer, Template};
-use brace_web_auth::model::CurrentUser;
+use brace_web_auth::guard::{Authenticated, WebGuard};
+use brace_web_auth::model::Permission;
 use futures::future::{err, Either, Future};
 use serde_json::json;
+use uuid::Uuid;
 
-use crate::model::PageWithPath;
+use crate::access::restrict;
+use crate::model::{PageFilter, PageWithPath};
 
 pub fn get(
-    user: CurrentUser,
+    access: WebGuard<Authenticated>,
+    req: HttpRequest,
+    filter: Query<PageFilter>,
+    pager: Query<Pager>,
     renderer: Data<Renderer>,
     database: Data<Database>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
-    match user {
-        CurrentUser::Anonymous => Either::A(err(ErrorForbidden("Forbidden"))),
-        CurrentUser::Authenticated(_) => Either::B(
-            crate::action::list::list(&database)
-                .map_err(ErrorInternalServerError)
-                .and_then(move |pages| render(pages, &renderer)),
-        ),
-    }
+    let mut filter = filter.into_inner();
+    let pager = pager.into_inner();
+    let path = req.path().to_owned();
+
+    filter.created_by = match restrict(&access, Permission::EditAnyPage, Permission::EditOwnPage) {
+        Ok(owner) => owner,
+        Err(e) => return Either::A(err(e)),
+    };
+
+    Either::B(
+        crate::action::list::list(&database, filter.clone(), pager)
+            .and_then(move |(pages, total)| {
+                let users = pages
+                    .iter()
+                    .flat_map(|page| page.created_by.into_iter().chain(page.updated_by))
+                    .collect();
+
+                brace_web_auth::action::names::names(&database, users)
+                    .map(move |authors| (pages, total, authors))
+            })
+            .map_err(ErrorInternalServerError)
+            .and_then(move |(pages, total, authors)| {
+                render(
+                    pages,
+                    authors,
+                    pager.paginate(&path, &filter, total),
+                    &renderer,
+                )
+            }),
+    )
 }
 
 fn render(
     pages: Vec<PageWithPath>,
+    authors: HashMap<Uuid, String>,
+    pager: Pagination,
     renderer: &Renderer,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
+    let user = |id: Option<Uuid>| id.and_then(|id| authors.get(&id).cloned());
+    let pages = pages
+        .into_iter()
+        .map(|page| {
+            json!({
+                "created_by": user(page.created_by),
+                "updated_by": user(page.updated_by),
+                "page": page,
+            })
+        })
+        .collect::<Vec<_>>();
+
     let template = Template::new(
         "page-list",
         json!({
             "title": "Pages",
             "pages": pages,
+            "pager": pager,
         }),
     );
 
@@ -0,0 +1,41 @@
+use actix_web::error::{Error, ErrorInternalServerError};
+use actix_web::web::Data;
+use actix_web::HttpResponse;
+use brace_db::Database;
+use brace_web::render::{Renderer, Template};
+use brace_web_auth::guard::{EditAnyPage, WebGuard};
+use futures::future::Future;
+use serde_json::json;
+
+use crate::model::PageRedirect;
+
+pub fn get(
+    _: WebGuard<EditAnyPage>,
+    renderer: Data<Renderer>,
+    database: Data<Database>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    crate::action::list_redirects::list_redirects(&database)
+        .map_err(ErrorInternalServerError)
+        .and_then(move |redirects| render(redirects, &renderer))
+}
+
+fn render(
+    redirects: Vec<PageRedirect>,
+    renderer: &Renderer,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    let template = Template::new(
+        "page-redirects",
+        json!({
+            "title": "Redirects",
+            "redirects": redirects,
+        }),
+    );
+
+    renderer
+        .send(template)
+        .map_err(ErrorInternalServerError)
+        .and_then(|res| match res {
+            Ok(body) => Ok(HttpResponse::Ok().content_type("text/html").body(body)),
+            Err(err) => Err(ErrorInternalServerError(err)),
+        })
+}
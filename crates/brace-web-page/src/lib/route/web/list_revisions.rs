@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+use actix_web::error::{Error, ErrorInternalServerError};
+use actix_web::web::{Data, Path};
+use actix_web::HttpResponse;
+use brace_db::Database;
+use brace_web::render::{Renderer, Template};
+use brace_web_auth::guard::{Authenticated, WebGuard};
+use brace_web_auth::model::Permission;
+use futures::future::Future;
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::access::authorize;
+use crate::model::{Page, PageRevision};
+
+pub fn get(
+    access: WebGuard<Authenticated>,
+    info: Path<Info>,
+    renderer: Data<Renderer>,
+    database: Data<Database>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    let page = info.page;
+
+    authorize(
+        &access,
+        &database,
+        page,
+        Permission::EditAnyPage,
+        Permission::EditOwnPage,
+    )
+    .and_then(move |_| {
+        crate::action::retrieve::retrieve(&database, page)
+            .join(crate::action::list_revisions::list_revisions(
+                &database, page,
+            ))
+            .and_then(move |(page, revisions)| {
+                let users = revisions
+                    .iter()
+                    .filter_map(|revision| revision.author)
+                    .collect();
+
+                brace_web_auth::action::names::names(&database, users)
+                    .map(move |authors| (page, revisions, authors))
+            })
+            .map_err(ErrorInternalServerError)
+    })
+    .and_then(move |(page, revisions, authors)| render(page, revisions, authors, &renderer))
+}
+
+fn render(
+    page: Page,
+    revisions: Vec<PageRevision>,
+    authors: HashMap<Uuid, String>,
+    renderer: &Renderer,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    let revisions = revisions
+        .into_iter()
+        .map(|revision| {
+            let author = revision
+                .author
+                .and_then(|author| authors.get(&author).cloned());
+
+            json!({
+                "revision": revision,
+                "author": author,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let template = Template::new(
+        "page-revisions",
+        json!({
+            "title": format!("Revisions of <em>{}</em>", page.title),
+            "page": page,
+            "revisions": revisions,
+        }),
+    );
+
+    renderer
+        .send(template)
+        .map_err(ErrorInternalServerError)
+        .and_then(|res| match res {
+            Ok(body) => Ok(HttpResponse::Ok().content_type("text/html").body(body)),
+            Err(err) => Err(ErrorInternalServerError(err)),
+        })
+}
+
+#[derive(Deserialize)]
+pub struct Info {
+    page: Uuid,
+}
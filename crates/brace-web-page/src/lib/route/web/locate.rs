@@ -1,33 +1,109 @@
-use actix_web::error::{Error, ErrorInternalServerError};
+use actix_web::error::{Error, ErrorForbidden, ErrorInternalServerError, ErrorNotFound};
 use actix_web::web::Data;
 use actix_web::{HttpRequest, HttpResponse};
 use brace_db::Database;
+use brace_web::redirect::HttpRedirect;
 use brace_web::render::{Renderer, Template};
-use futures::future::Future;
+use brace_web_auth::guard::Access;
+use brace_web_auth::model::{CurrentUser, Permission};
+use futures::future::{err, ok, Either, Future};
 use serde_json::json;
 
+use crate::access::owns;
+use crate::model::Page;
+
 pub fn get(
     req: HttpRequest,
+    user: CurrentUser,
     database: Data<Database>,
     renderer: Data<Renderer>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
-    crate::action::locate::locate(&database, req.match_info().path().to_owned())
+    let path = req.match_info().path().to_owned();
+
+    crate::action::locate::locate(&database, path.clone()).then(move |res| match res {
+        Ok((page, visible)) => Either::A(
+            preview(&page, visible, user, &database)
+                .and_then(move |preview| render(page, preview, &renderer)),
+        ),
+        Err(_) => Either::B(redirect(&req, path, &database)),
+    })
+}
+
+fn preview(
+    page: &Page,
+    visible: bool,
+    user: CurrentUser,
+    database: &Database,
+) -> impl Future<Item = bool, Error = Error> {
+    if visible && page.is_published() {
+        return Either::A(ok(false));
+    }
+
+    let user = match user {
+        CurrentUser::Authenticated(user) => user,
+        CurrentUser::Anonymous => return Either::A(err(ErrorNotFound("Page not found"))),
+    };
+    let owner = page.created_by;
+
+    Either::B(
+        brace_web_auth::action::permissions::permissions(database, user.id)
+            .map_err(ErrorInternalServerError)
+            .and_then(move |permissions| {
+                let access = Access { user, permissions };
+
+                if owns(
+                    &access,
+                    owner,
+                    Permission::EditAnyPage,
+                    Permission::EditOwnPage,
+                ) {
+                    Ok(true)
+                } else {
+                    Err(ErrorForbidden("Forbidden"))
+                }
+            }),
+    )
+}
+
+fn render(
+    page: Page,
+    preview: bool,
+    renderer: &Renderer,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    let template = Template::new(
+        "page",
+        json!({
+            "title": page.title,
+            "page": page,
+            "preview": preview,
+        }),
+    );
+
+    renderer
+        .send(template)
         .map_err(ErrorInternalServerError)
-        .and_then(move |page| {
-            let template = Template::new(
-                "page",
-                json!({
-                    "title": page.title,
-                    "page": page,
-                }),
-            );
-
-            renderer
-                .send(template)
-                .map_err(ErrorInternalServerError)
-                .and_then(move |res| match res {
-                    Ok(body) => Ok(HttpResponse::Ok().content_type("text/html").body(body)),
-                    Err(err) => Err(ErrorInternalServerError(err)),
-                })
+        .and_then(move |res| match res {
+            Ok(body) => Ok(HttpResponse::Ok().content_type("text/html").body(body)),
+            Err(err) => Err(ErrorInternalServerError(err)),
+        })
+}
+
+fn redirect(
+    req: &HttpRequest,
+    path: String,
+    database: &Database,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    let query = req.query_string().to_owned();
+
+    crate::action::locate_redirect::locate_redirect(database, path)
+        .map_err(ErrorNotFound)
+        .map(move |redirect| {
+            let location = if query.is_empty() {
+                redirect.location
+            } else {
+                format!("{}?{}", redirect.location, query)
+            };
+
+            HttpRedirect::moved(location).into_response()
         })
 }
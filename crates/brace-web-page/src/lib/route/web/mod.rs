@@ -4,9 +4,14 @@ use crate::router::PageRouter;
 
 pub mod create;
 pub mod delete;
+pub mod delete_redirect;
 pub mod list;
+pub mod list_redirects;
+pub mod list_revisions;
 pub mod locate;
 pub mod retrieve;
+pub mod retrieve_revision;
+pub mod revert_revision;
 pub mod update;
 
 pub fn config(conf: &mut ServiceConfig) {
@@ -18,6 +23,12 @@ pub fn config(conf: &mut ServiceConfig) {
                     .route(web::get().to_async(create::get))
                     .route(web::post().to_async(create::post)),
             )
+            .service(web::resource("/redirects/").route(web::get().to_async(list_redirects::get)))
+            .service(
+                web::resource("/redirects/{redirect}/delete")
+                    .route(web::get().to_async(delete_redirect::get))
+                    .route(web::post().to_async(delete_redirect::post)),
+            )
             .service(web::resource("/{page}").route(web::get().to_async(retrieve::get)))
             .service(
                 web::resource("/{page}/update")
@@ -28,6 +39,18 @@ pub fn config(conf: &mut ServiceConfig) {
                 web::resource("/{page}/delete")
                     .route(web::get().to_async(delete::get))
                     .route(web::post().to_async(delete::post)),
+            )
+            .service(
+                web::resource("/{page}/revisions").route(web::get().to_async(list_revisions::get)),
+            )
+            .service(
+                web::resource("/{page}/revisions/{revision}")
+                    .route(web::get().to_async(retrieve_revision::get)),
+            )
+            .service(
+                web::resource("/{page}/revisions/{revision}/revert")
+                    .route(web::get().to_async(revert_revision::get))
+                    .route(web::post().to_async(revert_revision::post)),
             ),
     )
     .service(PageRouter::new("/"));
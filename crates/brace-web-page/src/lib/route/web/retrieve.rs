@@ -1,25 +1,34 @@
-use actix_web::error::{Error, ErrorForbidden, ErrorInternalServerError};
+use actix_web::error::{Error, ErrorInternalServerError};
 use actix_web::web::{Data, Path};
 use brace_db::Database;
 use brace_web::redirect::HttpRedirect;
-use brace_web_auth::model::CurrentUser;
-use futures::future::{err, Either, Future};
+use brace_web_auth::guard::{Authenticated, WebGuard};
+use brace_web_auth::model::Permission;
+use futures::future::Future;
 use serde::Deserialize;
 use uuid::Uuid;
 
+use crate::access::authorize;
+
 pub fn get(
-    user: CurrentUser,
+    access: WebGuard<Authenticated>,
     info: Path<Info>,
     database: Data<Database>,
 ) -> impl Future<Item = HttpRedirect, Error = Error> {
-    match user {
-        CurrentUser::Anonymous => Either::A(err(ErrorForbidden("Forbidden"))),
-        CurrentUser::Authenticated(_) => Either::B(
-            crate::action::retrieve_path::retrieve_path(&database, info.page)
-                .map_err(ErrorInternalServerError)
-                .and_then(HttpRedirect::found),
-        ),
-    }
+    let page = info.page;
+
+    authorize(
+        &access,
+        &database,
+        page,
+        Permission::EditAnyPage,
+        Permission::EditOwnPage,
+    )
+    .and_then(move |_| {
+        crate::action::retrieve_path::retrieve_path(&database, page)
+            .map_err(ErrorInternalServerError)
+    })
+    .and_then(HttpRedirect::found)
 }
 
 #[derive(Deserialize)]
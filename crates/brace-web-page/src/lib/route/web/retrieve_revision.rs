@@ -0,0 +1,72 @@
+use actix_web::error::{Error, ErrorInternalServerError, ErrorNotFound};
+use actix_web::web::{Data, Path};
+use actix_web::HttpResponse;
+use brace_db::Database;
+use brace_web::render::{Renderer, Template};
+use brace_web_auth::guard::{Authenticated, WebGuard};
+use brace_web_auth::model::Permission;
+use futures::future::{err, Either, Future};
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::access::authorize;
+use crate::diff::{diff, previous};
+use crate::model::PageRevision;
+
+pub fn get(
+    access: WebGuard<Authenticated>,
+    info: Path<Info>,
+    renderer: Data<Renderer>,
+    database: Data<Database>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    let (page, revision) = (info.page, info.revision);
+
+    authorize(
+        &access,
+        &database,
+        page,
+        Permission::EditAnyPage,
+        Permission::EditOwnPage,
+    )
+    .and_then(move |_| {
+        crate::action::list_revisions::list_revisions(&database, page)
+            .map_err(ErrorInternalServerError)
+    })
+    .and_then(
+        move |revisions| match revisions.iter().position(|item| item.id == revision) {
+            Some(index) => Either::A(render(index, revisions, &renderer)),
+            None => Either::B(err(ErrorNotFound("Not Found"))),
+        },
+    )
+}
+
+fn render(
+    index: usize,
+    revisions: Vec<PageRevision>,
+    renderer: &Renderer,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    let revision = &revisions[index];
+    let template = Template::new(
+        "page-revision",
+        json!({
+            "title": format!("Revision {} of <em>{}</em>", revision.revision, revision.title),
+            "revision": revision,
+            "diff": diff(previous(&revisions, revision), revision),
+        }),
+    );
+
+    renderer
+        .send(template)
+        .map_err(ErrorInternalServerError)
+        .and_then(|res| match res {
+            Ok(body) => Ok(HttpResponse::Ok().content_type("text/html").body(body)),
+            Err(err) => Err(ErrorInternalServerError(err)),
+        })
+}
+
+#[derive(Deserialize)]
+pub struct Info {
+    page: Uuid,
+    revision: Uuid,
+}
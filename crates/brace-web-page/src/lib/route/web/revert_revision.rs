@@ -0,0 +1,116 @@
+use actix_web::error::{Error, ErrorConflict, ErrorInternalServerError};
+use actix_web::web::{Data, Path};
+use actix_web::HttpResponse;
+use brace_db::Database;
+use brace_web::redirect::HttpRedirect;
+use brace_web::render::{Renderer, Template};
+use brace_web_auth::guard::{Authenticated, WebGuard};
+use brace_web_auth::model::Permission;
+use brace_web_form::csrf::{forbidden, REJECTED_MESSAGE};
+use brace_web_form::{Csrf, CsrfToken};
+use futures::future::{Either, Future};
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::access::authorize;
+use crate::action::tree::TreeError;
+use crate::model::PageRevision;
+
+pub fn get(
+    access: WebGuard<Authenticated>,
+    info: Path<Info>,
+    csrf: CsrfToken,
+    renderer: Data<Renderer>,
+    database: Data<Database>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    let (page, revision) = (info.page, info.revision);
+
+    authorize(
+        &access,
+        &database,
+        page,
+        Permission::EditAnyPage,
+        Permission::EditOwnPage,
+    )
+    .and_then(move |_| {
+        crate::action::retrieve_revision::retrieve_revision(&database, page, revision)
+            .map_err(ErrorInternalServerError)
+    })
+    .and_then(move |revision| render(revision, csrf, &renderer, None))
+}
+
+pub fn post(
+    access: WebGuard<Authenticated>,
+    info: Path<Info>,
+    data: Csrf,
+    renderer: Data<Renderer>,
+    database: Data<Database>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    let (page, revision, user) = (info.page, info.revision, access.user.id);
+
+    authorize(
+        &access,
+        &database,
+        page,
+        Permission::EditAnyPage,
+        Permission::EditOwnPage,
+    )
+    .and_then(move |_| {
+        if !data.is_valid() {
+            return Either::A(
+                crate::action::retrieve_revision::retrieve_revision(&database, page, revision)
+                    .map_err(ErrorInternalServerError)
+                    .and_then(move |revision| {
+                        render(revision, data.token(), &renderer, Some(REJECTED_MESSAGE))
+                    })
+                    .map(forbidden),
+            );
+        }
+
+        Either::B(
+            crate::action::revert_revision::revert_revision(&database, page, revision, Some(user))
+                .map_err(|e| match e.downcast::<TreeError>() {
+                    Ok(e) => ErrorConflict(e),
+                    Err(e) => ErrorInternalServerError(e),
+                })
+                .map(|page| {
+                    HttpRedirect::to(format!("/pages/{}/revisions", page.id)).into_response()
+                }),
+        )
+    })
+}
+
+fn render(
+    revision: PageRevision,
+    csrf: CsrfToken,
+    renderer: &Renderer,
+    error: Option<&'static str>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    let template = Template::new(
+        "form-confirm",
+        json!({
+            "title": format!("Revert page <em>{}</em>?", revision.title),
+            "message": format!(
+                "Are you sure that you want to revert the page to revision {}?",
+                revision.revision,
+            ),
+            "error": error,
+            "csrf_token": csrf,
+        }),
+    );
+
+    renderer
+        .send(template)
+        .map_err(ErrorInternalServerError)
+        .and_then(|res| match res {
+            Ok(body) => Ok(HttpResponse::Ok().content_type("text/html").body(body)),
+            Err(err) => Err(ErrorInternalServerError(err)),
+        })
+}
+
+#[derive(Deserialize)]
+pub struct Info {
+    page: Uuid,
+    revision: Uuid,
+}
@@ -1,91 +1,167 @@
-use actix_web::error::{Error, ErrorForbidden, ErrorInternalServerError};
-use actix_web::web::{Data, Form as FormExtractor, Path};
+use actix_web::error::{Error, ErrorBadRequest, ErrorInternalServerError};
+use actix_web::web::{Data, Path};
 use actix_web::HttpResponse;
 use brace_db::Database;
 use brace_web::redirect::HttpRedirect;
 use brace_web::render::{Renderer, Template};
-use brace_web_auth::model::CurrentUser;
-use brace_web_form::{Form, FormData};
-use futures::future::{err, Either, Future};
+use brace_web_auth::guard::{Authenticated, WebGuard};
+use brace_web_auth::model::Permission;
+use brace_web_form::csrf::{forbidden, REJECTED_MESSAGE};
+use brace_web_form::{CsrfForm, FieldErrors, Form, FormData};
+use futures::future::{err, ok, Either, Future};
 use serde::Deserialize;
 use serde_json::json;
 use uuid::Uuid;
 
-use crate::form::page::PageForm;
+use crate::access::authorize;
+use crate::action::tree::TreeError;
+use crate::form::page::{rules, PageForm};
 use crate::model::Page;
 
 pub fn get(
-    user: CurrentUser,
+    access: WebGuard<Authenticated>,
     info: Path<Info>,
+    form: Form,
     renderer: Data<Renderer>,
     database: Data<Database>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
-    match user {
-        CurrentUser::Anonymous => Either::A(err(ErrorForbidden("Forbidden"))),
-        CurrentUser::Authenticated(_) => Either::B(
-            crate::action::retrieve::retrieve(&database, info.page)
-                .map_err(ErrorInternalServerError)
-                .and_then(move |page| render(page, database, renderer)),
-        ),
-    }
+    let page = info.page;
+
+    authorize(
+        &access,
+        &database,
+        page,
+        Permission::EditAnyPage,
+        Permission::EditOwnPage,
+    )
+    .and_then(move |_| {
+        crate::action::retrieve::retrieve(&database, page)
+            .and_then(FormData::with)
+            .map_err(ErrorInternalServerError)
+            .and_then(move |data| render(data, form, database, renderer, FieldErrors::new(), None))
+    })
 }
 
 pub fn post(
-    user: CurrentUser,
-    page: FormExtractor<Page>,
+    access: WebGuard<Authenticated>,
+    data: CsrfForm<FormData>,
+    form: Form,
     database: Data<Database>,
-) -> impl Future<Item = HttpRedirect, Error = Error> {
-    match user {
-        CurrentUser::Anonymous => Either::A(err(ErrorForbidden("Forbidden"))),
-        CurrentUser::Authenticated(_) => Either::B(
-            crate::action::update::update(&database, page.into_inner())
-                .map_err(ErrorInternalServerError)
-                .and_then(|page| HttpRedirect::to(format!("/pages/{}", page.id))),
-        ),
-    }
+    renderer: Data<Renderer>,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    let valid = data.is_valid();
+    let data = data.into_inner();
+    let user = access.user.id;
+
+    let page = match data.get::<Uuid>("id") {
+        Ok(page) => page,
+        Err(e) => return Either::A(err(ErrorBadRequest(e))),
+    };
+
+    Either::B(
+        authorize(
+            &access,
+            &database,
+            page,
+            Permission::EditAnyPage,
+            Permission::EditOwnPage,
+        )
+        .and_then(move |_| {
+            if !valid {
+                return Either::A(
+                    render(
+                        data,
+                        form,
+                        database,
+                        renderer,
+                        FieldErrors::new(),
+                        Some(REJECTED_MESSAGE.to_string()),
+                    )
+                    .map(forbidden),
+                );
+            }
+
+            Either::B(
+                rules()
+                    .validate(&data)
+                    .map_err(ErrorInternalServerError)
+                    .and_then(move |errors| {
+                        if !errors.is_empty() {
+                            return Either::A(render(data, form, database, renderer, errors, None));
+                        }
+
+                        let page = match data.parse::<Page>() {
+                            Ok(page) => page,
+                            Err(e) => return Either::B(Either::A(err(ErrorBadRequest(e)))),
+                        };
+
+                        Either::B(Either::B(
+                            crate::action::update::update(&database, page, Some(user)).then(
+                                move |res| match res {
+                                    Ok(page) => Either::A(ok(HttpRedirect::to(format!(
+                                        "/pages/{}",
+                                        page.id
+                                    ))
+                                    .into_response())),
+                                    Err(e) => match e.downcast::<TreeError>() {
+                                        Ok(e) => {
+                                            let mut errors = FieldErrors::new();
+
+                                            errors.insert(e.field(), e.to_string());
+
+                                            Either::B(render(
+                                                data, form, database, renderer, errors, None,
+                                            ))
+                                        }
+                                        Err(e) => Either::A(err(ErrorInternalServerError(e))),
+                                    },
+                                },
+                            ),
+                        ))
+                    }),
+            )
+        }),
+    )
 }
 
 fn render(
-    page: Page,
+    data: FormData,
+    form: Form,
     database: Data<Database>,
     renderer: Data<Renderer>,
+    errors: FieldErrors,
+    message: Option<String>,
 ) -> impl Future<Item = HttpResponse, Error = Error> {
-    let title = format!("Update page <em>{}</em>", page.title);
+    let title = format!(
+        "Update page <em>{}</em>",
+        data.get::<String>("title").unwrap_or_default()
+    );
+    let mut form = form.with(data).errors(errors);
 
-    match FormData::with(page) {
-        Ok(data) => {
-            let mut form = Form::new(()).with(data);
+    form.builder(PageForm {
+        database: (*database).clone(),
+    });
 
-            form.builder(PageForm {
-                database: (*database).clone(),
-            });
+    form.build()
+        .map_err(ErrorInternalServerError)
+        .and_then(move |form| {
+            let template = Template::new(
+                "form-layout",
+                json!({
+                    "title": title,
+                    "message": message,
+                    "form": form,
+                }),
+            );
 
-            Either::A(
-                form.build()
-                    .map_err(ErrorInternalServerError)
-                    .and_then(move |form| {
-                        let template = Template::new(
-                            "form-layout",
-                            json!({
-                                "title": title,
-                                "form": form,
-                            }),
-                        );
-
-                        renderer
-                            .send(template)
-                            .map_err(ErrorInternalServerError)
-                            .and_then(|res| match res {
-                                Ok(body) => {
-                                    Ok(HttpResponse::Ok().content_type("text/html").body(body))
-                                }
-                                Err(err) => Err(ErrorInternalServerError(err)),
-                            })
-                    }),
-            )
-        }
-        Err(e) => Either::B(err(ErrorInternalServerError(e))),
-    }
+            renderer
+                .send(template)
+                .map_err(ErrorInternalServerError)
+                .and_then(|res| match res {
+                    Ok(body) => Ok(HttpResponse::Ok().content_type("text/html").body(body)),
+                    Err(err) => Err(ErrorInternalServerError(err)),
+                })
+        })
 }
 
 #[derive(Deserialize)]
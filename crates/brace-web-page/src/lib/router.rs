@@ -7,8 +7,10 @@ use actix_web::dev::{
     AppService, HttpServiceFactory, ResourceDef, ServiceRequest, ServiceResponse,
 };
 use actix_web::error::{Error, ErrorNotFound};
+use actix_web::FromRequest;
 use brace_db::Database;
 use brace_web::render::Renderer;
+use brace_web_auth::model::CurrentUser;
 use futures::future::{ok, Either, Future, FutureResult};
 use futures::{Async, Poll};
 
@@ -125,10 +127,14 @@ impl Service for PageRouterService {
 
         if let Some(database) = database {
             if let Some(renderer) = renderer {
-                let (req, _) = req.into_parts();
+                let (req, mut payload) = req.into_parts();
+                let request = req.clone();
 
                 return Either::B(Box::new(
-                    crate::route::web::locate::get(req.clone(), database, renderer)
+                    CurrentUser::from_request(&req, &mut payload)
+                        .and_then(move |user| {
+                            crate::route::web::locate::get(request, user, database, renderer)
+                        })
                         .map_err(ErrorNotFound)
                         .then(move |res| match res {
                             Ok(res) => ServiceResponse::new(req, res),
@@ -0,0 +1,143 @@
+use brace_db::Migration;
+
+static CREATE_PAGES_UP: &str = r#"
+    CREATE TABLE IF NOT EXISTS pages (
+        id uuid PRIMARY KEY,
+        parent uuid REFERENCES pages(id),
+        slug character varying(255) NOT NULL,
+        title text NOT NULL CHECK (title <> ''),
+        description text NOT NULL DEFAULT '',
+        document jsonb NOT NULL DEFAULT '{}'::jsonb,
+        created timestamp with time zone NOT NULL DEFAULT now(),
+        updated timestamp with time zone NOT NULL DEFAULT now() CHECK (updated >= created)
+    )
+"#;
+
+static CREATE_PAGES_DOWN: &str = r#"
+    DROP TABLE pages
+"#;
+
+static CREATE_PAGE_REVISIONS_UP: &str = r#"
+    CREATE TABLE IF NOT EXISTS page_revisions (
+        id uuid PRIMARY KEY,
+        page uuid NOT NULL REFERENCES pages(id) ON DELETE CASCADE,
+        revision integer NOT NULL CHECK (revision > 0),
+        parent uuid,
+        slug character varying(255) NOT NULL,
+        title text NOT NULL,
+        description text NOT NULL DEFAULT '',
+        document jsonb NOT NULL DEFAULT '{}'::jsonb,
+        author uuid,
+        created timestamp with time zone NOT NULL DEFAULT now(),
+        UNIQUE (page, revision)
+    )
+"#;
+
+static CREATE_PAGE_REVISIONS_DOWN: &str = r#"
+    DROP TABLE page_revisions
+"#;
+
+static ADD_PAGE_STATUS_UP: &str = r#"
+    ALTER TABLE pages
+        ADD COLUMN status character varying(16) NOT NULL DEFAULT 'published' CHECK (status IN ('draft', 'published', 'archived')),
+        ADD COLUMN publish_at timestamp with time zone,
+        ADD COLUMN unpublish_at timestamp with time zone CHECK (unpublish_at > publish_at)
+"#;
+
+static ADD_PAGE_STATUS_DOWN: &str = r#"
+    ALTER TABLE pages
+        DROP COLUMN status,
+        DROP COLUMN publish_at,
+        DROP COLUMN unpublish_at
+"#;
+
+static CREATE_PAGE_REDIRECTS_UP: &str = r#"
+    CREATE TABLE IF NOT EXISTS page_redirects (
+        id uuid PRIMARY KEY,
+        path text NOT NULL UNIQUE CHECK (path <> ''),
+        page uuid NOT NULL REFERENCES pages(id) ON DELETE CASCADE,
+        created timestamp with time zone NOT NULL DEFAULT now()
+    )
+"#;
+
+static CREATE_PAGE_REDIRECTS_DOWN: &str = r#"
+    DROP TABLE page_redirects
+"#;
+
+static ADD_PAGE_SLUG_INDEXES_UP: &str = r#"
+    CREATE UNIQUE INDEX pages_parent_slug_key ON pages (parent, slug) WHERE parent IS NOT NULL;
+    CREATE UNIQUE INDEX pages_root_slug_key ON pages (slug) WHERE parent IS NULL
+"#;
+
+static ADD_PAGE_SLUG_INDEXES_DOWN: &str = r#"
+    DROP INDEX pages_parent_slug_key;
+    DROP INDEX pages_root_slug_key
+"#;
+
+static ADD_PAGE_AUTHORS_UP: &str = r#"
+    ALTER TABLE pages
+        ADD COLUMN created_by uuid,
+        ADD COLUMN updated_by uuid;
+
+    UPDATE pages p
+    SET created_by = (
+        SELECT author
+        FROM page_revisions
+        WHERE page = p.id
+        ORDER BY revision
+        LIMIT 1
+    ), updated_by = (
+        SELECT author
+        FROM page_revisions
+        WHERE page = p.id
+        ORDER BY revision DESC
+        LIMIT 1
+    )
+"#;
+
+static ADD_PAGE_AUTHORS_DOWN: &str = r#"
+    ALTER TABLE pages
+        DROP COLUMN created_by,
+        DROP COLUMN updated_by
+"#;
+
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        Migration::new(
+            "page",
+            "0001_create_pages",
+            CREATE_PAGES_UP,
+            CREATE_PAGES_DOWN,
+        ),
+        Migration::new(
+            "page",
+            "0002_create_page_revisions",
+            CREATE_PAGE_REVISIONS_UP,
+            CREATE_PAGE_REVISIONS_DOWN,
+        ),
+        Migration::new(
+            "page",
+            "0003_add_page_status",
+            ADD_PAGE_STATUS_UP,
+            ADD_PAGE_STATUS_DOWN,
+        ),
+        Migration::new(
+            "page",
+            "0004_create_page_redirects",
+            CREATE_PAGE_REDIRECTS_UP,
+            CREATE_PAGE_REDIRECTS_DOWN,
+        ),
+        Migration::new(
+            "page",
+            "0005_add_page_slug_indexes",
+            ADD_PAGE_SLUG_INDEXES_UP,
+            ADD_PAGE_SLUG_INDEXES_DOWN,
+        ),
+        Migration::new(
+            "page",
+            "0006_add_page_authors",
+            ADD_PAGE_AUTHORS_UP,
+            ADD_PAGE_AUTHORS_DOWN,
+        ),
+    ]
+}
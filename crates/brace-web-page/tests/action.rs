@@ -1,14 +1,29 @@
 use actix::System;
 use brace_db::{Database, DatabaseConfig};
+use brace_web::pager::{Order, Pager};
+use brace_web_auth::action::create::create as create_user;
+use brace_web_auth::action::delete::delete as delete_user;
+use brace_web_auth::action::install::install as install_users;
+use brace_web_auth::action::names::names;
+use brace_web_auth::action::uninstall::uninstall as uninstall_users;
+use brace_web_auth::model::User;
+use brace_web_page::action::check::check;
 use brace_web_page::action::create::create;
 use brace_web_page::action::delete::delete;
+use brace_web_page::action::delete_redirect::delete_redirect;
 use brace_web_page::action::install::install;
 use brace_web_page::action::list::list;
+use brace_web_page::action::list_redirects::list_redirects;
+use brace_web_page::action::list_revisions::list_revisions;
 use brace_web_page::action::locate::locate;
+use brace_web_page::action::locate_redirect::locate_redirect;
 use brace_web_page::action::retrieve::retrieve;
+use brace_web_page::action::retrieve_revision::retrieve_revision;
+use brace_web_page::action::revert_revision::revert_revision;
+use brace_web_page::action::tree::TreeError;
 use brace_web_page::action::uninstall::uninstall;
 use brace_web_page::action::update::update;
-use brace_web_page::model::Page;
+use brace_web_page::model::{Page, PageFilter, PageSort, PageStatus};
 use chrono::Utc;
 use serde_json::json;
 use uuid::Uuid;
@@ -25,8 +40,13 @@ fn test_page_lifecycle() {
         title: "Foo".to_string(),
         description: "FOO".to_string(),
         document: json!({}),
+        status: PageStatus::Published,
+        publish_at: None,
+        unpublish_at: None,
         created: Utc::now(),
         updated: Utc::now(),
+        created_by: None,
+        updated_by: None,
     };
 
     let uuid2 = Uuid::new_v4();
@@ -37,22 +57,47 @@ fn test_page_lifecycle() {
         title: "Bar".to_string(),
         description: "BAR".to_string(),
         document: json!({}),
+        status: PageStatus::Published,
+        publish_at: None,
+        unpublish_at: None,
         created: Utc::now(),
         updated: Utc::now(),
+        created_by: None,
+        updated_by: None,
     };
 
+    system.block_on(install_users(&database)).unwrap();
     system.block_on(install(&database)).unwrap();
 
-    assert!(system.block_on(create(&database, page.clone())).is_ok());
-    assert!(system.block_on(create(&database, page.clone())).is_err());
-    assert!(system.block_on(create(&database, page2.clone())).is_ok());
-    assert!(system.block_on(update(&database, page.clone())).is_ok());
+    assert!(system
+        .block_on(create(&database, page.clone(), None))
+        .is_ok());
+    assert!(system
+        .block_on(create(&database, page.clone(), None))
+        .is_err());
+    assert!(system
+        .block_on(create(&database, page2.clone(), None))
+        .is_ok());
+    assert!(system
+        .block_on(update(&database, page.clone(), None))
+        .is_ok());
     assert!(system.block_on(retrieve(&database, uuid)).is_ok());
     assert!(system
         .block_on(retrieve(&database, Uuid::new_v4()))
         .is_err());
     assert!(system.block_on(locate(&database, "/foo")).is_ok());
-    assert!(system.block_on(locate(&database, "/foo/bar")).is_ok());
+    assert!(system.block_on(locate(&database, "/foo/bar")).unwrap().1);
+    assert!(system
+        .block_on(update(
+            &database,
+            Page {
+                status: PageStatus::Draft,
+                ..page.clone()
+            },
+            None
+        ))
+        .is_ok());
+    assert!(!system.block_on(locate(&database, "/foo/bar")).unwrap().1);
     assert!(system.block_on(locate(&database, "/bar")).is_err());
     assert!(system.block_on(delete(&database, uuid)).is_err());
     assert!(system.block_on(delete(&database, uuid2)).is_ok());
@@ -62,7 +107,7 @@ fn test_page_lifecycle() {
 
     assert_eq!(
         system
-            .block_on(create(&database, page.clone()))
+            .block_on(create(&database, page.clone(), None))
             .unwrap()
             .title,
         "Foo"
@@ -79,13 +124,18 @@ fn test_page_lifecycle() {
         title: "B".to_string(),
         description: "B".to_string(),
         document: json!({}),
+        status: PageStatus::Published,
+        publish_at: None,
+        unpublish_at: None,
         created: Utc::now(),
         updated: Utc::now(),
+        created_by: None,
+        updated_by: None,
     };
 
     assert_eq!(
         system
-            .block_on(update(&database, page.clone()))
+            .block_on(update(&database, page.clone(), None))
             .unwrap()
             .title,
         "B"
@@ -103,11 +153,476 @@ fn test_page_lifecycle() {
         title: "C".to_string(),
         description: "C".to_string(),
         document: json!({}),
+        status: PageStatus::Published,
+        publish_at: None,
+        unpublish_at: None,
         created: Utc::now(),
         updated: Utc::now(),
+        created_by: None,
+        updated_by: None,
+    };
+
+    assert!(system
+        .block_on(create(&database, page.clone(), None))
+        .is_ok());
+    let (pages, total) = system
+        .block_on(list(&database, PageFilter::default(), Pager::default()))
+        .unwrap();
+
+    assert_eq!(pages.len(), 2);
+    assert_eq!(total, 2);
+    assert_eq!(pages[0].path, "/b");
+
+    let filter = PageFilter {
+        status: Some(PageStatus::Published),
+        ..PageFilter::default()
+    };
+
+    assert_eq!(
+        system
+            .block_on(list(&database, filter, Pager::default()))
+            .unwrap()
+            .1,
+        2
+    );
+
+    let filter = PageFilter {
+        status: Some(PageStatus::Draft),
+        ..PageFilter::default()
+    };
+
+    assert_eq!(
+        system
+            .block_on(list(&database, filter, Pager::default()))
+            .unwrap()
+            .1,
+        0
+    );
+
+    let filter = PageFilter {
+        title: Some("c".to_string()),
+        ..PageFilter::default()
+    };
+
+    assert_eq!(
+        system
+            .block_on(list(&database, filter, Pager::default()))
+            .unwrap()
+            .0[0]
+            .title,
+        "C"
+    );
+
+    let filter = PageFilter {
+        created_by: Some(Uuid::new_v4()),
+        ..PageFilter::default()
+    };
+
+    assert_eq!(
+        system
+            .block_on(list(&database, filter, Pager::default()))
+            .unwrap()
+            .1,
+        0
+    );
+
+    let filter = PageFilter {
+        sort: PageSort::Title,
+        order: Order::Desc,
+        ..PageFilter::default()
+    };
+    let pager = Pager {
+        limit: Some(1),
+        offset: 1,
+    };
+    let (pages, total) = system.block_on(list(&database, filter, pager)).unwrap();
+
+    assert_eq!(total, 2);
+    assert_eq!(pages.len(), 1);
+    assert_eq!(pages[0].title, "B");
+    assert!(system.block_on(uninstall(&database)).is_ok());
+    assert!(system.block_on(uninstall_users(&database)).is_ok());
+}
+
+#[test]
+fn test_page_revisions() {
+    let mut system = System::new("test");
+    let database = Database::from_config(DatabaseConfig::default()).unwrap();
+    let author = User {
+        email: "author@domain.test".to_string(),
+        password: "password".to_string(),
+        ..User::default()
+    };
+    let uuid = Uuid::new_v4();
+    let page = Page {
+        id: uuid,
+        parent: None,
+        slug: "rev".to_string(),
+        title: "First".to_string(),
+        description: "FIRST".to_string(),
+        document: json!({}),
+        status: PageStatus::Published,
+        publish_at: None,
+        unpublish_at: None,
+        created: Utc::now(),
+        updated: Utc::now(),
+        created_by: None,
+        updated_by: None,
+    };
+
+    system.block_on(install_users(&database)).unwrap();
+    system.block_on(install(&database)).unwrap();
+    system
+        .block_on(create_user(&database, author.clone()))
+        .unwrap();
+
+    assert!(system
+        .block_on(create(&database, page.clone(), Some(author.id)))
+        .is_ok());
+    assert!(system
+        .block_on(update(
+            &database,
+            Page {
+                title: "Second".to_string(),
+                ..page.clone()
+            },
+            None,
+        ))
+        .is_ok());
+
+    let revisions = system.block_on(list_revisions(&database, uuid)).unwrap();
+
+    assert_eq!(revisions.len(), 2);
+    assert_eq!(revisions[0].revision, 2);
+    assert_eq!(revisions[0].title, "Second");
+    assert_eq!(revisions[0].author, None);
+    assert_eq!(revisions[1].revision, 1);
+    assert_eq!(revisions[1].title, "First");
+    assert_eq!(revisions[1].author, Some(author.id));
+
+    let first = revisions[1].id;
+
+    assert_eq!(
+        system
+            .block_on(retrieve_revision(&database, uuid, first))
+            .unwrap()
+            .title,
+        "First"
+    );
+    assert!(system
+        .block_on(retrieve_revision(&database, Uuid::new_v4(), first))
+        .is_err());
+    assert_eq!(
+        system
+            .block_on(revert_revision(&database, uuid, first, Some(author.id)))
+            .unwrap()
+            .title,
+        "First"
+    );
+    assert_eq!(
+        system.block_on(retrieve(&database, uuid)).unwrap().title,
+        "First"
+    );
+
+    let revisions = system.block_on(list_revisions(&database, uuid)).unwrap();
+
+    assert_eq!(revisions.len(), 3);
+    assert_eq!(revisions[0].revision, 3);
+    assert_eq!(revisions[0].title, "First");
+
+    assert!(system
+        .block_on(revert_revision(&database, uuid, Uuid::new_v4(), None))
+        .is_err());
+
+    let parent = Page {
+        id: Uuid::new_v4(),
+        slug: "rev-parent".to_string(),
+        ..page.clone()
+    };
+
+    assert!(system
+        .block_on(create(&database, parent.clone(), None))
+        .is_ok());
+    assert!(system
+        .block_on(update(
+            &database,
+            Page {
+                parent: Some(parent.id),
+                ..page.clone()
+            },
+            None,
+        ))
+        .is_ok());
+
+    let nested = system.block_on(list_revisions(&database, uuid)).unwrap()[0].id;
+
+    assert!(system
+        .block_on(update(&database, page.clone(), None))
+        .is_ok());
+    assert!(system.block_on(delete(&database, parent.id)).is_ok());
+    assert_eq!(
+        system
+            .block_on(revert_revision(&database, uuid, nested, None))
+            .unwrap_err()
+            .downcast::<TreeError>()
+            .unwrap(),
+        TreeError::MissingParent
+    );
+    assert!(system.block_on(delete(&database, uuid)).is_ok());
+    assert!(system
+        .block_on(list_revisions(&database, uuid))
+        .unwrap()
+        .is_empty());
+    assert!(system.block_on(uninstall(&database)).is_ok());
+    assert!(system.block_on(uninstall_users(&database)).is_ok());
+}
+
+#[test]
+fn test_page_redirects() {
+    let mut system = System::new("test");
+    let database = Database::from_config(DatabaseConfig::default()).unwrap();
+    let uuid = Uuid::new_v4();
+    let page = Page {
+        id: uuid,
+        slug: "moved".to_string(),
+        title: "Moved".to_string(),
+        status: PageStatus::Published,
+        ..Page::default()
+    };
+    let child = Page {
+        id: Uuid::new_v4(),
+        parent: Some(uuid),
+        slug: "child".to_string(),
+        title: "Child".to_string(),
+        status: PageStatus::Published,
+        ..Page::default()
+    };
+
+    system.block_on(install_users(&database)).unwrap();
+    system.block_on(install(&database)).unwrap();
+
+    assert!(system
+        .block_on(create(&database, page.clone(), None))
+        .is_ok());
+    assert!(system
+        .block_on(create(&database, child.clone(), None))
+        .is_ok());
+    assert!(system
+        .block_on(update(&database, page.clone(), None))
+        .is_ok());
+    assert!(system
+        .block_on(list_redirects(&database))
+        .unwrap()
+        .is_empty());
+
+    let renamed = Page {
+        slug: "renamed".to_string(),
+        ..page.clone()
     };
 
-    assert!(system.block_on(create(&database, page.clone())).is_ok());
-    assert_eq!(system.block_on(list(&database)).unwrap().len(), 2);
+    assert!(system
+        .block_on(update(&database, renamed.clone(), None))
+        .is_ok());
+    assert!(system.block_on(locate(&database, "/moved")).is_err());
+    assert_eq!(
+        system
+            .block_on(locate_redirect(&database, "/moved"))
+            .unwrap()
+            .location,
+        "/renamed"
+    );
+    assert_eq!(
+        system
+            .block_on(locate_redirect(&database, "/moved/child"))
+            .unwrap()
+            .location,
+        "/renamed/child"
+    );
+    assert_eq!(system.block_on(list_redirects(&database)).unwrap().len(), 2);
+
+    assert!(system
+        .block_on(update(&database, page.clone(), None))
+        .is_ok());
+    assert!(system
+        .block_on(locate_redirect(&database, "/moved"))
+        .is_err());
+    assert_eq!(
+        system
+            .block_on(locate_redirect(&database, "/renamed"))
+            .unwrap()
+            .location,
+        "/moved"
+    );
+
+    let redirect = system
+        .block_on(locate_redirect(&database, "/renamed"))
+        .unwrap();
+
+    assert_eq!(
+        system
+            .block_on(delete_redirect(&database, redirect.id))
+            .unwrap(),
+        "/renamed"
+    );
+    assert!(system
+        .block_on(locate_redirect(&database, "/renamed"))
+        .is_err());
+    assert!(system
+        .block_on(delete_redirect(&database, redirect.id))
+        .is_err());
+
+    assert!(system.block_on(delete(&database, child.id)).is_ok());
+    assert!(system.block_on(delete(&database, uuid)).is_ok());
+    assert!(system
+        .block_on(list_redirects(&database))
+        .unwrap()
+        .is_empty());
+    assert!(system.block_on(uninstall(&database)).is_ok());
+    assert!(system.block_on(uninstall_users(&database)).is_ok());
+}
+
+#[test]
+fn test_page_tree() {
+    let mut system = System::new("test");
+    let database = Database::from_config(DatabaseConfig::default()).unwrap();
+    let uuid = Uuid::new_v4();
+    let page = Page {
+        id: uuid,
+        slug: "parent".to_string(),
+        title: "Parent".to_string(),
+        ..Page::default()
+    };
+    let child = Page {
+        id: Uuid::new_v4(),
+        parent: Some(uuid),
+        slug: "child".to_string(),
+        title: "Child".to_string(),
+        ..Page::default()
+    };
+
+    system.block_on(install_users(&database)).unwrap();
+    system.block_on(install(&database)).unwrap();
+
+    assert!(system
+        .block_on(create(&database, page.clone(), None))
+        .is_ok());
+    assert!(system
+        .block_on(create(&database, child.clone(), None))
+        .is_ok());
+
+    let duplicate = Page {
+        id: Uuid::new_v4(),
+        ..child.clone()
+    };
+    let err = system
+        .block_on(create(&database, duplicate, None))
+        .unwrap_err();
+
+    assert_eq!(
+        err.downcast::<TreeError>().unwrap(),
+        TreeError::Duplicate("child".to_string())
+    );
+
+    let looped = Page {
+        parent: Some(child.id),
+        ..page.clone()
+    };
+    let err = system
+        .block_on(update(&database, looped, None))
+        .unwrap_err();
+
+    assert_eq!(err.downcast::<TreeError>().unwrap(), TreeError::Cycle);
+
+    let looped = Page {
+        parent: Some(uuid),
+        ..page.clone()
+    };
+    let err = system
+        .block_on(update(&database, looped, None))
+        .unwrap_err();
+
+    assert_eq!(err.downcast::<TreeError>().unwrap(), TreeError::Cycle);
+    assert_eq!(
+        system.block_on(retrieve(&database, uuid)).unwrap().parent,
+        None
+    );
+    assert!(system.block_on(check(&database)).unwrap().is_empty());
+
+    assert!(system.block_on(delete(&database, child.id)).is_ok());
+    assert!(system.block_on(delete(&database, uuid)).is_ok());
+    assert!(system.block_on(uninstall(&database)).is_ok());
+    assert!(system.block_on(uninstall_users(&database)).is_ok());
+}
+
+#[test]
+fn test_page_authors() {
+    let mut system = System::new("test");
+    let database = Database::from_config(DatabaseConfig::default()).unwrap();
+    let author = User {
+        email: "author@domain.test".to_string(),
+        password: "password".to_string(),
+        ..User::default()
+    };
+    let editor = User {
+        email: "editor@domain.test".to_string(),
+        password: "password".to_string(),
+        display_name: Some("Editor".to_string()),
+        ..User::default()
+    };
+    let page = Page {
+        slug: "authors".to_string(),
+        title: "Authors".to_string(),
+        created_by: Some(editor.id),
+        ..Page::default()
+    };
+
+    system.block_on(install_users(&database)).unwrap();
+    system.block_on(install(&database)).unwrap();
+    system
+        .block_on(create_user(&database, author.clone()))
+        .unwrap();
+    system
+        .block_on(create_user(&database, editor.clone()))
+        .unwrap();
+
+    let created = system
+        .block_on(create(&database, page.clone(), Some(author.id)))
+        .unwrap();
+
+    assert_eq!(created.created_by, Some(author.id));
+    assert_eq!(created.updated_by, Some(author.id));
+
+    let updated = system
+        .block_on(update(&database, created, Some(editor.id)))
+        .unwrap();
+
+    assert_eq!(updated.created_by, Some(author.id));
+    assert_eq!(updated.updated_by, Some(editor.id));
+
+    let authors = system
+        .block_on(names(&database, vec![author.id, editor.id]))
+        .unwrap();
+
+    assert_eq!(authors.get(&author.id), Some(&author.email));
+    assert_eq!(authors.get(&editor.id), Some(&"Editor".to_string()));
+
+    system.block_on(delete_user(&database, author.id)).unwrap();
+
+    let page = system.block_on(retrieve(&database, page.id)).unwrap();
+    let authors = system
+        .block_on(names(&database, vec![author.id, editor.id]))
+        .unwrap();
+
+    assert_eq!(page.created_by, None);
+    assert_eq!(page.updated_by, Some(editor.id));
+    assert_eq!(authors.len(), 1);
+
+    let revisions = system.block_on(list_revisions(&database, page.id)).unwrap();
+
+    assert_eq!(revisions[0].author, Some(editor.id));
+    assert_eq!(revisions[1].author, None);
+
+    assert!(system.block_on(delete(&database, page.id)).is_ok());
     assert!(system.block_on(uninstall(&database)).is_ok());
+    assert!(system.block_on(uninstall_users(&database)).is_ok());
 }
@@ -5,13 +5,15 @@ use actix_web::http::header::{self, HeaderValue};
 use actix_web::http::{Method, StatusCode};
 use actix_web::App;
 use brace_db::{Database, DatabaseConfig};
+use brace_web_auth::action::assign_roles::assign_roles;
 use brace_web_auth::action::create::create;
 use brace_web_auth::action::install::install as install_users;
 use brace_web_auth::action::uninstall::uninstall as uninstall_users;
+use brace_web_auth::config::{AuthConfig, Jwt};
 use brace_web_auth::model::User;
 use brace_web_page::action::install::install;
 use brace_web_page::action::uninstall::uninstall;
-use brace_web_page::model::Page;
+use brace_web_page::model::{Page, PageStatus};
 use chrono::Utc;
 use futures::future::Future;
 use serde_json::{json, Value};
@@ -30,17 +32,24 @@ fn test_page_route_lifecycle() {
         title: "A".to_string(),
         description: "A".to_string(),
         document: json!({}),
+        status: PageStatus::Published,
+        publish_at: None,
+        unpublish_at: None,
         created: Utc::now(),
         updated: Utc::now(),
+        created_by: None,
+        updated_by: None,
     };
 
-    system.block_on(install(&database)).unwrap();
     system.block_on(install_users(&database)).unwrap();
+    system.block_on(install(&database)).unwrap();
 
     let mut srv = TestServer::new(|| {
         HttpService::new(
             App::new()
                 .data(Database::from_config(DatabaseConfig::default()).unwrap())
+                .data(Jwt::from_config(&AuthConfig::generate()).unwrap())
+                .data(AuthConfig::default())
                 .configure(brace_web_auth::route::api::config)
                 .configure(brace_web_page::route::api::config),
         )
@@ -96,9 +105,21 @@ fn test_page_route_lifecycle() {
         password: "password".to_string(),
         created: Utc::now(),
         updated: Utc::now(),
+        verified: true,
+        approved: true,
+        display_name: None,
+        avatar: None,
+        bio: None,
     };
 
-    system.block_on(create(&database, admin)).unwrap();
+    system.block_on(create(&database, admin.clone())).unwrap();
+    system
+        .block_on(assign_roles(
+            &database,
+            admin.id,
+            vec!["administrator".to_string()],
+        ))
+        .unwrap();
 
     let auth = json!({
         "email": "admin@domain.test",
@@ -124,6 +145,44 @@ fn test_page_route_lifecycle() {
 
     assert_eq!(res.status(), StatusCode::OK);
 
+    let req = srv
+        .request(Method::GET, srv.url("/api/pages/?status=draft"))
+        .header("Authorization", header.clone())
+        .send();
+    let res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let req = srv
+        .request(Method::GET, srv.url("/api/pages/?status=invalid"))
+        .header("Authorization", header.clone())
+        .send();
+    let res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    let invalid = Page {
+        title: "".to_string(),
+        slug: "a/b".to_string(),
+        ..page.clone()
+    };
+
+    let req = srv
+        .request(Method::POST, srv.url("/api/pages/"))
+        .header("Authorization", header.clone())
+        .send_json(&invalid);
+    let mut res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let json = res.json::<Value>().wait().unwrap();
+
+    assert_eq!(
+        json["error"]["fields"]["title"],
+        json!(["This field is required"])
+    );
+    assert!(json["error"]["fields"]["slug"].is_array());
+
     let req = srv
         .request(Method::POST, srv.url("/api/pages/"))
         .header("Authorization", header.clone())
@@ -140,6 +199,74 @@ fn test_page_route_lifecycle() {
         .request(Method::GET, srv.url(&format!("/api/pages/{}", uuid)))
         .header("Authorization", header.clone())
         .send();
+    let mut res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let json = res.json::<Value>().wait().unwrap();
+
+    assert_eq!(json["value"]["created_by"], json!(admin.id));
+    assert_eq!(json["value"]["updated_by"], json!(admin.id));
+
+    let draft = Page {
+        id: Uuid::new_v4(),
+        slug: "draft".to_string(),
+        status: PageStatus::Draft,
+        ..page.clone()
+    };
+    let draft_path = format!("/api/pages/{}", draft.id);
+
+    let req = srv
+        .request(Method::POST, srv.url("/api/pages/"))
+        .header("Authorization", header.clone())
+        .send_json(&draft);
+    let res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::CREATED);
+
+    let user = User {
+        id: Uuid::new_v4(),
+        email: "user@domain.test".to_string(),
+        ..admin.clone()
+    };
+
+    system.block_on(create(&database, user)).unwrap();
+
+    let auth = json!({
+        "email": "user@domain.test",
+        "password": "password",
+    });
+
+    let req = srv
+        .request(Method::POST, srv.url("/api/auth"))
+        .send_json(&auth);
+    let mut res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let json = res.json::<Value>().wait().unwrap();
+    let token = json.get("token").unwrap();
+    let user_header = format!("Bearer {}", token.as_str().unwrap());
+
+    for path in &[
+        "/api/pages/".to_string(),
+        "/api/pages/redirects/".to_string(),
+        draft_path.clone(),
+        format!("{}/revisions", draft_path),
+    ] {
+        let req = srv
+            .request(Method::GET, srv.url(path))
+            .header("Authorization", user_header.clone())
+            .send();
+        let res = srv.block_on(req).unwrap();
+
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    }
+
+    let req = srv
+        .request(Method::GET, srv.url(&draft_path))
+        .header("Authorization", header.clone())
+        .send();
     let res = srv.block_on(req).unwrap();
 
     assert_eq!(res.status(), StatusCode::OK);
@@ -152,6 +279,57 @@ fn test_page_route_lifecycle() {
 
     assert_eq!(res.status(), StatusCode::OK);
 
+    let req = srv
+        .request(Method::GET, srv.url(&format!("{}/revisions", path)))
+        .header("Authorization", header.clone())
+        .send();
+    let mut res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let json = res.json::<Value>().wait().unwrap();
+    let revisions = json.get("value").unwrap().as_array().unwrap();
+    let revision = revisions[1].get("id").unwrap().as_str().unwrap();
+
+    assert_eq!(revisions.len(), 2);
+
+    let req = srv
+        .request(
+            Method::GET,
+            srv.url(&format!("{}/revisions/{}", path, revision)),
+        )
+        .header("Authorization", header.clone())
+        .send();
+    let mut res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let json = res.json::<Value>().wait().unwrap();
+
+    assert!(json.get("diff").unwrap().is_array());
+
+    let req = srv
+        .request(
+            Method::GET,
+            srv.url(&format!("{}/revisions/{}", path, Uuid::new_v4())),
+        )
+        .header("Authorization", header.clone())
+        .send();
+    let res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    let req = srv
+        .request(
+            Method::POST,
+            srv.url(&format!("{}/revisions/{}/revert", path, revision)),
+        )
+        .header("Authorization", header.clone())
+        .send();
+    let res = srv.block_on(req).unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+
     let req = srv
         .request(Method::DELETE, srv.url(&format!("/api/pages/{}", uuid)))
         .header("Authorization", header.clone())
@@ -10,6 +10,8 @@ use serde::{Deserialize, Serialize};
 pub struct WebConfig {
     pub host: Ipv4Addr,
     pub port: u16,
+    pub url: Option<String>,
+    pub dev: bool,
     pub log: WebLogConfig,
 }
 
@@ -18,6 +20,8 @@ impl Default for WebConfig {
         Self {
             host: Ipv4Addr::new(127, 0, 0, 1),
             port: 8080,
+            url: None,
+            dev: false,
             log: WebLogConfig::default(),
         }
     }
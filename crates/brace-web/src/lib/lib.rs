@@ -1,5 +1,6 @@
 pub mod config;
 pub mod extract;
+pub mod pager;
 pub mod parse;
 pub mod redirect;
 pub mod render;
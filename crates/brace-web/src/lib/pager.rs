@@ -0,0 +1,173 @@
+use serde::{Deserialize, Serialize};
+use serde_qs::to_string;
+
+pub const DEFAULT_LIMIT: i64 = 25;
+pub const MAX_LIMIT: i64 = 100;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct Pager {
+    pub limit: Option<i64>,
+    pub offset: i64,
+}
+
+impl Pager {
+    pub fn all() -> Self {
+        Self {
+            limit: None,
+            offset: 0,
+        }
+    }
+
+    pub fn limit(&self) -> Option<i64> {
+        self.limit.map(|limit| limit.max(1).min(MAX_LIMIT))
+    }
+
+    pub fn offset(&self) -> i64 {
+        self.offset.max(0)
+    }
+
+    pub fn paginate<T>(&self, path: &str, query: &T, total: i64) -> Pagination
+    where
+        T: Serialize,
+    {
+        let offset = self.offset();
+        let limit = self.limit();
+        let link = |offset: i64| {
+            let mut params = to_string(query).unwrap_or_default();
+
+            if !params.is_empty() {
+                params.push('&');
+            }
+
+            if let Some(limit) = limit {
+                params.push_str(&format!("limit={}&", limit));
+            }
+
+            format!("{}?{}offset={}", path, params, offset)
+        };
+
+        let (next, prev) = match limit {
+            Some(limit) => (
+                if offset + limit < total {
+                    Some(link(offset + limit))
+                } else {
+                    None
+                },
+                if offset > 0 {
+                    Some(link((offset - limit).max(0)))
+                } else {
+                    None
+                },
+            ),
+            None => (None, None),
+        };
+
+        Pagination {
+            total,
+            limit,
+            offset,
+            next,
+            prev,
+        }
+    }
+}
+
+impl Default for Pager {
+    fn default() -> Self {
+        Self {
+            limit: Some(DEFAULT_LIMIT),
+            offset: 0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Pagination {
+    pub total: i64,
+    pub limit: Option<i64>,
+    pub offset: i64,
+    pub next: Option<String>,
+    pub prev: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Order {
+    Asc,
+    Desc,
+}
+
+impl Order {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Order::Asc => "asc",
+            Order::Desc => "desc",
+        }
+    }
+}
+
+impl Default for Order {
+    fn default() -> Self {
+        Order::Asc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use super::{Pager, MAX_LIMIT};
+
+    #[derive(Serialize)]
+    struct Filter {
+        title: String,
+    }
+
+    #[test]
+    fn test_pager_limit() {
+        let pager = Pager {
+            limit: Some(1000),
+            offset: -5,
+        };
+
+        assert_eq!(pager.limit(), Some(MAX_LIMIT));
+        assert_eq!(pager.offset(), 0);
+        assert_eq!(Pager::all().limit(), None);
+    }
+
+    #[test]
+    fn test_pager_paginate() {
+        let filter = Filter {
+            title: "foo".to_string(),
+        };
+        let pager = Pager {
+            limit: Some(10),
+            offset: 10,
+        };
+        let pagination = pager.paginate("/pages/", &filter, 25);
+
+        assert_eq!(pagination.total, 25);
+        assert_eq!(
+            pagination.next,
+            Some("/pages/?title=foo&limit=10&offset=20".to_string())
+        );
+        assert_eq!(
+            pagination.prev,
+            Some("/pages/?title=foo&limit=10&offset=0".to_string())
+        );
+
+        let pagination = Pager::default().paginate("/pages/", &filter, 25);
+
+        assert_eq!(
+            pagination.next,
+            Some("/pages/?title=foo&limit=25&offset=25".to_string())
+        );
+        assert_eq!(pagination.prev, None);
+
+        let pagination = Pager::all().paginate("/pages/", &filter, 25);
+
+        assert_eq!(pagination.next, None);
+        assert_eq!(pagination.prev, None);
+    }
+}
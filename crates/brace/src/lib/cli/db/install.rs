@@ -0,0 +1,48 @@
+use brace_cli::prelude::*;
+use brace_db::schema::migrate::migrate;
+use brace_db::schema::status::status;
+
+pub fn cmd() -> Command {
+    Command::new("install")
+        .about("Installs the database schema of modules that are not yet installed")
+        .arg(super::arg_config())
+        .arg(super::arg_module())
+}
+
+pub fn exec(shell: &mut Shell, matches: &ArgMatches) -> ExecResult {
+    let migrations = super::migrations(shell, matches)?;
+    let (mut system, database) = super::connect(shell, matches)?;
+
+    match system.block_on(status(&database, migrations.clone())) {
+        Ok(statuses) => {
+            if let Some(status) = statuses.iter().find(|status| status.applied.is_some()) {
+                shell.error(format!(
+                    "Module is already installed: {}",
+                    status.migration.module
+                ))?;
+                shell.exit(1);
+            }
+        }
+        Err(err) => {
+            shell.error(err)?;
+            shell.exit(1);
+        }
+    }
+
+    match system.block_on(migrate(&database, migrations)) {
+        Ok(migrations) => {
+            for migration in migrations {
+                shell.info(format!(
+                    "Applied migration {}/{}",
+                    migration.module, migration.name
+                ))?;
+            }
+
+            shell.exit(0);
+        }
+        Err(err) => {
+            shell.error(err)?;
+            shell.exit(1);
+        }
+    }
+}
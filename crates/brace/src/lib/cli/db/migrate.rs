@@ -0,0 +1,35 @@
+use brace_cli::prelude::*;
+use brace_db::schema::migrate::migrate;
+
+pub fn cmd() -> Command {
+    Command::new("migrate")
+        .about("Applies all pending database migrations")
+        .arg(super::arg_config())
+        .arg(super::arg_module())
+}
+
+pub fn exec(shell: &mut Shell, matches: &ArgMatches) -> ExecResult {
+    let migrations = super::migrations(shell, matches)?;
+    let (mut system, database) = super::connect(shell, matches)?;
+
+    match system.block_on(migrate(&database, migrations)) {
+        Ok(migrations) => {
+            if migrations.is_empty() {
+                shell.info("No pending migrations")?;
+            }
+
+            for migration in migrations {
+                shell.info(format!(
+                    "Applied migration {}/{}",
+                    migration.module, migration.name
+                ))?;
+            }
+
+            shell.exit(0);
+        }
+        Err(err) => {
+            shell.error(err)?;
+            shell.exit(1);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use std::path::Path;
+
+use actix::{System, SystemRunner};
+use brace_cli::prelude::*;
+use brace_config::load;
+use brace_db::{Database, Migration};
+
+use crate::config::AppConfig;
+
+pub mod install;
+pub mod migrate;
+pub mod rollback;
+pub mod status;
+
+pub fn cmd() -> Command {
+    Command::new("db")
+        .about("The database schema manager")
+        .subcommand(install::cmd())
+        .subcommand(migrate::cmd())
+        .subcommand(rollback::cmd())
+        .subcommand(status::cmd())
+        .setting(AppSettings::AllowExternalSubcommands)
+}
+
+pub fn exec(shell: &mut Shell, matches: &ArgMatches) -> ExecResult {
+    match matches.subcommand() {
+        ("install", Some(matches)) => install::exec(shell, matches),
+        ("migrate", Some(matches)) => migrate::exec(shell, matches),
+        ("rollback", Some(matches)) => rollback::exec(shell, matches),
+        ("status", Some(matches)) => status::exec(shell, matches),
+        (command, _) => exit_command_invalid(command, shell, &matches.usage()),
+    }
+}
+
+pub fn arg_config() -> Arg<'static, 'static> {
+    Arg::with_name("config")
+        .short("c")
+        .long("config")
+        .value_name("FILE")
+        .help("The configuration file to use")
+}
+
+pub fn arg_module() -> Arg<'static, 'static> {
+    Arg::with_name("module")
+        .short("m")
+        .long("module")
+        .value_name("MODULE")
+        .multiple(true)
+        .number_of_values(1)
+        .help("Limits the command to the given module")
+}
+
+pub fn connect(
+    shell: &mut Shell,
+    matches: &ArgMatches,
+) -> Result<(SystemRunner, Database), failure::Error> {
+    let config = match matches.value_of("config") {
+        Some(file) => match load::file::<AppConfig, _>(file)
+            .and_then(|config| config.database.validate().map(|()| config))
+            .and_then(|config| config.resolve(Path::new(file)))
+        {
+            Ok(config) => {
+                shell.info(format!("Using configuration file: {}", file))?;
+                config
+            }
+            Err(err) => {
+                shell.error(format!("Invalid configuration: {}", err))?;
+                shell.exit(1);
+            }
+        },
+        None => {
+            shell.error("No configuration file specified")?;
+            shell.exit(1);
+        }
+    };
+
+    let system = System::new("brace");
+
+    match Database::from_config(config.database) {
+        Ok(database) => Ok((system, database)),
+        Err(err) => {
+            shell.error(format!("Unable to connect to database: {}", err))?;
+            shell.exit(1);
+        }
+    }
+}
+
+pub fn migrations(
+    shell: &mut Shell,
+    matches: &ArgMatches,
+) -> Result<Vec<Migration>, failure::Error> {
+    let migrations = crate::migrations();
+
+    match matches.values_of("module") {
+        Some(modules) => {
+            let modules = modules.collect::<Vec<&str>>();
+
+            for module in modules.iter() {
+                if !migrations
+                    .iter()
+                    .any(|migration| migration.module == *module)
+                {
+                    shell.error(format!("Invalid module: {}", module))?;
+                    shell.exit(1);
+                }
+            }
+
+            Ok(migrations
+                .into_iter()
+                .filter(|migration| modules.contains(&migration.module))
+                .collect())
+        }
+        None => Ok(migrations),
+    }
+}
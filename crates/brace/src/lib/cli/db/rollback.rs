@@ -0,0 +1,32 @@
+use brace_cli::prelude::*;
+use brace_db::schema::rollback::rollback;
+
+pub fn cmd() -> Command {
+    Command::new("rollback")
+        .about("Rolls back the most recently applied database migration")
+        .arg(super::arg_config())
+        .arg(super::arg_module())
+}
+
+pub fn exec(shell: &mut Shell, matches: &ArgMatches) -> ExecResult {
+    let migrations = super::migrations(shell, matches)?;
+    let (mut system, database) = super::connect(shell, matches)?;
+
+    match system.block_on(rollback(&database, migrations)) {
+        Ok(Some(migration)) => {
+            shell.info(format!(
+                "Rolled back migration {}/{}",
+                migration.module, migration.name
+            ))?;
+            shell.exit(0);
+        }
+        Ok(None) => {
+            shell.info("No migrations to roll back")?;
+            shell.exit(0);
+        }
+        Err(err) => {
+            shell.error(err)?;
+            shell.exit(1);
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use brace_cli::prelude::*;
+use brace_db::schema::status::status;
+
+pub fn cmd() -> Command {
+    Command::new("status")
+        .about("Reports the state of the database schema of each module")
+        .arg(super::arg_config())
+        .arg(super::arg_module())
+        .arg(
+            Arg::with_name("check")
+                .long("check")
+                .help("Exits with code 2 if there are pending migrations"),
+        )
+}
+
+pub fn exec(shell: &mut Shell, matches: &ArgMatches) -> ExecResult {
+    let migrations = super::migrations(shell, matches)?;
+    let (mut system, database) = super::connect(shell, matches)?;
+
+    match system.block_on(status(&database, migrations)) {
+        Ok(statuses) => {
+            let mut pending = 0;
+
+            for status in statuses {
+                match status.applied {
+                    Some(applied) => shell.print(format!(
+                        "applied  {}/{} ({})",
+                        status.migration.module,
+                        status.migration.name,
+                        applied.format("%Y-%m-%dT%H:%M:%SZ")
+                    ))?,
+                    None => {
+                        pending += 1;
+                        shell.print(format!(
+                            "pending  {}/{}",
+                            status.migration.module, status.migration.name
+                        ))?
+                    }
+                }
+            }
+
+            if pending > 0 && matches.is_present("check") {
+                shell.exit(2);
+            }
+
+            shell.exit(0);
+        }
+        Err(err) => {
+            shell.error(err)?;
+            shell.exit(1);
+        }
+    }
+}
@@ -2,6 +2,9 @@ use std::ffi::OsString;
 
 use brace_cli::prelude::*;
 
+pub mod db;
+pub mod page;
+pub mod user;
 pub mod web;
 
 pub fn cmd() -> Command {
@@ -30,7 +33,10 @@ pub fn cmd() -> Command {
                 .takes_value(true)
                 .possible_values(&["auto", "always", "never"]),
         )
+        .subcommand(db::cmd())
+        .subcommand(page::cmd())
         .subcommand(brace_theme::cli::cmd())
+        .subcommand(user::cmd())
         .subcommand(web::cmd())
         .setting(AppSettings::AllowExternalSubcommands)
 }
@@ -49,7 +55,10 @@ pub fn exec(shell: &mut Shell, matches: &ArgMatches) -> ExecResult {
     }
 
     match matches.subcommand() {
+        ("db", Some(matches)) => db::exec(shell, matches),
+        ("page", Some(matches)) => page::exec(shell, matches),
         ("theme", Some(matches)) => brace_theme::cli::exec(shell, matches),
+        ("user", Some(matches)) => user::exec(shell, matches),
         ("web", Some(matches)) => web::exec(shell, matches),
         (command, _) => exit_command_invalid(command, shell, &matches.usage()),
     }
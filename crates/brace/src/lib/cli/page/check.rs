@@ -0,0 +1,57 @@
+use brace_cli::prelude::*;
+use brace_web_page::action::check::check;
+use brace_web_page::model::PageIssue;
+
+pub fn cmd() -> Command {
+    Command::new("check")
+        .about("Reports orphaned pages, cycles and duplicate sibling slugs in the page tree")
+        .arg(crate::cli::db::arg_config())
+}
+
+pub fn exec(shell: &mut Shell, matches: &ArgMatches) -> ExecResult {
+    let (mut system, database) = crate::cli::db::connect(shell, matches)?;
+
+    match system.block_on(check(&database)) {
+        Ok(issues) => {
+            for issue in issues.iter() {
+                match issue {
+                    PageIssue::Orphan { page, parent } => {
+                        shell.print(format!("orphan     {} (missing parent {})", page, parent))?
+                    }
+                    PageIssue::Cycle { page, parent } => shell.print(format!(
+                        "cycle      {} (parent {})",
+                        page,
+                        parent.map(|parent| parent.to_string()).unwrap_or_default()
+                    ))?,
+                    PageIssue::Duplicate {
+                        parent,
+                        slug,
+                        pages,
+                    } => shell.print(format!(
+                        "duplicate  {} under {} ({})",
+                        slug,
+                        parent
+                            .map(|parent| parent.to_string())
+                            .unwrap_or_else(|| "/".to_string()),
+                        pages
+                            .iter()
+                            .map(|page| page.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ))?,
+                }
+            }
+
+            if issues.is_empty() {
+                shell.info("The page tree is consistent")?;
+                shell.exit(0);
+            }
+
+            shell.exit(2);
+        }
+        Err(err) => {
+            shell.error(err)?;
+            shell.exit(1);
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use brace_cli::prelude::*;
+
+pub mod check;
+
+pub fn cmd() -> Command {
+    Command::new("page")
+        .about("The page content manager")
+        .subcommand(check::cmd())
+        .setting(AppSettings::AllowExternalSubcommands)
+}
+
+pub fn exec(shell: &mut Shell, matches: &ArgMatches) -> ExecResult {
+    match matches.subcommand() {
+        ("check", Some(matches)) => check::exec(shell, matches),
+        (command, _) => exit_command_invalid(command, shell, &matches.usage()),
+    }
+}
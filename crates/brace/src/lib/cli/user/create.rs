@@ -0,0 +1,88 @@
+use brace_cli::prelude::*;
+use brace_web_auth::action::assign_roles::assign_roles;
+use brace_web_auth::action::create::create;
+use brace_web_auth::action::list_roles::list_roles;
+use brace_web_auth::model::User;
+use futures::future::Future;
+
+pub fn cmd() -> Command {
+    Command::new("create")
+        .about("Creates a verified and approved user, such as the first administrator")
+        .arg(crate::cli::db::arg_config())
+        .arg(
+            Arg::with_name("email")
+                .long("email")
+                .value_name("EMAIL")
+                .help("The email address of the user")
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("password")
+                .long("password")
+                .value_name("PASSWORD")
+                .help("The password of the user")
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("role")
+                .short("r")
+                .long("role")
+                .value_name("ROLE")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Assigns the role to the user, such as administrator"),
+        )
+}
+
+pub fn exec(shell: &mut Shell, matches: &ArgMatches) -> ExecResult {
+    let (mut system, database) = crate::cli::db::connect(shell, matches)?;
+    let user = User {
+        email: matches.value_of("email").unwrap_or_default().to_string(),
+        password: matches.value_of("password").unwrap_or_default().to_string(),
+        verified: true,
+        approved: true,
+        ..User::default()
+    };
+    let roles = matches
+        .values_of("role")
+        .map(|roles| roles.map(str::to_string).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    match system.block_on(list_roles(&database)) {
+        Ok(known) => {
+            if let Some(role) = roles
+                .iter()
+                .find(|role| !known.iter().any(|known| &known.name == *role))
+            {
+                shell.error(format!("Unknown role: {}", role))?;
+                shell.exit(1);
+            }
+        }
+        Err(err) => {
+            shell.error(err)?;
+            shell.exit(1);
+        }
+    }
+
+    let res = system.block_on(create(&database, user).and_then(move |user| {
+        let id = user.id;
+
+        assign_roles(&database, id, roles).map(move |roles| (user, roles))
+    }));
+
+    match res {
+        Ok((user, roles)) => {
+            shell.info(format!("Created user {} ({})", user.email, user.id))?;
+
+            for role in roles {
+                shell.info(format!("Assigned role {}", role.name))?;
+            }
+
+            shell.exit(0);
+        }
+        Err(err) => {
+            shell.error(err)?;
+            shell.exit(1);
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use brace_cli::prelude::*;
+
+pub mod create;
+
+pub fn cmd() -> Command {
+    Command::new("user")
+        .about("The user account manager")
+        .subcommand(create::cmd())
+        .setting(AppSettings::AllowExternalSubcommands)
+}
+
+pub fn exec(shell: &mut Shell, matches: &ArgMatches) -> ExecResult {
+    match matches.subcommand() {
+        ("create", Some(matches)) => create::exec(shell, matches),
+        (command, _) => exit_command_invalid(command, shell, &matches.usage()),
+    }
+}
@@ -1,6 +1,7 @@
 use std::path::Path;
 
 use brace_cli::prelude::*;
+use brace_web_auth::config::AuthConfig;
 
 use crate::config::AppConfig;
 
@@ -19,7 +20,12 @@ pub fn cmd() -> Command {
 pub fn exec(shell: &mut Shell, matches: &ArgMatches) -> ExecResult {
     let directory = matches.value_of("directory").unwrap();
 
-    match crate::init(AppConfig::default(), Path::new(directory)) {
+    let config = AppConfig {
+        auth: AuthConfig::generate(),
+        ..AppConfig::default()
+    };
+
+    match crate::init(config, Path::new(directory)) {
         Ok(()) => {
             shell.info(format!("Created new site at {}", directory))?;
             shell.exit(0);
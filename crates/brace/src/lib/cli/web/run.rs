@@ -4,7 +4,6 @@ use std::path::Path;
 
 use brace_cli::prelude::*;
 use brace_config::load;
-use failure::format_err;
 use path_absolutize::Absolutize;
 
 use crate::config::AppConfig;
@@ -33,11 +32,18 @@ pub fn cmd() -> Command {
                 .value_name("PORT")
                 .help("The port number"),
         )
+        .arg(
+            Arg::with_name("dev")
+                .long("dev")
+                .help("Runs the server in development mode"),
+        )
 }
 
 pub fn exec(shell: &mut Shell, matches: &ArgMatches) -> ExecResult {
     match matches.value_of("config") {
-        Some(file) => match load::file(file) {
+        Some(file) => match load::file::<AppConfig, _>(file)
+            .and_then(|config| config.database.validate().map(|()| config))
+        {
             Ok(config) => {
                 let config = overload_file(file, config, shell, matches)?;
 
@@ -54,7 +60,7 @@ pub fn exec(shell: &mut Shell, matches: &ArgMatches) -> ExecResult {
         None => {
             let config = overload_default(shell, matches)?;
 
-            shell.warn("No configuration file specified")?;
+            shell.warn("No configuration file specified, running in development mode")?;
             crate::run(config, &current_dir()?)?;
 
             Ok(())
@@ -85,6 +91,10 @@ pub fn overload(
         }
     }
 
+    if matches.is_present("dev") {
+        config.web.dev = true;
+    }
+
     Ok(config)
 }
 
@@ -94,18 +104,7 @@ pub fn overload_file(
     shell: &mut Shell,
     matches: &ArgMatches,
 ) -> Result<AppConfig, failure::Error> {
-    let mut config = overload(config, shell, matches)?;
-
-    match Path::new(path).parent() {
-        Some(parent) => {
-            for theme in config.themes.iter_mut() {
-                theme.path = parent.join(&theme.path).absolutize()?;
-            }
-
-            Ok(config)
-        }
-        None => Err(format_err!("Invalid path {}", path)),
-    }
+    overload(config, shell, matches)?.resolve(Path::new(path))
 }
 
 pub fn overload_default(
@@ -114,6 +113,8 @@ pub fn overload_default(
 ) -> Result<AppConfig, failure::Error> {
     let mut config = overload(AppConfig::default(), shell, matches)?;
 
+    config.web.dev = true;
+
     for theme in config.themes.iter_mut() {
         theme.path = theme.path.absolutize()?;
     }
@@ -1,8 +1,13 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use brace_db::DatabaseConfig;
+use brace_mail::config::MailTransportConfig;
+use brace_mail::MailConfig;
 use brace_theme::config::ThemeReferenceInfo;
 use brace_web::config::WebConfig;
+use brace_web_auth::config::AuthConfig;
+use failure::{format_err, Error};
+use path_absolutize::Absolutize;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -10,6 +15,8 @@ use serde::{Deserialize, Serialize};
 pub struct AppConfig {
     pub web: WebConfig,
     pub database: DatabaseConfig,
+    pub auth: AuthConfig,
+    pub mail: MailConfig,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub themes: Vec<ThemeReferenceInfo>,
 }
@@ -19,6 +26,8 @@ impl Default for AppConfig {
         Self {
             web: WebConfig::default(),
             database: DatabaseConfig::default(),
+            auth: AuthConfig::default(),
+            mail: MailConfig::default(),
             themes: vec![ThemeReferenceInfo {
                 name: Some("default".to_string()),
                 path: PathBuf::from("themes/default/theme.toml"),
@@ -26,3 +35,44 @@ impl Default for AppConfig {
         }
     }
 }
+
+impl AppConfig {
+    pub fn resolve(mut self, file: &Path) -> Result<Self, Error> {
+        let parent = match file.parent() {
+            Some(parent) => parent,
+            None => return Err(format_err!("Invalid path {}", file.display())),
+        };
+
+        for theme in self.themes.iter_mut() {
+            resolve(parent, &mut theme.path)?;
+        }
+
+        if let Some(file) = self.auth.key_file.as_mut() {
+            resolve(parent, file)?;
+        }
+
+        if let Some(file) = self.auth.public_key_file.as_mut() {
+            resolve(parent, file)?;
+        }
+
+        if let Some(file) = self.auth.cookie.key_file.as_mut() {
+            resolve(parent, file)?;
+        }
+
+        if let Some(file) = self.database.tls.ca_file.as_mut() {
+            resolve(parent, file)?;
+        }
+
+        if let MailTransportConfig::File { path } = &mut self.mail.transport {
+            resolve(parent, path)?;
+        }
+
+        Ok(self)
+    }
+}
+
+fn resolve(parent: &Path, path: &mut PathBuf) -> Result<(), Error> {
+    *path = parent.join(&path).absolutize()?;
+
+    Ok(())
+}
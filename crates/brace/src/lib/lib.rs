@@ -1,17 +1,23 @@
 use std::path::{Path, PathBuf};
 
 use actix::System;
-use actix_identity::{CookieIdentityPolicy, IdentityService};
+use actix_identity::IdentityService;
 use actix_web::middleware::Logger;
 use actix_web::web::{get, resource};
 use actix_web::App;
 use actix_web::HttpServer;
 use brace_config::{load, save};
-use brace_db::Database;
+use brace_db::schema::migrate::migrate;
+use brace_db::{Database, Migration};
+use brace_mail::config::MailTransportConfig;
+use brace_mail::Mailer;
 use brace_theme::config::ThemeConfig;
 use brace_web::render::{Renderer, RendererConfig};
-use failure::Error;
-use log::info;
+use brace_web_auth::config::{AuthConfig, Jwt};
+use brace_web_auth::session::SessionIdentityPolicy;
+use brace_web_form::CsrfProtection;
+use failure::{format_err, Error};
+use log::{info, warn};
 
 use self::config::AppConfig;
 use self::route::resources::ThemeResources;
@@ -33,11 +39,73 @@ pub fn init(config: AppConfig, path: &Path) -> Result<(), Error> {
     Ok(())
 }
 
-pub fn run(config: AppConfig, path: &Path) -> Result<(), Error> {
+pub fn migrations() -> Vec<Migration> {
+    let mut migrations = Vec::new();
+
+    migrations.extend(brace_web_auth::schema::migrations());
+    migrations.extend(brace_web_page::schema::migrations());
+
+    migrations
+}
+
+pub fn run(mut config: AppConfig, path: &Path) -> Result<(), Error> {
     logger::init(&config, path)?;
 
-    let system = System::new("brace");
+    if !config.auth.has_secret() {
+        if !config.web.dev {
+            return Err(format_err!(
+                "No token signing secret configured, set auth.secret or auth.key_file"
+            ));
+        }
+
+        warn!("No token signing secret configured, using a temporary secret");
+        config.auth.secret = AuthConfig::generate().secret;
+    }
+
+    if !config.auth.cookie.has_key() {
+        if !config.web.dev {
+            return Err(format_err!(
+                "No cookie key configured, set auth.cookie.key or auth.cookie.key_file"
+            ));
+        }
+
+        warn!("No cookie key configured, using a temporary key");
+        config.auth.cookie.key = AuthConfig::generate().cookie.key;
+    }
+
+    if config.web.url.is_none() {
+        if !config.web.dev {
+            return Err(format_err!(
+                "No site url configured, set web.url to the public address of the site"
+            ));
+        }
+
+        warn!("No site url configured, links in emails will use the listening address");
+    }
+
+    if config.mail.transport == MailTransportConfig::Log {
+        if !config.web.dev {
+            return Err(format_err!(
+                "No mail transport configured, set mail.transport to smtp, sendmail or file"
+            ));
+        }
+
+        warn!("No mail transport configured, emails will only be written to the log");
+    }
+
+    let jwt = Jwt::from_config(&config.auth)?;
+    let key = config.auth.cookie.key()?;
+
+    let mut system = System::new("brace");
     let database = Database::from_config(config.database.clone())?;
+    let mailer = Mailer::from_config(config.mail.clone())?;
+
+    if config.database.migrate {
+        for migration in system.block_on(migrate(&database, migrations()))? {
+            info!("Applied migration {}/{}", migration.module, migration.name);
+        }
+    }
+
     let renderer = Renderer::from_config(RendererConfig {
         themes: config.themes.clone(),
     })?;
@@ -59,11 +127,15 @@ pub fn run(config: AppConfig, path: &Path) -> Result<(), Error> {
             .data(config.clone())
             .data(database.clone())
             .data(renderer.clone())
+            .data(jwt.clone())
+            .data(mailer.clone())
+            .data(config.web.clone())
+            .data(config.auth.clone())
             .wrap(Logger::new(&format))
+            .wrap(CsrfProtection::new(&key).secure(config.auth.cookie.secure))
             .wrap(IdentityService::new(
-                CookieIdentityPolicy::new(&[0; 32])
-                    .name("auth")
-                    .secure(false),
+                SessionIdentityPolicy::new(config.auth.cookie.policy(&key), database.clone())
+                    .forwarded(config.auth.lockout.forwarded),
             ))
             .service(resource("/").route(get().to_async(route::index::get)))
             .service(resource("/themes").route(get().to_async(route::themes::get)))
@@ -101,3 +101,69 @@ fn test_command_web_missing() {
         .code(1)
         .stderr(CMD_WEB_MISSING_ERR);
 }
+
+static CMD_DB_INVALID_ERR: &'static str = "\
+error: Invalid subcommand: invalid
+
+USAGE:
+    brace db [SUBCOMMAND]
+";
+
+#[test]
+fn test_command_db_invalid() {
+    Command::cargo_bin("brace")
+        .unwrap()
+        .args(&["db", "invalid"])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(CMD_DB_INVALID_ERR);
+}
+
+static CMD_DB_MISSING_ERR: &'static str = "\
+error: Expected a valid subcommand
+
+USAGE:
+    brace db [SUBCOMMAND]
+";
+
+#[test]
+fn test_command_db_missing() {
+    Command::cargo_bin("brace")
+        .unwrap()
+        .arg("db")
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(CMD_DB_MISSING_ERR);
+}
+
+static CMD_DB_CONFIG_ERR: &'static str = "\
+error: No configuration file specified
+";
+
+#[test]
+fn test_command_db_status_without_config() {
+    Command::cargo_bin("brace")
+        .unwrap()
+        .args(&["db", "status"])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(CMD_DB_CONFIG_ERR);
+}
+
+static CMD_DB_MODULE_ERR: &'static str = "\
+error: Invalid module: invalid
+";
+
+#[test]
+fn test_command_db_migrate_invalid_module() {
+    Command::cargo_bin("brace")
+        .unwrap()
+        .args(&["db", "migrate", "--module", "invalid"])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(CMD_DB_MODULE_ERR);
+}
@@ -4,6 +4,8 @@ use std::time::Duration;
 
 use assert_cmd::prelude::*;
 use brace::config::AppConfig;
+use brace_mail::config::MailTransportConfig;
+use brace_web_auth::config::AuthConfig;
 use tempfile::TempDir;
 
 #[test]
@@ -57,6 +59,11 @@ fn test_web_server_with_config() {
     let mut config = AppConfig::default();
 
     config.web.port = 8002;
+    config.web.url = Some("http://127.0.0.1:8002".to_string());
+    config.auth = AuthConfig::generate();
+    config.mail.transport = MailTransportConfig::File {
+        path: path.join("mail"),
+    };
 
     brace::init(config, path).unwrap();
 
@@ -83,3 +90,25 @@ fn test_web_server_with_config() {
     assert_eq!(res2.unwrap().status(), 500);
     assert_eq!(res3.unwrap().status(), 404);
 }
+
+#[test]
+fn test_web_server_without_secret() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path();
+    let mut config = AppConfig::default();
+
+    config.web.port = 8003;
+
+    brace::init(config, path).unwrap();
+
+    Command::cargo_bin("brace")
+        .unwrap()
+        .args(&[
+            "web",
+            "run",
+            "--config",
+            path.join("config.toml").to_str().unwrap(),
+        ])
+        .assert()
+        .failure();
+}